@@ -0,0 +1,229 @@
+use crate::audio::flac::encode_flac;
+use crate::audio::helpers::TARGET_SAMPLE_RATE;
+use crate::audio::types::{ArchivedFile, AudioState};
+use anyhow::{Context, Result};
+use log::{debug, warn};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tauri::{AppHandle, Manager};
+
+/// Unlinked files younger than this are left alone, their history entry may still
+/// be on its way.
+const UNLINKED_GRACE_PERIOD: Duration = Duration::from_secs(60);
+
+/// Archived recordings live in the app data dir, unlike the temporary WAV files
+/// removed by `cleanup_recordings`.
+pub fn ensure_archive_dir(app: &AppHandle) -> Result<PathBuf> {
+    let archive = app
+        .path()
+        .app_data_dir()
+        .context("Failed to resolve app data dir")?
+        .join("archive");
+
+    if !archive.exists() {
+        std::fs::create_dir_all(&archive).context("Failed to create archive dir")?;
+    }
+
+    Ok(archive)
+}
+
+/// Compresses 16 kHz `samples` into the archive in the background and returns the
+/// file name to store on the history entry.
+pub fn archive_recording(app: &AppHandle, samples: Vec<f32>) -> String {
+    let millis = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    let file_name = format!("murmure-{}.flac", millis);
+
+    app.state::<AudioState>()
+        .archive_writes
+        .lock()
+        .insert(file_name.clone());
+
+    let app = app.clone();
+    let name = file_name.clone();
+    std::thread::spawn(move || {
+        if let Err(e) = write_archive_file(&app, &name, &samples) {
+            warn!("Failed to archive recording: {:#}", e);
+        }
+        // Removed while it was being written
+        if !app
+            .state::<AudioState>()
+            .archive_writes
+            .lock()
+            .remove(&name)
+        {
+            remove_archived_file(&app, &name);
+        }
+        if let Err(e) = enforce_retention(&app) {
+            warn!("Failed to apply archive retention: {:#}", e);
+        }
+    });
+
+    file_name
+}
+
+/// Full path of an archived file, if it has not been removed by the retention policy.
+pub fn archived_file_path(app: &AppHandle, file_name: &str) -> Result<Option<PathBuf>> {
+    let path = ensure_archive_dir(app)?.join(file_name);
+    Ok(path.is_file().then_some(path))
+}
+
+/// Deletes an archived file, e.g. when the history is cleared. A file still being
+/// written is deleted by its writer once done.
+pub fn remove_archived_file(app: &AppHandle, file_name: &str) {
+    if app
+        .state::<AudioState>()
+        .archive_writes
+        .lock()
+        .remove(file_name)
+    {
+        return;
+    }
+
+    match archived_file_path(app, file_name) {
+        Ok(Some(path)) => {
+            if let Err(e) = std::fs::remove_file(&path) {
+                warn!("Failed to delete {}: {}", path.display(), e);
+            }
+        }
+        Ok(None) => {}
+        Err(e) => warn!("Failed to locate archived file {}: {:#}", file_name, e),
+    }
+}
+
+fn write_archive_file(app: &AppHandle, file_name: &str, samples: &[f32]) -> Result<()> {
+    let path = ensure_archive_dir(app)?.join(file_name);
+    let flac = encode_flac(samples, TARGET_SAMPLE_RATE as u32);
+    std::fs::write(&path, &flac).context("Failed to write archive file")?;
+    debug!(
+        "Archived {} ({} bytes for {} samples)",
+        path.display(),
+        flac.len(),
+        samples.len()
+    );
+    Ok(())
+}
+
+/// Deletes archived files no longer linked to the history, those older than
+/// `archive_max_age_days`, then the oldest ones until the archive fits in
+/// `archive_max_size_mb`. Files still being written are skipped.
+pub fn enforce_retention(app: &AppHandle) -> Result<()> {
+    let settings = crate::settings::load_settings(app);
+    let max_age = settings
+        .archive_max_age_days
+        .map(|days| Duration::from_secs(days as u64 * 24 * 60 * 60));
+    let max_bytes = settings.archive_max_size_mb.map(|mb| mb * 1024 * 1024);
+
+    let referenced: HashSet<String> = crate::history::archived_files(app)?.into_iter().collect();
+    let writing = app.state::<AudioState>().archive_writes.lock().clone();
+
+    let entries =
+        std::fs::read_dir(ensure_archive_dir(app)?).context("Failed to read archive directory")?;
+    let files = entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            let metadata = entry.metadata().ok()?;
+            let candidate =
+                metadata.is_file() && is_archived_file(&path) && !writing.contains(&name);
+            candidate.then(|| ArchivedFile {
+                path,
+                modified: metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+                size: metadata.len(),
+                referenced: referenced.contains(&name),
+            })
+        })
+        .collect();
+
+    let mut removed = Vec::new();
+    for path in files_to_prune(files, SystemTime::now(), max_age, max_bytes) {
+        match std::fs::remove_file(&path) {
+            Ok(()) => removed.extend(
+                path.file_name()
+                    .map(|name| name.to_string_lossy().to_string()),
+            ),
+            Err(e) => warn!("Failed to delete {}: {}", path.display(), e),
+        }
+    }
+
+    crate::history::forget_archived_files(app, &removed)
+}
+
+fn files_to_prune(
+    mut files: Vec<ArchivedFile>,
+    now: SystemTime,
+    max_age: Option<Duration>,
+    max_bytes: Option<u64>,
+) -> Vec<PathBuf> {
+    // Newest first, so everything past the age or size budget is at the end
+    files.sort_by(|a, b| b.modified.cmp(&a.modified));
+
+    let mut total = 0u64;
+    files
+        .into_iter()
+        .filter(|file| {
+            let age = now.duration_since(file.modified).unwrap_or_default();
+            if !file.referenced {
+                return age > UNLINKED_GRACE_PERIOD;
+            }
+            total += file.size;
+            let too_old = max_age.is_some_and(|max| age > max);
+            let over_budget = max_bytes.is_some_and(|max| total > max);
+            too_old || over_budget
+        })
+        .map(|file| file.path)
+        .collect()
+}
+
+fn is_archived_file(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "flac")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_files_to_prune() {
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(100 * 86400);
+        let file = |name: &str, days_ago: u64, size: u64| ArchivedFile {
+            path: PathBuf::from(name),
+            modified: now - Duration::from_secs(days_ago * 86400),
+            size,
+            referenced: true,
+        };
+        let files = || vec![file("old", 40, 10), file("new", 1, 10), file("mid", 10, 10)];
+
+        let by_age = files_to_prune(files(), now, Some(Duration::from_secs(30 * 86400)), None);
+        assert_eq!(by_age, vec![PathBuf::from("old")]);
+
+        let by_size = files_to_prune(files(), now, None, Some(15));
+        assert_eq!(by_size, vec![PathBuf::from("mid"), PathBuf::from("old")]);
+
+        assert!(files_to_prune(files(), now, None, None).is_empty());
+    }
+
+    #[test]
+    fn test_files_to_prune_unlinked() {
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(100 * 86400);
+        let file = |name: &str, secs_ago: u64, referenced: bool| ArchivedFile {
+            path: PathBuf::from(name),
+            modified: now - Duration::from_secs(secs_ago),
+            size: 10,
+            referenced,
+        };
+        let files = vec![
+            file("lost", 3600, false),
+            file("fresh", 5, false),
+            file("linked", 3600, true),
+        ];
+
+        // Unlinked files neither stay nor count towards the size budget
+        let pruned = files_to_prune(files, now, None, Some(10));
+        assert_eq!(pruned, vec![PathBuf::from("lost")]);
+    }
+}
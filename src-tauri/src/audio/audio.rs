@@ -1,15 +1,13 @@
-use crate::audio::helpers::{cleanup_recordings, ensure_recordings_dir, generate_unique_wav_name};
-use crate::audio::pipeline::process_recording;
+use crate::audio::helpers::{
+    cleanup_recordings, ensure_recordings_dir, generate_unique_wav_name, remove_recording,
+};
+use crate::audio::pipeline::{process_recording, reprocess_recording};
 use crate::audio::recorder::AudioRecorder;
-use crate::audio::types::{AudioState, RecordingMode};
+use crate::audio::types::{AudioState, DiscardReason, RecordedAudio, RecordingMode};
 use crate::clipboard;
-use crate::engine::transcription_engine::TranscriptionEngine;
-use crate::engine::{ParakeetEngine, ParakeetModelParams};
-use crate::model::Model;
 use crate::overlay::overlay;
 use anyhow::Result;
 use log::{debug, error, info, warn};
-use std::sync::Arc;
 use tauri::{AppHandle, Emitter, Manager};
 
 pub fn record_audio(app: &AppHandle, mode: RecordingMode) {
@@ -33,22 +31,28 @@ fn internal_record_audio(app: &AppHandle) {
         return;
     }
 
-    let recordings_dir = match ensure_recordings_dir(app) {
-        Ok(dir) => dir,
-        Err(e) => {
-            error!("Failed to initialize recordings directory: {}", e);
-            return;
-        }
-    };
+    // In-memory capture never writes the audio to disk
+    let file_path = if crate::settings::load_settings(app).in_memory_capture {
+        *state.current_file_name.lock() = None;
+        None
+    } else {
+        let recordings_dir = match ensure_recordings_dir(app) {
+            Ok(dir) => dir,
+            Err(e) => {
+                error!("Failed to initialize recordings directory: {}", e);
+                return;
+            }
+        };
 
-    let file_name = generate_unique_wav_name();
-    let file_path = recordings_dir.join(&file_name);
-    *state.current_file_name.lock() = Some(file_name.clone());
+        let file_name = generate_unique_wav_name();
+        *state.current_file_name.lock() = Some(file_name.clone());
+        Some(recordings_dir.join(&file_name))
+    };
 
     // Get the shared limit_reached flag
     let limit_reached = state.get_limit_reached_arc();
 
-    match AudioRecorder::new(app.clone(), &file_path, limit_reached) {
+    match AudioRecorder::new(app.clone(), file_path.as_deref(), limit_reached) {
         Ok(mut recorder) => {
             if let Err(e) = recorder.start() {
                 error!("Failed to start recording: {}", e);
@@ -91,58 +95,136 @@ pub fn stop_recording_with_options(app: &AppHandle, _unused: bool) -> Option<std
     let invert_signal = state.invert_enter_signal.clone();
 
     // Stop recorder immediately
-    {
+    let (recorded, preroll_ms) = {
         let mut recorder_guard = state.recorder.lock();
-        if let Some(recorder) = recorder_guard.as_mut() {
-            if let Err(e) = recorder.stop() {
+        let recorded = match recorder_guard.as_mut().map(|recorder| recorder.stop()) {
+            Some(Ok(recorded)) => recorded,
+            Some(Err(e)) => {
                 error!("Failed to stop recorder: {}", e);
+                None
             }
-        }
+            None => None,
+        };
+        let preroll_ms = recorder_guard
+            .as_mut()
+            .map_or(0, |recorder| recorder.take_preroll_ms());
         *recorder_guard = None;
-    }
-
-    let file_name_opt = state.current_file_name.lock().take();
+        (recorded, preroll_ms)
+    };
+    state.current_file_name.lock().take();
 
-    if let Some(file_name) = file_name_opt {
-        let path = ensure_recordings_dir(app)
-            .map(|dir| dir.join(&file_name))
-            .ok();
+    if let Some(audio) = recorded {
+        let path = match &audio {
+            RecordedAudio::File(path) => Some(path.clone()),
+            RecordedAudio::Samples { .. } => None,
+        };
 
         // Reset level immediately; keep overlay visible during processing for feedback animation.
         let _ = app.emit("mic-level", 0.0f32);
         let _ = app.emit("overlay-mode", "standard");
         let overlay_mode = crate::settings::load_settings(app).overlay_mode;
 
-        if let Some(p) = path.clone() {
-            let app_clone = app.clone();
-            // Wait briefly for a possible second click before processing
-            std::thread::spawn(move || {
-                std::thread::sleep(std::time::Duration::from_millis(350));
-                let invert = invert_signal.load(std::sync::atomic::Ordering::SeqCst);
-                info!("Processing recording (invert_send_enter={})", invert);
-                match process_recording(&app_clone, &p) {
-                    Ok(final_text) => {
-                        if let Err(e) = write_transcription(&app_clone, &final_text, invert) {
-                            error!("Failed to use clipboard: {}", e);
-                        }
+        let app_clone = app.clone();
+        let mode = state.get_recording_mode();
+        let previous_chunk = state.pending_chunk.lock().take();
+        // Wait briefly for a possible second click before processing
+        std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_millis(350));
+            if let Some(previous_chunk) = previous_chunk {
+                let _ = previous_chunk.join();
+            }
+            let invert = invert_signal.load(std::sync::atomic::Ordering::SeqCst);
+            info!("Processing recording (invert_send_enter={})", invert);
+            match process_recording(&app_clone, audio, mode, preroll_ms, false) {
+                Ok(Some(final_text)) => {
+                    if let Err(e) = write_transcription(&app_clone, &final_text, invert) {
+                        error!("Failed to use clipboard: {}", e);
                     }
-                    Err(e) => {
-                        error!("Processing failed: {}", e);
-                        if overlay_mode.as_str() == "recording" {
-                            overlay::hide_recording_overlay(&app_clone);
-                        }
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    error!("Processing failed: {}", e);
+                    if overlay_mode.as_str() == "recording" {
+                        overlay::hide_recording_overlay(&app_clone);
                     }
                 }
-            });
-        }
+            }
+        });
 
         return path;
     } else {
-        debug!("Recording stopped (no active file)");
+        debug!("Recording stopped (no active recording)");
     }
     None
 }
 
+/// Transcribes and pastes what was recorded so far when a recording reaches its
+/// limit, while the recording continues into a new chunk.
+pub fn rollover_recording(app: &AppHandle) {
+    let state = app.state::<AudioState>();
+    let next_file_name = state
+        .current_file_name
+        .lock()
+        .is_some()
+        .then(generate_unique_wav_name);
+    let next_path = match &next_file_name {
+        Some(file_name) => match ensure_recordings_dir(app) {
+            Ok(dir) => Some(dir.join(file_name)),
+            Err(e) => {
+                error!("Failed to initialize recordings directory: {}", e);
+                return;
+            }
+        },
+        None => None,
+    };
+
+    let result = state.recorder.lock().as_mut().map(|recorder| {
+        recorder
+            .rollover(next_path.as_deref())
+            .map(|chunk| (chunk, recorder.take_preroll_ms()))
+    });
+    let (chunk, preroll_ms) = match result {
+        Some(Ok((Some(chunk), preroll_ms))) => (chunk, preroll_ms),
+        Some(Ok((None, _))) | None => return,
+        Some(Err(e)) => {
+            error!("Failed to roll over recording: {:#}", e);
+            crate::shortcuts::force_stop_recording(app);
+            return;
+        }
+    };
+    if next_file_name.is_some() {
+        *state.current_file_name.lock() = next_file_name;
+    }
+    info!("Recording limit reached, transcribing this chunk and recording the next one");
+
+    let mut pending_chunk = state.pending_chunk.lock();
+    let previous_chunk = pending_chunk.take();
+    let app_clone = app.clone();
+    let mode = state.get_recording_mode();
+    *pending_chunk = Some(std::thread::spawn(move || {
+        if let Some(previous_chunk) = previous_chunk {
+            let _ = previous_chunk.join();
+        }
+        let chunk_path = match &chunk {
+            RecordedAudio::File(path) => Some(path.clone()),
+            RecordedAudio::Samples { .. } => None,
+        };
+        match process_recording(&app_clone, chunk, mode, preroll_ms, true) {
+            Ok(Some(text)) => {
+                if let Err(e) = write_chunk_transcription(&app_clone, &text) {
+                    error!("Failed to use clipboard: {}", e);
+                }
+            }
+            Ok(None) => {}
+            Err(e) => error!("Processing failed: {}", e),
+        }
+        // Only this chunk, the next one is still being written to the same directory
+        if let Some(path) = chunk_path {
+            remove_recording(&path);
+        }
+    }));
+}
+
 pub fn show_invert_feedback(app: &AppHandle) {
     let s = crate::settings::load_settings(app);
     let mode_str = if !s.auto_send_enter {
@@ -176,7 +258,7 @@ pub fn cancel_recording(app: &AppHandle) {
     debug!("Cancelling audio recording...");
     let state = app.state::<AudioState>();
 
-    // Stop recorder
+    // Stop recorder, audio captured in memory is dropped with it
     {
         let mut recorder_guard = state.recorder.lock();
         if let Some(recorder) = recorder_guard.as_mut() {
@@ -230,6 +312,79 @@ pub fn cancel_recording(app: &AppHandle) {
     });
 }
 
+/// Drops a recording without transcribing it, with the cancel sound and animation.
+pub fn discard_recording(app: &AppHandle, reason: DiscardReason) {
+    info!("Recording discarded: {:?}", reason);
+    crate::audio::sound::play_sound(app, crate::audio::sound::Sound::CancelRecording);
+    let _ = app.emit("recording-discarded", reason);
+
+    if let Err(e) = cleanup_recordings(app) {
+        error!("Failed to cleanup recordings: {}", e);
+    }
+
+    // The overlay stayed visible during processing, hide it once the animation is over
+    if crate::settings::load_settings(app).overlay_mode.as_str() == "recording" {
+        let app_clone = app.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_millis(700));
+            overlay::hide_recording_overlay(&app_clone);
+        });
+    }
+}
+
+/// Runs the last recording through the pipeline again in `mode` and pastes the result.
+pub fn reprocess_last_recording(app: &AppHandle, mode: RecordingMode) {
+    if app
+        .state::<AudioState>()
+        .recent_recordings
+        .lock()
+        .is_empty()
+    {
+        warn!("No recording to reprocess");
+        return;
+    }
+
+    let app_clone = app.clone();
+    std::thread::spawn(move || {
+        info!("Reprocessing last recording in {:?} mode", mode);
+        match reprocess_recording(&app_clone, 0, mode, None) {
+            Ok(text) => {
+                if let Err(e) = write_transcription(&app_clone, &text, false) {
+                    error!("Failed to use clipboard: {}", e);
+                }
+            }
+            Err(e) => error!("Reprocessing failed: {}", e),
+        }
+    });
+}
+
+/// Pauses or resumes the current recording and tells the overlay.
+/// Returns false when nothing is recording or the state is unchanged.
+pub fn set_recording_paused(app: &AppHandle, paused: bool) -> bool {
+    let state = app.state::<AudioState>();
+    let changed = match state.recorder.lock().as_ref() {
+        Some(recorder) if paused => recorder.pause(),
+        Some(recorder) => recorder.resume(),
+        None => false,
+    };
+
+    if changed {
+        info!("Recording {}", if paused { "paused" } else { "resumed" });
+        let _ = app.emit("recording-paused", paused);
+    }
+    changed
+}
+
+pub fn toggle_pause_recording(app: &AppHandle) {
+    let is_paused = app
+        .state::<AudioState>()
+        .recorder
+        .lock()
+        .as_ref()
+        .is_some_and(|recorder| recorder.is_paused());
+    set_recording_paused(app, !is_paused);
+}
+
 pub fn write_transcription(
     app: &AppHandle,
     transcription: &str,
@@ -286,33 +441,22 @@ pub fn write_transcription(
     Ok(())
 }
 
-pub fn write_last_transcription(app: &AppHandle, transcription: &str) -> Result<()> {
-    if let Err(e) = clipboard::paste_last_transcript(transcription, app) {
-        error!("Failed to paste last transcription: {}", e);
+/// Pastes the transcription of a rolled-over chunk. The recording goes on, so the
+/// overlay stays, no Enter is sent and the recordings directory is left alone.
+pub fn write_chunk_transcription(app: &AppHandle, transcription: &str) -> Result<()> {
+    if let Err(e) = clipboard::paste_with_enter_override(transcription, app, false) {
+        error!("Failed to paste text: {}", e);
     }
 
-    debug!("Last transcription written to clipboard {}", transcription);
+    debug!("Chunk transcription written to clipboard {}", transcription);
     Ok(())
 }
 
-pub fn preload_engine(app: &AppHandle) -> Result<()> {
-    let state = app.state::<AudioState>();
-    let mut engine = state.engine.lock();
-
-    if engine.is_none() {
-        let model = app.state::<Arc<Model>>();
-        let model_path = model
-            .get_model_path()
-            .map_err(|e| anyhow::anyhow!("Failed to get model path: {}", e))?;
-
-        let mut new_engine = ParakeetEngine::new();
-        new_engine
-            .load_model_with_params(&model_path, ParakeetModelParams::int8())
-            .map_err(|e| anyhow::anyhow!("Failed to load model: {}", e))?;
-
-        *engine = Some(new_engine);
-        info!("Model loaded and cached in memory");
+pub fn write_last_transcription(app: &AppHandle, transcription: &str) -> Result<()> {
+    if let Err(e) = clipboard::paste_last_transcript(transcription, app) {
+        error!("Failed to paste last transcription: {}", e);
     }
 
+    debug!("Last transcription written to clipboard {}", transcription);
     Ok(())
 }
@@ -0,0 +1,123 @@
+use crate::audio::types::{ChannelMixer, ChannelStrategy};
+
+/// The loudest channel is only replaced by one at least this much louder (about 3 dB),
+/// so the selection does not flip between channels of similar level.
+const LOUDEST_SWITCH_RATIO: f32 = 2.0;
+/// Time constant of the per-channel energy used to find the loudest channel.
+const LOUDEST_WINDOW_MS: f32 = 500.0;
+
+impl ChannelStrategy {
+    /// Channels a capture config needs for the strategy to have any effect.
+    pub fn min_channels(self) -> u16 {
+        match self {
+            ChannelStrategy::Average | ChannelStrategy::Left => 1,
+            ChannelStrategy::Right | ChannelStrategy::Loudest => 2,
+            ChannelStrategy::Channel(index) => index.saturating_add(1),
+        }
+    }
+}
+
+impl ChannelMixer {
+    pub fn new(strategy: ChannelStrategy, channels: usize, sample_rate: usize) -> Self {
+        let channels = channels.max(1);
+        let window = (LOUDEST_WINDOW_MS / 1000.0 * sample_rate as f32).max(1.0);
+        Self {
+            strategy,
+            channels,
+            energy: vec![0.0; channels],
+            decay: 1.0 - 1.0 / window,
+            loudest: 0,
+        }
+    }
+
+    pub fn mix(&mut self, frame: impl Iterator<Item = f32>) -> f32 {
+        let last = self.channels - 1;
+        match self.strategy {
+            ChannelStrategy::Average => frame.sum::<f32>() / self.channels as f32,
+            ChannelStrategy::Left => frame.take(1).sum(),
+            ChannelStrategy::Right => frame.skip(1.min(last)).take(1).sum(),
+            ChannelStrategy::Channel(index) => frame.skip((index as usize).min(last)).take(1).sum(),
+            ChannelStrategy::Loudest => {
+                let mut selected = 0.0;
+                for (channel, sample) in frame.take(self.channels).enumerate() {
+                    self.energy[channel] = self.energy[channel] * self.decay + sample * sample;
+                    if channel == self.loudest {
+                        selected = sample;
+                    }
+                }
+
+                let (loudest, energy) =
+                    self.energy
+                        .iter()
+                        .enumerate()
+                        .fold((0, 0.0), |best, (channel, &energy)| {
+                            if energy > best.1 {
+                                (channel, energy)
+                            } else {
+                                best
+                            }
+                        });
+                if energy > self.energy[self.loudest] * LOUDEST_SWITCH_RATIO {
+                    self.loudest = loudest;
+                }
+                selected
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_RATE: usize = 16000;
+
+    fn mix_all(mixer: &mut ChannelMixer, frames: &[[f32; 2]]) -> Vec<f32> {
+        frames
+            .iter()
+            .map(|frame| mixer.mix(frame.iter().copied()))
+            .collect()
+    }
+
+    #[test]
+    fn test_fixed_channel_strategies() {
+        let frame = [0.2, 0.6];
+        let mix = |strategy| ChannelMixer::new(strategy, 2, SAMPLE_RATE).mix(frame.into_iter());
+
+        assert!((mix(ChannelStrategy::Average) - 0.4).abs() < 1e-6);
+        assert_eq!(mix(ChannelStrategy::Left), 0.2);
+        assert_eq!(mix(ChannelStrategy::Right), 0.6);
+        assert_eq!(mix(ChannelStrategy::Channel(1)), 0.6);
+        // Channels missing from the stream fall back to the last one
+        assert_eq!(mix(ChannelStrategy::Channel(5)), 0.6);
+
+        let mut mono = ChannelMixer::new(ChannelStrategy::Right, 1, SAMPLE_RATE);
+        assert_eq!(mono.mix([0.3].into_iter()), 0.3);
+    }
+
+    #[test]
+    fn test_loudest_channel_follows_the_speaker() {
+        let mut mixer = ChannelMixer::new(ChannelStrategy::Loudest, 2, SAMPLE_RATE);
+
+        let right_speaking = vec![[0.01, 0.5]; SAMPLE_RATE / 2];
+        let output = mix_all(&mut mixer, &right_speaking);
+        assert_eq!(*output.last().unwrap(), 0.5);
+
+        // A short burst on the other channel does not steal the selection
+        let click = vec![[0.9, 0.01]; SAMPLE_RATE / 100];
+        mix_all(&mut mixer, &click);
+        let output = mix_all(&mut mixer, &[[0.2, 0.4]]);
+        assert_eq!(output[0], 0.4);
+
+        let left_speaking = vec![[0.5, 0.01]; SAMPLE_RATE];
+        let output = mix_all(&mut mixer, &left_speaking);
+        assert_eq!(*output.last().unwrap(), 0.5);
+    }
+
+    #[test]
+    fn test_min_channels() {
+        assert_eq!(ChannelStrategy::Average.min_channels(), 1);
+        assert_eq!(ChannelStrategy::Loudest.min_channels(), 2);
+        assert_eq!(ChannelStrategy::Channel(3).min_channels(), 4);
+    }
+}
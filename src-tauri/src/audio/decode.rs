@@ -0,0 +1,233 @@
+use crate::audio::helpers::TARGET_SAMPLE_RATE;
+use crate::audio::resample::resample;
+use crate::audio::types::{ChannelMixer, ChannelStrategy, DecodeError, ResampleQuality};
+use log::warn;
+use std::fs::File;
+use std::path::Path;
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::{Decoder, DecoderOptions, CODEC_TYPE_NULL, CODEC_TYPE_OPUS};
+use symphonia::core::errors::Error as SymphoniaError;
+use symphonia::core::formats::{FormatOptions, FormatReader, Packet};
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+
+impl DecodeError {
+    /// True when the file itself is at fault rather than the machine reading it.
+    pub fn is_invalid_input(&self) -> bool {
+        !matches!(self, DecodeError::Io(_))
+    }
+}
+
+/// Decodes WAV (any bit depth, integer or float), FLAC, MP3, OGG/Vorbis
+/// and M4A/AAC files to 16 kHz mono samples, channels combined according to `channels`.
+pub fn decode_audio_file(
+    path: &Path,
+    channels: ChannelStrategy,
+    quality: ResampleQuality,
+) -> Result<Vec<f32>, DecodeError> {
+    let source = MediaSourceStream::new(Box::new(File::open(path)?), Default::default());
+    let mut hint = Hint::new();
+    if let Some(extension) = path.extension().and_then(|ext| ext.to_str()) {
+        hint.with_extension(extension);
+    }
+
+    let probed = symphonia::default::get_probe()
+        .format(
+            &hint,
+            source,
+            &FormatOptions::default(),
+            &MetadataOptions::default(),
+        )
+        .map_err(|e| match e {
+            // Running out of data while looking for a known header is not an I/O failure
+            SymphoniaError::IoError(e) if e.kind() != std::io::ErrorKind::UnexpectedEof => {
+                DecodeError::Io(e)
+            }
+            e => DecodeError::UnsupportedFormat(e.to_string()),
+        })?;
+    let mut format = probed.format;
+    let (mut track_id, sample_rate, mut decoder) = open_audio_track(format.as_ref())?;
+
+    let mut mono = Vec::new();
+    let mut interleaved = Vec::new();
+    let mut mixer: Option<(usize, ChannelMixer)> = None;
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            // End of stream
+            Err(SymphoniaError::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+                break
+            }
+            // The tracks changed, e.g. the next stream of a chained OGG file
+            Err(SymphoniaError::ResetRequired) => {
+                let (next_track_id, next_sample_rate, next_decoder) =
+                    open_audio_track(format.as_ref())?;
+                if next_sample_rate != sample_rate {
+                    return Err(DecodeError::UnsupportedFormat(format!(
+                        "sample rate changes from {} to {} Hz mid-stream",
+                        sample_rate, next_sample_rate
+                    )));
+                }
+                (track_id, decoder) = (next_track_id, next_decoder);
+                continue;
+            }
+            Err(e) => return Err(DecodeError::Corrupt(e.to_string())),
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+
+        let Some(channel_count) = decode_packet(decoder.as_mut(), &packet, &mut interleaved)?
+        else {
+            continue;
+        };
+
+        // Kept across packets so the loudest channel is tracked over the whole file
+        let mixer = match &mut mixer {
+            Some((count, mixer)) if *count == channel_count => mixer,
+            mixer_slot => {
+                let new_mixer = ChannelMixer::new(channels, channel_count, sample_rate);
+                &mut mixer_slot.insert((channel_count, new_mixer)).1
+            }
+        };
+        mono.extend(
+            interleaved
+                .chunks_exact(channel_count)
+                .map(|frame| mixer.mix(frame.iter().copied())),
+        );
+    }
+
+    if sample_rate == TARGET_SAMPLE_RATE {
+        return Ok(mono);
+    }
+    Ok(resample(&mono, sample_rate, TARGET_SAMPLE_RATE, quality))
+}
+
+/// Id, sample rate and decoder of the first audio track.
+fn open_audio_track(
+    format: &dyn FormatReader,
+) -> Result<(u32, usize, Box<dyn Decoder>), DecodeError> {
+    let track = format
+        .tracks()
+        .iter()
+        .find(|track| track.codec_params.codec != CODEC_TYPE_NULL)
+        .ok_or(DecodeError::NoAudioTrack)?;
+
+    // Symphonia has no Opus decoder and libopus would need a C toolchain to build
+    if track.codec_params.codec == CODEC_TYPE_OPUS {
+        return Err(DecodeError::UnsupportedCodec(
+            "Opus, convert the file to FLAC, WAV or MP3 first".to_string(),
+        ));
+    }
+
+    let sample_rate = track
+        .codec_params
+        .sample_rate
+        .ok_or(DecodeError::UnknownSampleRate)? as usize;
+    let decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &DecoderOptions::default())
+        .map_err(|e| DecodeError::UnsupportedCodec(e.to_string()))?;
+    Ok((track.id, sample_rate, decoder))
+}
+
+/// Replaces `interleaved` with the samples of `packet` and returns their channel
+/// count, `None` when the packet is damaged and skipped.
+fn decode_packet(
+    decoder: &mut dyn Decoder,
+    packet: &Packet,
+    interleaved: &mut Vec<f32>,
+) -> Result<Option<usize>, DecodeError> {
+    interleaved.clear();
+    let decoded = match decoder.decode(packet) {
+        Ok(decoded) => decoded,
+        // A damaged packet only loses a few milliseconds of audio
+        Err(SymphoniaError::DecodeError(e)) => {
+            warn!("Skipping undecodable audio packet: {}", e);
+            return Ok(None);
+        }
+        Err(e) => return Err(DecodeError::Corrupt(e.to_string())),
+    };
+
+    let spec = *decoded.spec();
+    let mut buffer = SampleBuffer::<f32>::new(decoded.capacity() as u64, spec);
+    buffer.copy_interleaved_ref(decoded);
+    interleaved.extend_from_slice(buffer.samples());
+    Ok(Some(spec.channels.count().max(1)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hound::{SampleFormat, WavSpec, WavWriter};
+
+    fn temp_wav(
+        name: &str,
+        spec: WavSpec,
+        write: impl FnOnce(&mut WavWriter<std::io::BufWriter<File>>),
+    ) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "murmure-decode-{}-{}.wav",
+            name,
+            std::process::id()
+        ));
+        let mut writer = WavWriter::create(&path, spec).unwrap();
+        write(&mut writer);
+        writer.finalize().unwrap();
+        path
+    }
+
+    #[test]
+    fn test_decode_float_stereo_wav() {
+        let spec = WavSpec {
+            channels: 2,
+            sample_rate: 16000,
+            bits_per_sample: 32,
+            sample_format: SampleFormat::Float,
+        };
+        let path = temp_wav("float", spec, |writer| {
+            for _ in 0..1600 {
+                writer.write_sample(0.5f32).unwrap();
+                writer.write_sample(-0.25f32).unwrap();
+            }
+        });
+
+        let samples =
+            decode_audio_file(&path, ChannelStrategy::Average, ResampleQuality::Fast).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(samples.len(), 1600);
+        assert!(samples.iter().all(|&s| (s - 0.125).abs() < 1e-6));
+    }
+
+    #[test]
+    fn test_decode_24_bit_wav_is_resampled() {
+        let spec = WavSpec {
+            channels: 1,
+            sample_rate: 48000,
+            bits_per_sample: 24,
+            sample_format: SampleFormat::Int,
+        };
+        let path = temp_wav("24bit", spec, |writer| {
+            for _ in 0..48000 {
+                writer.write_sample(1 << 22).unwrap();
+            }
+        });
+
+        let samples =
+            decode_audio_file(&path, ChannelStrategy::Average, ResampleQuality::Fast).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(samples.len(), TARGET_SAMPLE_RATE);
+        assert!((samples[TARGET_SAMPLE_RATE / 2] - 0.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_decode_rejects_unknown_formats() {
+        let path =
+            std::env::temp_dir().join(format!("murmure-decode-text-{}.txt", std::process::id()));
+        std::fs::write(&path, "not audio at all").unwrap();
+
+        let result = decode_audio_file(&path, ChannelStrategy::Average, ResampleQuality::Fast);
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(result, Err(DecodeError::UnsupportedFormat(_))));
+    }
+}
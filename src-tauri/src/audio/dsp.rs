@@ -0,0 +1,245 @@
+use crate::audio::types::{DspChain, Normalization};
+
+/// Level targeted by peak normalization, leaves headroom against clipping.
+const PEAK_TARGET_DB: f32 = -1.0;
+/// Loudness targeted by RMS normalization and the AGC.
+const RMS_TARGET_DB: f32 = -20.0;
+/// Gain is capped so silence and hiss are not blown up to speech level.
+const MAX_GAIN_DB: f32 = 20.0;
+/// Gain applied while the noise gate is closed.
+const GATE_FLOOR_DB: f32 = -30.0;
+/// The gate stays open this long after the last loud frame, so word endings survive.
+const GATE_HOLD_MS: usize = 200;
+/// Analysis frame length of the noise gate and the AGC.
+const FRAME_MS: usize = 10;
+/// Frames quieter than this do not move the AGC gain.
+const AGC_SILENCE_DB: f32 = -50.0;
+/// Time constants of the gain changes, fast down to avoid clipping, slow up.
+const ATTACK_MS: f32 = 5.0;
+const RELEASE_MS: f32 = 300.0;
+
+impl DspChain {
+    pub fn from_settings(settings: &crate::settings::AppSettings) -> Self {
+        Self {
+            high_pass_hz: settings.dsp_high_pass_hz,
+            noise_gate_db: settings.dsp_noise_gate_db,
+            normalization: settings.dsp_normalization,
+        }
+    }
+
+    pub fn process(&self, samples: &mut [f32], sample_rate: usize) {
+        if self.high_pass_hz > 0 {
+            high_pass(samples, sample_rate, self.high_pass_hz as f32);
+        }
+        if let Some(threshold_db) = self.noise_gate_db {
+            noise_gate(samples, sample_rate, threshold_db);
+        }
+        match self.normalization {
+            Normalization::Off => {}
+            Normalization::Peak => normalize_peak(samples),
+            Normalization::Rms => normalize_rms(samples),
+            Normalization::Agc => agc(samples, sample_rate),
+        }
+    }
+}
+
+/// Second-order Butterworth high-pass, also removes any DC offset.
+pub fn high_pass(samples: &mut [f32], sample_rate: usize, cutoff_hz: f32) {
+    let nyquist = sample_rate as f32 / 2.0;
+    if samples.is_empty() || cutoff_hz <= 0.0 || cutoff_hz >= nyquist {
+        return;
+    }
+
+    let omega = 2.0 * std::f32::consts::PI * cutoff_hz / sample_rate as f32;
+    let alpha = omega.sin() / std::f32::consts::SQRT_2;
+    let cos = omega.cos();
+    let a0 = 1.0 + alpha;
+    let b0 = (1.0 + cos) / 2.0 / a0;
+    let b1 = -(1.0 + cos) / a0;
+    let b2 = b0;
+    let a1 = -2.0 * cos / a0;
+    let a2 = (1.0 - alpha) / a0;
+
+    let (mut x1, mut x2, mut y1, mut y2) = (0.0, 0.0, 0.0, 0.0);
+    for sample in samples.iter_mut() {
+        let x0 = *sample;
+        let y0 = b0 * x0 + b1 * x1 + b2 * x2 - a1 * y1 - a2 * y2;
+        (x2, x1) = (x1, x0);
+        (y2, y1) = (y1, y0);
+        *sample = y0;
+    }
+}
+
+/// Attenuates frames quieter than `threshold_db`, with a hold time and smoothed gain
+/// so the gate does not click or chop the end of words.
+pub fn noise_gate(samples: &mut [f32], sample_rate: usize, threshold_db: f32) {
+    let frame_len = (sample_rate * FRAME_MS / 1000).max(1);
+    let hold_frames = GATE_HOLD_MS / FRAME_MS;
+    let threshold = db_to_gain(threshold_db);
+    let floor = db_to_gain(GATE_FLOOR_DB);
+    let attack = smoothing_coefficient(sample_rate, ATTACK_MS);
+    let release = smoothing_coefficient(sample_rate, RELEASE_MS);
+
+    let mut gain = floor;
+    let mut frames_since_open = hold_frames;
+    for frame in samples.chunks_mut(frame_len) {
+        if rms(frame) >= threshold {
+            frames_since_open = 0;
+        } else {
+            frames_since_open += 1;
+        }
+        let target = if frames_since_open <= hold_frames {
+            1.0
+        } else {
+            floor
+        };
+
+        // Opens quickly for word onsets, closes slowly
+        let coefficient = if target > gain { attack } else { release };
+        for sample in frame.iter_mut() {
+            gain += (target - gain) * coefficient;
+            *sample *= gain;
+        }
+    }
+}
+
+pub fn normalize_peak(samples: &mut [f32]) {
+    let peak = peak(samples);
+    if peak > 0.0 {
+        apply_gain(samples, db_to_gain(PEAK_TARGET_DB) / peak);
+    }
+}
+
+/// The gain is lowered if needed so the loudest sample does not clip.
+pub fn normalize_rms(samples: &mut [f32]) {
+    let level = rms(samples);
+    let peak = peak(samples);
+    if level > 0.0 && peak > 0.0 {
+        let gain = (db_to_gain(RMS_TARGET_DB) / level).min(1.0 / peak);
+        apply_gain(samples, gain);
+    }
+}
+
+/// Automatic gain control: each frame is brought toward the RMS target,
+/// the gain is frozen during silence so background noise is not pumped up.
+pub fn agc(samples: &mut [f32], sample_rate: usize) {
+    let frame_len = (sample_rate * FRAME_MS / 1000).max(1);
+    let target = db_to_gain(RMS_TARGET_DB);
+    let silence = db_to_gain(AGC_SILENCE_DB);
+    let max_gain = db_to_gain(MAX_GAIN_DB);
+    let attack = smoothing_coefficient(sample_rate, ATTACK_MS);
+    let release = smoothing_coefficient(sample_rate, RELEASE_MS);
+
+    let mut gain = 1.0;
+    let mut target_gain = 1.0;
+    for frame in samples.chunks_mut(frame_len) {
+        let level = rms(frame);
+        if level >= silence {
+            target_gain = (target / level).min(max_gain);
+        }
+
+        let coefficient = if target_gain < gain { attack } else { release };
+        for sample in frame.iter_mut() {
+            gain += (target_gain - gain) * coefficient;
+            *sample = (*sample * gain).clamp(-1.0, 1.0);
+        }
+    }
+}
+
+fn apply_gain(samples: &mut [f32], gain: f32) {
+    let gain = gain.min(db_to_gain(MAX_GAIN_DB));
+    for sample in samples.iter_mut() {
+        *sample *= gain;
+    }
+}
+
+/// Per-sample coefficient of a one-pole smoother reaching ~63% in `time_ms`.
+fn smoothing_coefficient(sample_rate: usize, time_ms: f32) -> f32 {
+    1.0 - (-1000.0 / (time_ms * sample_rate as f32)).exp()
+}
+
+fn db_to_gain(db: f32) -> f32 {
+    10f32.powf(db / 20.0)
+}
+
+fn peak(samples: &[f32]) -> f32 {
+    samples
+        .iter()
+        .fold(0.0, |peak, sample| peak.max(sample.abs()))
+}
+
+fn rms(samples: &[f32]) -> f32 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    (samples.iter().map(|sample| sample * sample).sum::<f32>() / samples.len() as f32).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_RATE: usize = 16000;
+
+    fn tone(amplitude: f32, ms: usize) -> Vec<f32> {
+        (0..SAMPLE_RATE * ms / 1000)
+            .map(|i| {
+                let t = i as f32 / SAMPLE_RATE as f32;
+                amplitude * (2.0 * std::f32::consts::PI * 440.0 * t).sin()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_high_pass_removes_dc_and_keeps_speech_band() {
+        let mut samples: Vec<f32> = tone(0.5, 1000).iter().map(|s| s + 0.3).collect();
+        high_pass(&mut samples, SAMPLE_RATE, 80.0);
+
+        let settled = &samples[SAMPLE_RATE / 2..];
+        let mean = settled.iter().sum::<f32>() / settled.len() as f32;
+        assert!(mean.abs() < 0.01);
+        assert!((peak(settled) - 0.5).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_noise_gate_attenuates_quiet_parts_only() {
+        let mut samples = [tone(0.001, 500), tone(0.3, 500)].concat();
+        noise_gate(&mut samples, SAMPLE_RATE, -40.0);
+
+        let (noise, speech) = samples.split_at(SAMPLE_RATE / 2);
+        assert!(peak(noise) < 0.001 * db_to_gain(GATE_FLOOR_DB) * 1.5);
+        assert!((peak(&speech[SAMPLE_RATE / 10..]) - 0.3).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_peak_normalization_is_capped() {
+        let mut samples = tone(0.25, 100);
+        normalize_peak(&mut samples);
+        assert!((peak(&samples) - db_to_gain(PEAK_TARGET_DB)).abs() < 0.01);
+
+        let mut faint = tone(0.001, 100);
+        normalize_peak(&mut faint);
+        assert!((peak(&faint) - 0.01).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_rms_normalization_does_not_clip() {
+        let mut samples = tone(0.05, 100);
+        normalize_rms(&mut samples);
+        assert!((rms(&samples) - db_to_gain(RMS_TARGET_DB)).abs() < 0.005);
+
+        let mut spiky = vec![0.0; SAMPLE_RATE];
+        spiky[100] = 0.5;
+        normalize_rms(&mut spiky);
+        assert!(peak(&spiky) <= 1.0);
+    }
+
+    #[test]
+    fn test_agc_brings_quiet_speech_to_target() {
+        let mut samples = tone(0.02, 2000);
+        agc(&mut samples, SAMPLE_RATE);
+
+        let settled = rms(&samples[SAMPLE_RATE..]);
+        assert!((settled - db_to_gain(RMS_TARGET_DB)).abs() < 0.01);
+    }
+}
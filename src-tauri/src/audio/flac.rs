@@ -0,0 +1,305 @@
+//! Minimal FLAC encoder for 16-bit mono audio.
+//!
+//! Uses fixed linear predictors (orders 0 to 4) with a single Rice partition per
+//! frame. That is far from the best ratio FLAC can reach, but it typically saves a
+//! third or more over WAV on dictation audio without a native codec library.
+//!
+//! Kept in-tree on purpose: the archive only writes one sample format, so this is a
+//! few hundred lines with no unsafe code, while the pure-Rust encoder crates pull
+//! in a generic multi-channel, multi-depth pipeline and its dependencies for it.
+//! Every stream is checked against symphonia's FLAC decoder in the tests below.
+
+/// Samples per frame; 4096 is what reference encoders use at this sample rate.
+const BLOCK_SIZE: usize = 4096;
+const BITS_PER_SAMPLE: u32 = 16;
+const MAX_FIXED_ORDER: usize = 4;
+/// Rice parameter 15 is reserved as an escape code.
+const MAX_RICE_PARAM: u32 = 14;
+
+/// Encodes `samples` (in -1.0..=1.0) as a complete FLAC stream.
+pub fn encode_flac(samples: &[f32], sample_rate: u32) -> Vec<u8> {
+    let pcm: Vec<i32> = samples
+        .iter()
+        .map(|s| (s.clamp(-1.0, 1.0) * i16::MAX as f32) as i32)
+        .collect();
+
+    let mut out = Vec::with_capacity(pcm.len() + 64);
+    out.extend_from_slice(b"fLaC");
+    write_stream_info(&mut out, pcm.len() as u64, sample_rate);
+    for (index, block) in pcm.chunks(BLOCK_SIZE).enumerate() {
+        write_frame(&mut out, block, index as u64);
+    }
+    out
+}
+
+fn write_stream_info(out: &mut Vec<u8>, total_samples: u64, sample_rate: u32) {
+    let block_size = BLOCK_SIZE.min(total_samples.max(16) as usize) as u64;
+    let mut w = BitWriter::default();
+    // Last metadata block, type 0 (STREAMINFO), 34 bytes long
+    w.write(1, 1);
+    w.write(0, 7);
+    w.write(34, 24);
+    w.write(block_size, 16);
+    w.write(block_size, 16);
+    // Unknown minimum and maximum frame sizes
+    w.write(0, 24);
+    w.write(0, 24);
+    w.write(sample_rate as u64, 20);
+    w.write(0, 3); // channels - 1
+    w.write((BITS_PER_SAMPLE - 1) as u64, 5);
+    w.write(total_samples, 36);
+    // An all-zero MD5 signature means "not computed"
+    for _ in 0..16 {
+        w.write(0, 8);
+    }
+    out.extend_from_slice(&w.bytes);
+}
+
+fn write_frame(out: &mut Vec<u8>, block: &[i32], frame_number: u64) {
+    let mut w = BitWriter::default();
+    w.write(0b11_1111_1111_1110, 14); // sync code
+    w.write(0, 1);
+    w.write(0, 1); // fixed block size
+    w.write(0b0111, 4); // block size - 1 follows as 16 bits
+    w.write(0b0000, 4); // sample rate from STREAMINFO
+    w.write(0b0000, 4); // mono
+    w.write(0b100, 3); // 16 bits per sample
+    w.write(0, 1);
+    write_utf8_number(&mut w, frame_number);
+    w.write(block.len() as u64 - 1, 16);
+    let crc = crc8(&w.bytes);
+    w.write(crc as u64, 8);
+
+    write_subframe(&mut w, block);
+    w.align();
+    let crc = crc16(&w.bytes);
+    w.write(crc as u64, 16);
+    out.extend_from_slice(&w.bytes);
+}
+
+fn write_subframe(w: &mut BitWriter, block: &[i32]) {
+    let verbatim_bits = block.len() as u64 * BITS_PER_SAMPLE as u64;
+    let best = (0..=MAX_FIXED_ORDER.min(block.len().saturating_sub(1)))
+        .map(|order| {
+            let residuals = fixed_residuals(block, order);
+            let (rice_param, bits) = best_rice_param(&residuals);
+            (order, residuals, rice_param, bits)
+        })
+        .min_by_key(|(order, _, _, bits)| *order as u64 * BITS_PER_SAMPLE as u64 + bits);
+
+    match best {
+        Some((order, residuals, rice_param, bits))
+            if order as u64 * BITS_PER_SAMPLE as u64 + bits < verbatim_bits =>
+        {
+            w.write(0, 1);
+            w.write(0b001000 | order as u64, 6);
+            w.write(0, 1); // no wasted bits
+            for &sample in &block[..order] {
+                w.write_signed(sample, BITS_PER_SAMPLE);
+            }
+            w.write(0b00, 2); // Rice coding with 4-bit parameters
+            w.write(0, 4); // single partition
+            w.write(rice_param as u64, 4);
+            for &residual in &residuals {
+                w.write_rice(residual, rice_param);
+            }
+        }
+        _ => {
+            w.write(0, 1);
+            w.write(0b000001, 6);
+            w.write(0, 1);
+            for &sample in block {
+                w.write_signed(sample, BITS_PER_SAMPLE);
+            }
+        }
+    }
+}
+
+/// Prediction error of the FLAC fixed predictor of `order`, one per sample after the warm-up.
+fn fixed_residuals(block: &[i32], order: usize) -> Vec<i32> {
+    (order..block.len())
+        .map(|i| {
+            let s = |back: usize| block[i - back];
+            match order {
+                0 => s(0),
+                1 => s(0) - s(1),
+                2 => s(0) - 2 * s(1) + s(2),
+                3 => s(0) - 3 * s(1) + 3 * s(2) - s(3),
+                _ => s(0) - 4 * s(1) + 6 * s(2) - 4 * s(3) + s(4),
+            }
+        })
+        .collect()
+}
+
+/// Returns the Rice parameter giving the shortest encoding of `residuals`, with that
+/// length in bits including the partition header.
+fn best_rice_param(residuals: &[i32]) -> (u32, u64) {
+    let folded: Vec<u64> = residuals.iter().map(|&r| zigzag(r) as u64).collect();
+    (0..=MAX_RICE_PARAM)
+        .map(|k| {
+            let bits: u64 = folded.iter().map(|&u| (u >> k) + 1 + k as u64).sum();
+            (k, bits + 10)
+        })
+        .min_by_key(|&(_, bits)| bits)
+        .unwrap_or((0, 10))
+}
+
+fn zigzag(value: i32) -> u32 {
+    ((value << 1) ^ (value >> 31)) as u32
+}
+
+fn write_utf8_number(w: &mut BitWriter, value: u64) {
+    if value < 0x80 {
+        w.write(value, 8);
+        return;
+    }
+    let mut continuation = 1;
+    while value >= 1 << (6 + 5 * continuation) {
+        continuation += 1;
+    }
+    let lead_bits = 6 - continuation;
+    let marker = (0xFF00u64 >> (continuation + 1)) & 0xFF;
+    w.write(marker | (value >> (6 * continuation)), 8);
+    debug_assert!(value >> (6 * continuation) < 1 << lead_bits);
+    for i in (0..continuation).rev() {
+        w.write(0b1000_0000 | ((value >> (6 * i)) & 0x3F), 8);
+    }
+}
+
+fn crc8(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0u8, |mut crc, &byte| {
+        crc ^= byte;
+        for _ in 0..8 {
+            crc = if crc & 0x80 != 0 {
+                (crc << 1) ^ 0x07
+            } else {
+                crc << 1
+            };
+        }
+        crc
+    })
+}
+
+fn crc16(bytes: &[u8]) -> u16 {
+    bytes.iter().fold(0u16, |mut crc, &byte| {
+        crc ^= (byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x8005
+            } else {
+                crc << 1
+            };
+        }
+        crc
+    })
+}
+
+/// MSB-first bit writer; `bytes` is only complete after `align`.
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    used_bits: u32,
+}
+
+impl BitWriter {
+    fn write(&mut self, value: u64, bits: u32) {
+        for i in (0..bits).rev() {
+            self.write_bit((value >> i) & 1 == 1);
+        }
+    }
+
+    fn write_signed(&mut self, value: i32, bits: u32) {
+        self.write(value as u64 & ((1 << bits) - 1), bits);
+    }
+
+    fn write_rice(&mut self, value: i32, param: u32) {
+        let folded = zigzag(value);
+        for _ in 0..folded >> param {
+            self.write_bit(false);
+        }
+        self.write_bit(true);
+        self.write(folded as u64, param);
+    }
+
+    fn write_bit(&mut self, bit: bool) {
+        if self.used_bits == 0 {
+            self.bytes.push(0);
+        }
+        if bit {
+            *self.bytes.last_mut().unwrap() |= 0x80 >> self.used_bits;
+        }
+        self.used_bits = (self.used_bits + 1) % 8;
+    }
+
+    fn align(&mut self) {
+        self.used_bits = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use symphonia::core::audio::SampleBuffer;
+    use symphonia::core::codecs::DecoderOptions;
+    use symphonia::core::formats::FormatOptions;
+    use symphonia::core::io::MediaSourceStream;
+    use symphonia::core::meta::MetadataOptions;
+    use symphonia::core::probe::Hint;
+
+    fn decode(bytes: Vec<u8>) -> Vec<i16> {
+        let mss = MediaSourceStream::new(Box::new(std::io::Cursor::new(bytes)), Default::default());
+        let mut hint = Hint::new();
+        hint.with_extension("flac");
+        let probed = symphonia::default::get_probe()
+            .format(
+                &hint,
+                mss,
+                &FormatOptions::default(),
+                &MetadataOptions::default(),
+            )
+            .unwrap();
+        let mut format = probed.format;
+        let track = format.default_track().unwrap();
+        let mut decoder = symphonia::default::get_codecs()
+            .make(&track.codec_params, &DecoderOptions::default())
+            .unwrap();
+        let mut decoded = Vec::new();
+        while let Ok(packet) = format.next_packet() {
+            let buffer = decoder.decode(&packet).unwrap();
+            let mut samples = SampleBuffer::<i16>::new(buffer.capacity() as u64, *buffer.spec());
+            samples.copy_interleaved_ref(buffer);
+            decoded.extend_from_slice(samples.samples());
+        }
+        decoded
+    }
+
+    #[test]
+    fn test_encode_flac_round_trip() {
+        let samples: Vec<f32> = (0..10_000)
+            .map(|i| (i as f32 * 0.05).sin() * 0.5 + if i % 7 == 0 { 0.01 } else { 0.0 })
+            .collect();
+        let expected: Vec<i16> = samples
+            .iter()
+            .map(|s| (s * i16::MAX as f32) as i16)
+            .collect();
+
+        let encoded = encode_flac(&samples, 16000);
+
+        assert!(encoded.len() < samples.len() * 2);
+        assert_eq!(decode(encoded), expected);
+    }
+
+    #[test]
+    fn test_encode_flac_round_trip_past_128_frames() {
+        // Frame numbers from 128 on take two or more bytes in the frame header
+        let samples: Vec<f32> = (0..600_000)
+            .map(|i| (i as f32 * 0.01).sin() * 0.3)
+            .collect();
+        let expected: Vec<i16> = samples
+            .iter()
+            .map(|s| (s * i16::MAX as f32) as i16)
+            .collect();
+
+        assert_eq!(decode(encode_flac(&samples, 16000)), expected);
+    }
+}
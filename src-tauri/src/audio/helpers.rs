@@ -7,6 +7,9 @@ use std::io::BufWriter;
 use std::path::{Path, PathBuf};
 use tauri::Manager;
 
+/// Sample rate expected by the transcription engines.
+pub const TARGET_SAMPLE_RATE: usize = 16000;
+
 pub fn ensure_recordings_dir(app: &tauri::AppHandle) -> Result<PathBuf> {
     let recordings = app
         .path()
@@ -50,75 +53,12 @@ pub fn cleanup_recordings(app: &tauri::AppHandle) -> Result<()> {
     Ok(())
 }
 
-pub fn read_wav_samples(wav_path: &Path) -> Result<Vec<f32>> {
-    let mut reader = hound::WavReader::open(wav_path)?;
-    let spec = reader.spec();
-
-    if spec.bits_per_sample != 16 {
-        return Err(anyhow::anyhow!(
-            "Expected 16 bits per sample, found {}",
-            spec.bits_per_sample
-        ));
-    }
-
-    if spec.sample_format != hound::SampleFormat::Int {
-        return Err(anyhow::anyhow!(
-            "Expected Int sample format, found {:?}",
-            spec.sample_format
-        ));
-    }
-
-    let raw_i16: Result<Vec<i16>, _> = reader.samples::<i16>().collect();
-    let mut raw_i16 = raw_i16?;
-
-    if spec.channels > 1 {
-        let ch = spec.channels as usize;
-        let mut mono: Vec<i16> = Vec::with_capacity(raw_i16.len() / ch);
-        for frame in raw_i16.chunks_exact(ch) {
-            let sum: i32 = frame.iter().map(|&s| s as i32).sum();
-            let avg = (sum / ch as i32).clamp(i16::MIN as i32, i16::MAX as i32) as i16;
-            mono.push(avg);
-        }
-        raw_i16 = mono;
-    }
-
-    let samples_f32: Vec<f32> = raw_i16
-        .into_iter()
-        .map(|s| s as f32 / i16::MAX as f32)
-        .collect();
-
-    let out = if spec.sample_rate != 16000 {
-        resample_linear(&samples_f32, spec.sample_rate as usize, 16000)
-    } else {
-        samples_f32
-    };
-
-    Ok(out)
-}
-
-pub fn resample_linear(input: &[f32], src_hz: usize, dst_hz: usize) -> Vec<f32> {
-    if input.is_empty() || src_hz == 0 || dst_hz == 0 {
-        return Vec::new();
-    }
-    if src_hz == dst_hz {
-        return input.to_vec();
+/// Deletes a single recording, e.g. a rolled-over chunk once transcribed while the
+/// next chunk is still being written next to it.
+pub fn remove_recording(path: &Path) {
+    if let Err(e) = std::fs::remove_file(path) {
+        warn!("Failed to delete {}: {}", path.display(), e);
     }
-    let ratio = dst_hz as f64 / src_hz as f64;
-    let out_len = ((input.len() as f64) * ratio).ceil() as usize;
-    if out_len == 0 {
-        return Vec::new();
-    }
-    let mut out = Vec::with_capacity(out_len);
-    let last_idx = input.len().saturating_sub(1);
-    for i in 0..out_len {
-        let t = (i as f64) / ratio;
-        let idx = t.floor() as usize;
-        let frac = (t - idx as f64) as f32;
-        let a = input[idx];
-        let b = input[std::cmp::min(idx + 1, last_idx)];
-        out.push(a + (b - a) * frac);
-    }
-    out
 }
 
 pub fn create_wav_writer(
@@ -135,3 +75,55 @@ pub fn create_wav_writer(
     };
     WavWriter::new(writer, spec).context("Failed to create WAV writer")
 }
+
+/// Model directory and WAV recordings used by the ignored benchmarks, see
+/// `fixtures/README.md`. `None` when either is missing.
+#[cfg(test)]
+pub fn benchmark_fixtures() -> Option<(PathBuf, Vec<PathBuf>)> {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let model_dir = std::env::var("MURMURE_MODEL_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| {
+            manifest_dir
+                .join("../resources")
+                .join(crate::model::MODEL_FILENAME)
+        });
+    let fixtures_dir = std::env::var("MURMURE_FIXTURES_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| manifest_dir.join("fixtures"));
+
+    let mut wavs: Vec<_> = std::fs::read_dir(&fixtures_dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "wav"))
+        .collect();
+    wavs.sort();
+
+    if !model_dir.is_dir() || wavs.is_empty() {
+        eprintln!(
+            "No model in {} or no WAV fixtures in {}, see fixtures/README.md",
+            model_dir.display(),
+            fixtures_dir.display()
+        );
+        return None;
+    }
+    Some((model_dir, wavs))
+}
+
+/// Reads a fixture as mono samples, with its sample rate.
+#[cfg(test)]
+pub fn read_fixture(path: &Path) -> (Vec<f32>, usize) {
+    let mut reader = hound::WavReader::open(path).expect("Failed to open fixture");
+    let spec = reader.spec();
+    let channels = spec.channels as usize;
+    let samples = reader
+        .samples::<i16>()
+        .map(|s| s.expect("Invalid sample") as f32 / i16::MAX as f32)
+        .collect::<Vec<_>>()
+        .chunks_exact(channels)
+        .map(|frame| frame.iter().sum::<f32>() / channels as f32)
+        .collect();
+    (samples, spec.sample_rate as usize)
+}
@@ -0,0 +1,157 @@
+use crate::audio::types::{AudioState, EngineStatus};
+use crate::audio::worker::{request_engine_load, request_engine_unload};
+use crate::engine::registry::{self, DEFAULT_ENGINE_ID};
+use crate::engine::transcription_engine::TranscriptionEngine;
+use crate::engine::{ModelParams, QuantizationType, SessionOptions};
+use crate::model::helpers::has_fp32_files;
+use crate::model::Model;
+use anyhow::Result;
+use log::{debug, error, info, warn};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Instant;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Half a second of silence, enough for ONNX Runtime to build its execution plans.
+const WARMUP_SAMPLES: usize = 8000;
+
+/// Loads the engine selected in the settings on the transcription worker
+/// and waits until it is ready.
+pub fn preload_engine(app: &AppHandle) -> Result<()> {
+    request_engine_load(app).map_err(|e| anyhow::anyhow!(e))
+}
+
+/// Drops the loaded model to free its memory. It is loaded again on the next dictation.
+pub fn unload_engine(app: &AppHandle) {
+    request_engine_unload(app);
+}
+
+pub fn get_engine_status(app: &AppHandle) -> EngineStatus {
+    *app.state::<AudioState>().engine_status.lock()
+}
+
+/// Loads the engine selected in the settings, replacing `engine`
+/// if the engine or its model directory changed since it was loaded.
+/// A freshly loaded engine is warmed up before being marked as ready.
+/// Only called from the transcription worker, which owns the engine.
+pub fn ensure_engine_loaded<'a>(
+    app: &AppHandle,
+    engine: &'a mut Option<Box<dyn TranscriptionEngine>>,
+) -> Result<&'a mut dyn TranscriptionEngine> {
+    let mut settings = crate::settings::load_settings(app);
+    let model_path = resolve_engine_model_path(app, &mut settings)?;
+
+    let is_up_to_date = engine.as_ref().is_some_and(|loaded| {
+        loaded.id() == settings.engine && loaded.loaded_model_path() == Some(model_path.as_path())
+    });
+
+    if !is_up_to_date {
+        // Free the previous model before loading the new one
+        *engine = None;
+        set_engine_status(app, EngineStatus::Loading);
+
+        let loaded = registry::create_engine(&settings.engine)
+            .ok_or_else(|| anyhow::anyhow!("Unknown transcription engine: {}", settings.engine))
+            .and_then(|mut new_engine| {
+                new_engine
+                    .load_model_with_params(
+                        &model_path,
+                        model_params_from_settings(&settings, &model_path),
+                    )
+                    .map_err(|e| anyhow::anyhow!("Failed to load model: {}", e))?;
+                Ok(new_engine)
+            });
+        let mut new_engine = match loaded {
+            Ok(new_engine) => new_engine,
+            Err(e) => {
+                set_engine_status(app, EngineStatus::Unloaded);
+                return Err(e);
+            }
+        };
+
+        info!(
+            "Model for engine '{}' loaded and cached in memory",
+            settings.engine
+        );
+
+        let started = Instant::now();
+        match new_engine.transcribe_samples(vec![0.0; WARMUP_SAMPLES], None) {
+            Ok(_) => debug!("Engine warmed up in {:?}", started.elapsed()),
+            Err(e) => warn!("Engine warm-up failed: {}", e),
+        }
+
+        *engine = Some(new_engine);
+        set_engine_status(app, EngineStatus::Ready);
+    }
+
+    engine
+        .as_deref_mut()
+        .ok_or_else(|| anyhow::anyhow!("Engine not loaded"))
+}
+
+pub fn release_engine(app: &AppHandle, engine: &mut Option<Box<dyn TranscriptionEngine>>) {
+    if engine.take().is_some() {
+        info!("Transcription engine unloaded");
+        set_engine_status(app, EngineStatus::Unloaded);
+    }
+}
+
+fn set_engine_status(app: &AppHandle, status: EngineStatus) {
+    *app.state::<AudioState>().engine_status.lock() = status;
+    if let Err(e) = app.emit("engine-status", status) {
+        error!("Failed to emit engine status: {}", e);
+    }
+}
+
+/// FP32 falls back to Int8 when the model does not ship full precision files,
+/// for instance after switching to another model.
+pub fn model_params_from_settings(
+    settings: &crate::settings::AppSettings,
+    model_path: &Path,
+) -> ModelParams {
+    let quantization = match settings.model_precision {
+        QuantizationType::FP32 if !has_fp32_files(model_path, &settings.engine) => {
+            warn!(
+                "No FP32 files in {}, loading the Int8 model instead",
+                model_path.display()
+            );
+            QuantizationType::Int8
+        }
+        precision => precision,
+    };
+
+    ModelParams {
+        quantization,
+        session: SessionOptions {
+            intra_threads: (settings.onnx_intra_threads > 0).then_some(settings.onnx_intra_threads),
+            inter_threads: (settings.onnx_inter_threads > 0).then_some(settings.onnx_inter_threads),
+            optimization_level: settings.onnx_optimization_level,
+        },
+    }
+}
+
+pub fn resolve_engine_model_path(
+    app: &AppHandle,
+    settings: &mut crate::settings::AppSettings,
+) -> Result<PathBuf> {
+    if let Some(path) = &settings.engine_model_path {
+        return Ok(PathBuf::from(path));
+    }
+
+    if let Some(path) = crate::model::manager::active_model_path(app, settings)? {
+        return Ok(path);
+    }
+
+    // Only the default engine ships with a bundled model
+    if settings.engine != DEFAULT_ENGINE_ID {
+        anyhow::bail!(
+            "No model directory configured for engine '{}'",
+            settings.engine
+        );
+    }
+
+    let model = app.state::<Arc<Model>>();
+    model
+        .get_model_path()
+        .map_err(|e| anyhow::anyhow!("Failed to get model path: {}", e))
+}
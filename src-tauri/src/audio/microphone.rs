@@ -1,9 +1,18 @@
+use crate::audio::helpers::TARGET_SAMPLE_RATE;
+use crate::audio::types::{ChannelStrategy, MicFallback, MicTracker};
+use anyhow::Result;
 use cpal::traits::{DeviceTrait, HostTrait};
-use cpal::SampleFormat;
-use log::info;
+use cpal::{SampleFormat, SupportedStreamConfig};
+use log::{info, warn};
 use serde::Serialize;
 use std::collections::{HashMap, HashSet};
-use tauri::Manager;
+use std::time::Duration;
+use tauri::{Emitter, Manager};
+
+/// How often the device watcher lists the input devices.
+const DEVICE_POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// Consecutive lists a microphone must be missing from to count as removed.
+const MISSES_BEFORE_REMOVAL: u32 = 2;
 
 #[derive(Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -12,6 +21,9 @@ pub struct MicDevice {
     pub label: String,
 }
 
+/// Key of `channel_strategies` used when no microphone is selected.
+pub const DEFAULT_MIC_KEY: &str = "default";
+
 fn is_supported_sample_format(format: SampleFormat) -> bool {
     matches!(
         format,
@@ -50,6 +62,115 @@ fn is_valid_input_device(device: &cpal::Device) -> bool {
     false
 }
 
+/// Lists the capture configs with at least `min_channels` channels, best first,
+/// followed by the device default. The device's native rate comes first: other
+/// rates go through the driver's resampler, e.g. the ALSA plug layer, which is
+/// worse than ours. Callers try the next config when a stream fails to open.
+pub fn negotiate_input_configs(
+    device: &cpal::Device,
+    min_channels: u16,
+) -> Result<Vec<SupportedStreamConfig>> {
+    let default_config = device.default_input_config().ok();
+    let native_rate = default_config.as_ref().map(|config| config.sample_rate());
+
+    let mut configs: Vec<SupportedStreamConfig> = device
+        .supported_input_configs()
+        .into_iter()
+        .flatten()
+        .filter(|range| {
+            range.channels() >= min_channels.max(1)
+                && is_supported_sample_format(range.sample_format())
+        })
+        .filter_map(|range| {
+            let rate = native_rate
+                .filter(|rate| (range.min_sample_rate()..=range.max_sample_rate()).contains(rate))
+                .unwrap_or_else(|| {
+                    (TARGET_SAMPLE_RATE as u32)
+                        .clamp(range.min_sample_rate(), range.max_sample_rate())
+                });
+            range.try_with_sample_rate(rate)
+        })
+        .collect();
+    configs.sort_by_key(|config| {
+        config_cost(
+            config.sample_rate(),
+            config.channels(),
+            config.sample_format(),
+            native_rate,
+        )
+    });
+    configs.dedup();
+
+    match default_config {
+        Some(config) if !configs.contains(&config) => configs.push(config),
+        None if configs.is_empty() => {
+            return Err(anyhow::anyhow!("No input config available"));
+        }
+        _ => {}
+    }
+    Ok(configs)
+}
+
+/// Lower is better: the native rate first, then the sample rate, channel count
+/// and sample format. Rates above 16 kHz only need downsampling, rates below it
+/// lose information.
+fn config_cost(
+    sample_rate: u32,
+    channels: u16,
+    format: SampleFormat,
+    native_rate: Option<u32>,
+) -> (bool, u64, u16, u8) {
+    let target = TARGET_SAMPLE_RATE as u64;
+    let rate = sample_rate as u64;
+    let rate_cost = if rate >= target {
+        rate - target
+    } else {
+        (target - rate) * 1000
+    };
+    let format_cost = match format {
+        SampleFormat::F32 => 0,
+        SampleFormat::I16 => 1,
+        SampleFormat::I32 => 2,
+        _ => 3,
+    };
+    (
+        native_rate != Some(sample_rate),
+        rate_cost,
+        channels,
+        format_cost,
+    )
+}
+
+/// Channel strategy configured for the selected microphone.
+pub fn channel_strategy_for(settings: &crate::settings::AppSettings) -> ChannelStrategy {
+    let key = settings.mic_id.as_deref().unwrap_or(DEFAULT_MIC_KEY);
+    settings
+        .channel_strategies
+        .get(key)
+        .copied()
+        .unwrap_or_default()
+}
+
+/// Most input channels the selected microphone offers, or the system default
+/// when `mic_id` is `None`. Listing configs is slow, so only done on request.
+pub fn get_channel_count(mic_id: Option<&str>) -> Option<u16> {
+    let device = match mic_id {
+        Some(id) => resolve_input_device(id)?,
+        None => cpal::default_host().default_input_device()?,
+    };
+    Some(get_max_channels(&device))
+}
+
+fn get_max_channels(device: &cpal::Device) -> u16 {
+    let listed = device
+        .supported_input_configs()
+        .ok()
+        .and_then(|configs| configs.map(|range| range.channels()).max());
+    listed
+        .or_else(|| device.default_input_config().ok().map(|c| c.channels()))
+        .unwrap_or(1)
+}
+
 fn normalize_label_part(value: &str) -> String {
     value.trim().to_lowercase()
 }
@@ -290,3 +411,218 @@ pub fn init_mic_cache_if_needed(app: &tauri::AppHandle, mic_id: Option<String>)
         });
     }
 }
+
+/// Tells the frontend that recordings no longer use the preferred microphone.
+pub fn emit_mic_fallback(app: &tauri::AppHandle, preferred_id: &str) {
+    let fallback_label = cpal::default_host()
+        .default_input_device()
+        .and_then(|device| get_device_id(&device).map(|id| get_device_label(&device, &id)));
+    warn!(
+        "Microphone {} unavailable, using {}",
+        preferred_id,
+        fallback_label.as_deref().unwrap_or("no microphone")
+    );
+    let _ = app.emit(
+        "mic-fallback",
+        MicFallback {
+            preferred_id: preferred_id.to_string(),
+            fallback_label,
+        },
+    );
+}
+
+/// Spawns the thread watching for microphones being plugged in or out. Emits
+/// `mic-added` and `mic-removed`, switches to the system default when the
+/// preferred microphone disappears and back to it when it returns.
+pub fn start_device_watcher(app: &tauri::AppHandle) {
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let mut tracker = MicTracker::new(MISSES_BEFORE_REMOVAL);
+        let mut preferred: Option<String> = None;
+        let mut preferred_missing = false;
+        loop {
+            std::thread::sleep(DEVICE_POLL_INTERVAL);
+
+            // Listing devices makes some drivers glitch open streams. A glitch in the
+            // pre-roll is harmless, while the pre-roll notices its own device going away
+            let audio_state = app.state::<crate::audio::types::AudioState>();
+            if audio_state.recorder.lock().is_some() {
+                continue;
+            }
+
+            let Some((added, removed)) = tracker.update(get_mic_list()) else {
+                continue;
+            };
+            for mic in &added {
+                info!("Microphone connected: {}", mic.label);
+                let _ = app.emit("mic-added", mic);
+            }
+            for mic in &removed {
+                info!("Microphone disconnected: {}", mic.label);
+                let _ = app.emit("mic-removed", mic);
+            }
+
+            let mic_id = crate::settings::load_settings(&app).mic_id;
+            if mic_id != preferred {
+                preferred = mic_id;
+                preferred_missing = false;
+            }
+            if let Some(id) = &preferred {
+                // Older settings may store the device name rather than its id
+                let available = tracker.contains(id) || resolve_input_device(id).is_some();
+                if !available && !preferred_missing {
+                    preferred_missing = true;
+                    audio_state.set_cached_device(None);
+                    crate::audio::preroll::restart_preroll(&app);
+                    emit_mic_fallback(&app, id);
+                } else if available && preferred_missing {
+                    preferred_missing = false;
+                    info!("Preferred microphone {} is back", id);
+                    update_mic_cache(&app, Some(id.clone()));
+                    crate::audio::preroll::restart_preroll(&app);
+                }
+            }
+        }
+    });
+}
+
+impl MicTracker {
+    fn new(misses_before_removal: u32) -> Self {
+        Self {
+            known: None,
+            misses: HashMap::new(),
+            misses_before_removal: misses_before_removal.max(1),
+        }
+    }
+
+    /// Takes the latest device list. Returns the microphones added and removed
+    /// when the known list changed, or on the first list so the preferred
+    /// microphone gets checked, `None` otherwise.
+    fn update(&mut self, current: Vec<MicDevice>) -> Option<(Vec<MicDevice>, Vec<MicDevice>)> {
+        let Some(known) = &mut self.known else {
+            self.known = Some(current);
+            return Some((Vec::new(), Vec::new()));
+        };
+
+        let (added, missing) = diff_mic_lists(known, &current);
+        self.misses
+            .retain(|id, _| missing.iter().any(|mic| mic.id == *id));
+        let mut removed = Vec::new();
+        for mic in missing {
+            let misses = self.misses.entry(mic.id.clone()).or_insert(0);
+            *misses += 1;
+            if *misses >= self.misses_before_removal {
+                self.misses.remove(&mic.id);
+                removed.push(mic);
+            }
+        }
+
+        known.retain(|mic| !removed.iter().any(|gone| gone.id == mic.id));
+        known.extend(added.iter().cloned());
+        (!added.is_empty() || !removed.is_empty()).then_some((added, removed))
+    }
+
+    fn contains(&self, id: &str) -> bool {
+        self.known
+            .as_ref()
+            .is_some_and(|known| known.iter().any(|mic| mic.id == id))
+    }
+}
+
+fn diff_mic_lists(
+    previous: &[MicDevice],
+    current: &[MicDevice],
+) -> (Vec<MicDevice>, Vec<MicDevice>) {
+    let missing_from = |list: &[MicDevice], mic: &MicDevice| !list.iter().any(|m| m.id == mic.id);
+    let added = current
+        .iter()
+        .filter(|mic| missing_from(previous, mic))
+        .cloned()
+        .collect();
+    let removed = previous
+        .iter()
+        .filter(|mic| missing_from(current, mic))
+        .cloned()
+        .collect();
+    (added, removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mic(id: &str) -> MicDevice {
+        MicDevice {
+            id: id.to_string(),
+            label: id.to_uppercase(),
+        }
+    }
+
+    #[test]
+    fn test_diff_mic_lists() {
+        let previous = vec![mic("builtin"), mic("headset")];
+        let current = vec![mic("builtin"), mic("webcam")];
+
+        let (added, removed) = diff_mic_lists(&previous, &current);
+        assert_eq!(added.len(), 1);
+        assert_eq!(added[0].id, "webcam");
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].id, "headset");
+
+        let (added, removed) = diff_mic_lists(&current, &current);
+        assert!(added.is_empty() && removed.is_empty());
+    }
+
+    #[test]
+    fn test_mic_tracker_waits_before_removing() {
+        let mut tracker = MicTracker::new(2);
+        let (added, removed) = tracker
+            .update(vec![mic("builtin"), mic("headset")])
+            .unwrap();
+        assert!(added.is_empty() && removed.is_empty());
+
+        // A single missing list is not enough
+        assert!(tracker.update(vec![mic("builtin")]).is_none());
+        assert!(tracker.contains("headset"));
+        assert!(tracker
+            .update(vec![mic("builtin"), mic("headset")])
+            .is_none());
+        assert!(tracker.update(vec![mic("builtin")]).is_none());
+
+        let (added, removed) = tracker.update(vec![mic("builtin")]).unwrap();
+        assert!(added.is_empty());
+        assert_eq!(removed[0].id, "headset");
+        assert!(!tracker.contains("headset"));
+
+        let (added, removed) = tracker.update(vec![mic("builtin"), mic("webcam")]).unwrap();
+        assert_eq!(added[0].id, "webcam");
+        assert!(removed.is_empty());
+    }
+
+    #[test]
+    fn test_config_cost_prefers_16khz_mono() {
+        let best = config_cost(16000, 1, SampleFormat::I16, None);
+        assert!(best < config_cost(16000, 2, SampleFormat::F32, None));
+        assert!(best < config_cost(48000, 1, SampleFormat::F32, None));
+    }
+
+    #[test]
+    fn test_config_cost_prefers_the_native_rate() {
+        let native = Some(48000);
+        let best = config_cost(48000, 1, SampleFormat::I16, native);
+        assert!(best < config_cost(16000, 1, SampleFormat::F32, native));
+        assert!(best < config_cost(48000, 2, SampleFormat::I16, native));
+    }
+
+    #[test]
+    fn test_config_cost_prefers_downsampling_over_upsampling() {
+        assert!(
+            config_cost(48000, 2, SampleFormat::I16, None)
+                < config_cost(8000, 1, SampleFormat::F32, None)
+        );
+        assert!(
+            config_cost(22050, 1, SampleFormat::I16, None)
+                < config_cost(44100, 1, SampleFormat::I16, None)
+        );
+    }
+}
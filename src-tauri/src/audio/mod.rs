@@ -1,10 +1,23 @@
+pub mod archive;
 pub mod audio;
+pub mod channels;
+pub mod decode;
+pub mod dsp;
+pub mod flac;
 pub mod helpers;
+pub mod lifecycle;
 pub mod microphone;
 pub mod pipeline;
+pub mod preroll;
+pub mod preview;
 pub mod recorder;
+pub mod resample;
 pub mod sound;
 pub mod types;
+pub mod vad;
+pub mod worker;
 
 pub use audio::*;
+pub use lifecycle::*;
 pub use pipeline::*;
+pub use worker::*;
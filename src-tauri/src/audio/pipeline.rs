@@ -1,22 +1,125 @@
-use crate::audio::helpers::read_wav_samples;
-use crate::audio::types::{AudioState, RecordingMode};
+use crate::audio::archive::archive_recording;
+use crate::audio::decode::decode_audio_file;
+use crate::audio::helpers::TARGET_SAMPLE_RATE;
+use crate::audio::resample::resample;
+use crate::audio::types::{
+    AudioState, DspChain, JobPriority, ProcessTarget, RecordedAudio, RecordingMode,
+    RetainedRecording, Vad,
+};
+use crate::audio::vad::discard_reason;
+use crate::audio::worker::submit_transcription;
 use crate::dictionary::{fix_transcription_with_dictionary, get_cc_rules_path, Dictionary};
-use crate::engine::transcription_engine::TranscriptionEngine;
-use crate::engine::ParakeetModelParams;
+use crate::engine::transcription_engine::TranscriptionResult;
+use crate::engine::{
+    ContextBiasing, DecodingOptions, DecodingStrategy, InferenceParams, TimestampGranularity,
+};
 use crate::formatting_rules;
 use crate::history;
-use crate::model::Model;
 use crate::stats;
 use anyhow::{Context, Result};
 use log::{debug, error, info, warn};
 use std::path::Path;
-use std::sync::Arc;
 use tauri::{AppHandle, Emitter, Manager};
 
-pub fn process_recording(app: &AppHandle, file_path: &Path) -> Result<String> {
+/// Words with a confidence below this value are reported as uncertain to the LLM.
+const LOW_CONFIDENCE_THRESHOLD: f32 = 0.6;
+
+/// Transcribes and post-processes a recording according to `mode`. `preroll_ms` is
+/// the audio from before the shortcut at its start, `is_chunk` is set for the chunks
+/// of a rolled-over recording that is still going on.
+/// Returns `None` when the recording is discarded as an accidental tap.
+pub fn process_recording(
+    app: &AppHandle,
+    audio: RecordedAudio,
+    mode: RecordingMode,
+    preroll_ms: u32,
+    is_chunk: bool,
+) -> Result<Option<String>> {
+    let settings = crate::settings::load_settings(app);
+    let quality = settings.resample_quality;
+    let samples = match &audio {
+        RecordedAudio::File(path) => {
+            decode_audio_file(path, settings.file_channel_strategy, quality)?
+        }
+        RecordedAudio::Samples {
+            samples,
+            sample_rate,
+        } => resample(samples, *sample_rate, TARGET_SAMPLE_RATE, quality),
+    };
+    // Only what was recorded after the shortcut tells a tap from a dictation
+    let preroll_len = (preroll_ms as usize * TARGET_SAMPLE_RATE / 1000).min(samples.len());
+    if let Some(reason) = discard_reason(
+        &samples[preroll_len..],
+        TARGET_SAMPLE_RATE,
+        settings.min_recording_ms,
+        settings.min_recording_level_db,
+    ) {
+        if is_chunk {
+            // The recording goes on, so no sound or overlay animation for a silent chunk
+            info!("Rollover chunk discarded: {:?}", reason);
+        } else {
+            crate::audio::discard_recording(app, reason);
+        }
+        return Ok(None);
+    }
+
+    let (duration_seconds, size_bytes) = recording_duration_and_size(&audio);
+    let target = ProcessTarget::New {
+        duration_seconds,
+        size_bytes,
+    };
+    process_samples(app, samples, mode, None, target).map(Some)
+}
+
+/// Runs a retained recording through the pipeline again, e.g. in LLM mode after
+/// a disappointing Standard result. `index` 0 is the most recent recording.
+/// `llm_mode` picks the LLM mode for this run only, instead of the active one.
+/// The new text replaces the recording's history entry.
+pub fn reprocess_recording(
+    app: &AppHandle,
+    index: usize,
+    mode: RecordingMode,
+    llm_mode: Option<usize>,
+) -> Result<String> {
+    let recording = app
+        .state::<AudioState>()
+        .recent_recordings
+        .lock()
+        .get(index)
+        .cloned()
+        .context("No recording to reprocess")?;
+    let target = ProcessTarget::Reprocess {
+        history_id: recording.history_id,
+    };
+    process_samples(app, recording.samples, mode, llm_mode, target)
+}
+
+fn process_samples(
+    app: &AppHandle,
+    samples: Vec<f32>,
+    mode: RecordingMode,
+    llm_mode: Option<usize>,
+    target: ProcessTarget,
+) -> Result<String> {
+    let settings = crate::settings::load_settings(app);
+    // Reprocessed recordings are already archived and retained
+    let kept_samples = match target {
+        ProcessTarget::New { .. }
+            if settings.archive_enabled || settings.retained_recordings > 0 =>
+        {
+            Some(samples.clone())
+        }
+        _ => None,
+    };
+
     // 1. Transcribe
-    let raw_text = transcribe_audio(app, file_path)?;
+    let transcription = transcribe_samples(app, samples, JobPriority::Interactive, 1)?;
+    let raw_text = transcription.text.clone();
     debug!("Raw transcription: {}", raw_text);
+    let uncertain_words = transcription.uncertain_words(LOW_CONFIDENCE_THRESHOLD);
+    if !uncertain_words.is_empty() {
+        debug!("Low confidence words: {:?}", uncertain_words);
+    }
 
     if raw_text.trim().is_empty() {
         debug!("Transcription is empty, skipping further processing.");
@@ -28,56 +131,138 @@ pub fn process_recording(app: &AppHandle, file_path: &Path) -> Result<String> {
     debug!("Transcription fixed with dictionary: {}", text);
 
     // 3. LLM Post-processing
-    let llm_text = apply_llm_processing(app, text)?;
+    let llm_text = apply_llm_processing(app, text, &uncertain_words, mode, llm_mode)?;
 
     // 4. Apply formatting rules
     let final_text = apply_formatting_rules(app, llm_text);
     debug!("Transcription with formatting rules: {}", final_text);
 
-    // 5. Save Stats & History
-    save_stats_and_history(app, file_path, &final_text)?;
+    // 5. Archive audio, save Stats & History
+    match target {
+        ProcessTarget::New {
+            duration_seconds,
+            size_bytes,
+        } => {
+            let audio_file = kept_samples
+                .as_ref()
+                .filter(|_| settings.archive_enabled)
+                .map(|samples| archive_recording(app, samples.clone()));
+            let history_id =
+                save_stats_and_history(app, duration_seconds, size_bytes, &final_text, audio_file);
+            if let Some(samples) = kept_samples {
+                retain_recording(app, samples, history_id, settings.retained_recordings);
+            }
+        }
+        ProcessTarget::Reprocess { history_id } => {
+            replace_history_entry(app, history_id, &final_text);
+        }
+    }
 
     Ok(final_text)
 }
 
-pub fn transcribe_audio(app: &AppHandle, audio_path: &Path) -> Result<String> {
+pub fn transcribe_audio(
+    app: &AppHandle,
+    audio_path: &Path,
+    priority: JobPriority,
+    n_best: usize,
+) -> Result<TranscriptionResult> {
+    let settings = crate::settings::load_settings(app);
+    let samples = decode_audio_file(
+        audio_path,
+        settings.file_channel_strategy,
+        settings.resample_quality,
+    )?;
+    transcribe_samples(app, samples, priority, n_best)
+}
+
+/// Queues 16 kHz mono samples on the transcription worker and waits for the result.
+/// The worker loads the configured engine first if needed.
+///
+/// With `n_best` above 1 the result also carries up to `n_best - 1` runner-up
+/// hypotheses, using beam search even when greedy decoding is configured.
+pub fn transcribe_samples(
+    app: &AppHandle,
+    samples: Vec<f32>,
+    priority: JobPriority,
+    n_best: usize,
+) -> Result<TranscriptionResult> {
+    let (samples, params, offset_seconds) = prepare_transcription(app, samples, n_best);
+
     let _ = app.emit("llm-processing-start", ());
+    let job = submit_transcription(app, samples, params, priority);
+    debug!("Transcription queued as job {}", job.id);
+    let result = job.wait();
+    let _ = app.emit("llm-processing-end", ());
 
-    let state = app.state::<AudioState>();
+    let mut result = result.map_err(|e| anyhow::anyhow!("Transcription failed: {}", e))?;
+    // Timestamps are relative to the trimmed audio
+    for segment in &mut result.segments {
+        segment.start += offset_seconds;
+        segment.end += offset_seconds;
+    }
 
-    // Ensure engine is loaded
-    {
-        let mut engine_guard = state.engine.lock();
-        if engine_guard.is_none() {
-            let model = app.state::<Arc<Model>>();
-            let model_path = model
-                .get_model_path()
-                .map_err(|e| anyhow::anyhow!("Failed to get model path: {}", e))?;
-
-            let mut new_engine = crate::engine::ParakeetEngine::new();
-            new_engine
-                .load_model_with_params(&model_path, ParakeetModelParams::int8())
-                .map_err(|e| anyhow::anyhow!("Failed to load model: {}", e))?;
-
-            *engine_guard = Some(new_engine);
-            info!("Model loaded and cached in memory");
+    Ok(result)
+}
+
+/// Applies the configured DSP and silence trimming to 16 kHz `samples` and builds the
+/// decoding parameters, so live previews and final transcriptions see the same input.
+/// Also returns where the kept audio starts, in seconds.
+pub fn prepare_transcription(
+    app: &AppHandle,
+    mut samples: Vec<f32>,
+    n_best: usize,
+) -> (Vec<f32>, InferenceParams, f32) {
+    let settings = crate::settings::load_settings(app);
+    DspChain::from_settings(&settings).process(&mut samples, TARGET_SAMPLE_RATE);
+
+    // Leading and trailing silence only costs inference time
+    let mut offset_seconds = 0.0;
+    if settings.vad_trim_silence {
+        let vad = Vad::adaptive(&samples, TARGET_SAMPLE_RATE);
+        match vad.speech_range(&samples, TARGET_SAMPLE_RATE) {
+            Some(range) => {
+                debug!("Trimmed {} silent samples", samples.len() - range.len());
+                offset_seconds = range.start as f32 / TARGET_SAMPLE_RATE as f32;
+                samples = samples[range].to_vec();
+            }
+            // Trimming only saves time, quiet recordings are still transcribed
+            None => debug!("No speech detected, transcribing the whole recording"),
         }
     }
 
-    let samples = read_wav_samples(audio_path)?;
+    let mut params = inference_params_from_settings(&settings, n_best);
+    if settings.context_biasing_enabled {
+        let words: Vec<String> = app.state::<Dictionary>().get().into_keys().collect();
+        if !words.is_empty() {
+            params.decoding.biasing = Some(ContextBiasing {
+                words,
+                weight: settings.context_biasing_weight,
+            });
+        }
+    }
 
-    let mut engine_guard = state.engine.lock();
-    let engine = engine_guard
-        .as_mut()
-        .ok_or_else(|| anyhow::anyhow!("Engine not loaded"))?;
+    (samples, params, offset_seconds)
+}
 
-    let result = engine.transcribe_samples(samples, None).map_err(|e| {
-        let _ = app.emit("llm-processing-end", ());
-        anyhow::anyhow!("Transcription failed: {}", e)
-    })?;
-    let _ = app.emit("llm-processing-end", ());
+fn inference_params_from_settings(
+    settings: &crate::settings::AppSettings,
+    n_best: usize,
+) -> InferenceParams {
+    let n_best = n_best.max(1);
+    let strategy = match settings.beam_size.max(n_best) {
+        0 | 1 => DecodingStrategy::Greedy,
+        beam_size => DecodingStrategy::BeamSearch { beam_size, n_best },
+    };
 
-    Ok(result.text)
+    InferenceParams {
+        timestamp_granularity: TimestampGranularity::Word,
+        decoding: DecodingOptions {
+            strategy,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
 }
 
 fn apply_dictionary_and_rules(app: &AppHandle, text: String) -> Result<String> {
@@ -91,10 +276,13 @@ fn apply_dictionary_and_rules(app: &AppHandle, text: String) -> Result<String> {
     ))
 }
 
-fn apply_llm_processing(app: &AppHandle, text: String) -> Result<String> {
-    let state = app.state::<AudioState>();
-    let recording_mode = state.get_recording_mode();
-
+fn apply_llm_processing(
+    app: &AppHandle,
+    text: String,
+    uncertain_words: &[String],
+    recording_mode: RecordingMode,
+    llm_mode: Option<usize>,
+) -> Result<String> {
     let rt = tokio::runtime::Runtime::new().context("Failed to create tokio runtime")?;
 
     match recording_mode {
@@ -139,7 +327,7 @@ DO NOT explain, comment, or add any text beyond the transformation output.
             }
 
             // Call direct LLM function
-            match rt.block_on(crate::llm::process_command_with_llm(app, prompt)) {
+            match rt.block_on(crate::llm::process_command_with_llm(app, prompt, llm_mode)) {
                 Ok(response) => {
                     debug!("Command processed with LLM: {}", response);
                     Ok(response)
@@ -158,6 +346,8 @@ DO NOT explain, comment, or add any text beyond the transformation output.
             match rt.block_on(crate::llm::post_process_with_llm(
                 app,
                 text.clone(),
+                uncertain_words,
+                llm_mode,
                 false, // force_bypass
             )) {
                 Ok(llm_text) => {
@@ -191,9 +381,82 @@ fn apply_formatting_rules(app: &AppHandle, text: String) -> String {
     }
 }
 
-fn save_stats_and_history(app: &AppHandle, file_path: &Path, text: &str) -> Result<()> {
-    // Calculate duration and size
-    let (duration_seconds, wav_size_bytes) = match hound::WavReader::open(file_path) {
+/// Keeps the last `limit` recordings for `reprocess_recording`, newest first.
+fn retain_recording(app: &AppHandle, samples: Vec<f32>, history_id: Option<u64>, limit: usize) {
+    let state = app.state::<AudioState>();
+    let mut recent = state.recent_recordings.lock();
+    if limit > 0 {
+        recent.push_front(RetainedRecording {
+            samples,
+            history_id,
+        });
+    }
+    recent.truncate(limit);
+}
+
+/// Puts a reprocessed text in place of the original one, or adds it when the entry
+/// has already left the history.
+fn replace_history_entry(app: &AppHandle, history_id: Option<u64>, text: &str) {
+    let replaced = match history_id {
+        Some(id) => history::replace_transcription(app, id, text.to_string()),
+        None => Ok(false),
+    };
+    let result = match replaced {
+        Ok(true) => Ok(()),
+        Ok(false) => history::add_transcription(app, text.to_string(), None).map(|_| ()),
+        Err(e) => Err(e),
+    };
+    if let Err(e) = result {
+        error!("Failed to save to history: {}", e);
+    }
+}
+
+fn recording_duration_and_size(audio: &RecordedAudio) -> (f64, u64) {
+    match audio {
+        RecordedAudio::File(file_path) => wav_duration_and_size(file_path),
+        RecordedAudio::Samples {
+            samples,
+            sample_rate,
+        } => samples_duration_and_size(samples, *sample_rate),
+    }
+}
+
+/// Counted as the 16-bit WAV the recorder would have written.
+fn samples_duration_and_size(samples: &[f32], sample_rate: usize) -> (f64, u64) {
+    (
+        samples.len() as f64 / sample_rate.max(1) as f64,
+        (samples.len() * std::mem::size_of::<i16>()) as u64,
+    )
+}
+
+fn save_stats_and_history(
+    app: &AppHandle,
+    duration_seconds: f64,
+    wav_size_bytes: u64,
+    text: &str,
+    audio_file: Option<String>,
+) -> Option<u64> {
+    let word_count: u64 = text.split_whitespace().filter(|s| !s.is_empty()).count() as u64;
+
+    let history_id = match history::add_transcription(app, text.to_string(), audio_file) {
+        Ok(id) => Some(id),
+        Err(e) => {
+            error!("Failed to save to history: {}", e);
+            None
+        }
+    };
+
+    if let Err(e) =
+        stats::add_transcription_session(app, word_count, duration_seconds, wav_size_bytes)
+    {
+        error!("Failed to save stats session: {}", e);
+    }
+
+    history_id
+}
+
+fn wav_duration_and_size(file_path: &Path) -> (f64, u64) {
+    match hound::WavReader::open(file_path) {
         Ok(reader) => {
             let spec = reader.spec();
             let total_samples = reader.duration() as f64;
@@ -206,19 +469,5 @@ fn save_stats_and_history(app: &AppHandle, file_path: &Path, text: &str) -> Resu
             (seconds, size)
         }
         Err(_) => (0.0, 0),
-    };
-
-    let word_count: u64 = text.split_whitespace().filter(|s| !s.is_empty()).count() as u64;
-
-    if let Err(e) = history::add_transcription(app, text.to_string()) {
-        error!("Failed to save to history: {}", e);
     }
-
-    if let Err(e) =
-        stats::add_transcription_session(app, word_count, duration_seconds, wav_size_bytes)
-    {
-        error!("Failed to save stats session: {}", e);
-    }
-
-    Ok(())
 }
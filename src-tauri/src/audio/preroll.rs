@@ -0,0 +1,245 @@
+use crate::audio::microphone::{channel_strategy_for, negotiate_input_configs};
+use crate::audio::recorder::{AudioRecorder, SendStream};
+use crate::audio::types::{
+    AudioState, ChannelMixer, ChannelStrategy, PrerollBuffer, PrerollCapture, PrerollPolicy,
+    SampleRing, SharedPrerollBuffer,
+};
+use anyhow::{Context, Result};
+use cpal::traits::{DeviceTrait, StreamTrait};
+use log::{debug, error, info, warn};
+use parking_lot::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, Manager};
+
+impl PrerollBuffer {
+    pub fn new(sample_rate: usize, duration_ms: u32) -> Self {
+        Self {
+            samples: SampleRing::new(sample_rate * duration_ms as usize / 1000),
+            sample_rate,
+        }
+    }
+
+    pub fn push(&mut self, sample: f32) {
+        self.samples.push(sample);
+    }
+
+    /// Hands the buffered audio over to a recording, so it is never used twice.
+    pub fn take(&mut self) -> Vec<f32> {
+        self.samples.take()
+    }
+}
+
+/// Spawns the thread opening and closing the warm stream as the settings
+/// and the privacy policy allow. It sleeps until `refresh_preroll` wakes it.
+pub fn start_preroll_monitor(app: &AppHandle) {
+    let (wakeup, requests) = mpsc::channel();
+    *app.state::<AudioState>().preroll_wakeup.lock() = Some(wakeup);
+
+    let app = app.clone();
+    std::thread::spawn(move || {
+        sync_preroll(&app);
+        while requests.recv().is_ok() {
+            // Several events often arrive together, one check covers them all
+            while requests.try_recv().is_ok() {}
+            sync_preroll(&app);
+        }
+    });
+}
+
+/// Asks the monitor to check the settings and policy again, e.g. after a
+/// settings change, an overlay toggle or a focus change.
+pub fn refresh_preroll(app: &AppHandle) {
+    if let Some(wakeup) = app.state::<AudioState>().preroll_wakeup.lock().as_ref() {
+        let _ = wakeup.send(());
+    }
+}
+
+/// Closes the warm stream and drops its audio, then lets the monitor reopen it
+/// with the current microphone and settings if still allowed.
+pub fn restart_preroll(app: &AppHandle) {
+    if app.state::<AudioState>().preroll.lock().take().is_some() {
+        let _ = app.emit("preroll-status", false);
+    }
+    refresh_preroll(app);
+}
+
+fn sync_preroll(app: &AppHandle) {
+    let settings = crate::settings::load_settings(app);
+    let state = app.state::<AudioState>();
+    let wanted = settings.preroll_ms > 0 && is_policy_satisfied(app, settings.preroll_policy);
+
+    if !wanted {
+        if state.preroll.lock().take().is_some() {
+            debug!("Pre-roll buffer released");
+            let _ = app.emit("preroll-status", false);
+        }
+        return;
+    }
+
+    let is_current = state.preroll.lock().as_ref().is_some_and(|capture| {
+        capture.duration_ms == settings.preroll_ms && !capture.failed.load(Ordering::SeqCst)
+    });
+    if is_current {
+        return;
+    }
+
+    // Opening the device can take a while, recordings must not wait on the lock meanwhile
+    *state.preroll.lock() = None;
+    match open_preroll(app, settings.preroll_ms) {
+        Ok(capture) => {
+            info!("Pre-roll buffer active ({} ms)", settings.preroll_ms);
+            *state.preroll.lock() = Some(capture);
+            let _ = app.emit("preroll-status", true);
+        }
+        Err(e) => error!("Failed to open pre-roll stream: {:#}", e),
+    }
+}
+
+/// Returns the buffered audio and its sample rate, `None` when pre-roll is off.
+pub fn take_preroll(app: &AppHandle) -> Option<(Vec<f32>, usize)> {
+    let state = app.state::<AudioState>();
+    let preroll = state.preroll.lock();
+    let mut buffer = preroll.as_ref()?.buffer.lock();
+    let sample_rate = buffer.sample_rate;
+    Some((buffer.take(), sample_rate))
+}
+
+fn is_policy_satisfied(app: &AppHandle, policy: PrerollPolicy) -> bool {
+    let window_label = match policy {
+        PrerollPolicy::Always => return true,
+        PrerollPolicy::Foreground => "main",
+        PrerollPolicy::OverlayVisible => "recording_overlay",
+    };
+    let Some(window) = app.get_webview_window(window_label) else {
+        return false;
+    };
+
+    match policy {
+        PrerollPolicy::Foreground => window.is_focused().unwrap_or(false),
+        _ => window.is_visible().unwrap_or(false),
+    }
+}
+
+fn open_preroll(app: &AppHandle, duration_ms: u32) -> Result<PrerollCapture> {
+    let device = AudioRecorder::get_device(app.clone())?;
+    let strategy = channel_strategy_for(&crate::settings::load_settings(app));
+    let mut last_error = None;
+    for config in negotiate_input_configs(&device, strategy.min_channels())? {
+        match open_preroll_with_config(app, &device, &config, strategy, duration_ms) {
+            Ok(capture) => return Ok(capture),
+            Err(e) => {
+                warn!(
+                    "Failed to open {} Hz/{} ch pre-roll stream ({:#}), trying the next config",
+                    config.sample_rate(),
+                    config.channels(),
+                    e
+                );
+                last_error = Some(e);
+            }
+        }
+    }
+    Err(last_error.unwrap_or_else(|| anyhow::anyhow!("No input config available")))
+}
+
+fn open_preroll_with_config(
+    app: &AppHandle,
+    device: &cpal::Device,
+    config: &cpal::SupportedStreamConfig,
+    strategy: ChannelStrategy,
+    duration_ms: u32,
+) -> Result<PrerollCapture> {
+    let buffer = Arc::new(Mutex::new(PrerollBuffer::new(
+        config.sample_rate() as usize,
+        duration_ms,
+    )));
+
+    let failed = Arc::new(AtomicBool::new(false));
+    let on_error = {
+        let app = app.clone();
+        let failed = failed.clone();
+        move |err: cpal::StreamError| {
+            error!("Pre-roll stream error: {}", err);
+            // The monitor reopens the stream, falling back to another microphone if needed
+            if matches!(err, cpal::StreamError::DeviceNotAvailable) {
+                failed.store(true, Ordering::SeqCst);
+                refresh_preroll(&app);
+            }
+        }
+    };
+
+    let stream = match config.sample_format() {
+        cpal::SampleFormat::F32 => {
+            build_preroll_stream::<f32, _>(device, config, strategy, buffer.clone(), on_error)
+        }
+        cpal::SampleFormat::I16 => {
+            build_preroll_stream::<i16, _>(device, config, strategy, buffer.clone(), on_error)
+        }
+        cpal::SampleFormat::I32 => {
+            build_preroll_stream::<i32, _>(device, config, strategy, buffer.clone(), on_error)
+        }
+        cpal::SampleFormat::U16 => {
+            build_preroll_stream::<u16, _>(device, config, strategy, buffer.clone(), on_error)
+        }
+        cpal::SampleFormat::U8 => {
+            build_preroll_stream::<u8, _>(device, config, strategy, buffer.clone(), on_error)
+        }
+        f => Err(anyhow::anyhow!("Unsupported sample format: {:?}", f)),
+    }?;
+    stream.play().context("Failed to start pre-roll stream")?;
+
+    Ok(PrerollCapture {
+        _stream: SendStream(Some(stream)),
+        buffer,
+        duration_ms,
+        failed,
+    })
+}
+
+fn build_preroll_stream<T, E>(
+    device: &cpal::Device,
+    config: &cpal::SupportedStreamConfig,
+    strategy: ChannelStrategy,
+    buffer: SharedPrerollBuffer,
+    on_error: E,
+) -> Result<cpal::Stream>
+where
+    T: cpal::Sample + cpal::SizedSample + Send + 'static,
+    f32: cpal::FromSample<T>,
+    E: FnMut(cpal::StreamError) + Send + 'static,
+{
+    let channels = config.channels() as usize;
+    let mut mixer = ChannelMixer::new(strategy, channels, config.sample_rate() as usize);
+    let stream = device.build_input_stream(
+        &config.clone().into(),
+        move |data: &[T], _: &cpal::InputCallbackInfo| {
+            let mut buffer = buffer.lock();
+            for frame in data.chunks_exact(channels) {
+                buffer.push(mixer.mix(frame.iter().map(|&s| s.to_sample::<f32>())));
+            }
+        },
+        on_error,
+        None,
+    )?;
+
+    Ok(stream)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preroll_buffer_keeps_the_last_samples_once() {
+        let mut buffer = PrerollBuffer::new(16000, 300);
+        for i in 0..16000 {
+            buffer.push(i as f32);
+        }
+
+        let samples = buffer.take();
+        assert_eq!(samples.len(), 4800);
+        assert_eq!(samples[0], 11200.0);
+        assert!(buffer.take().is_empty());
+    }
+}
@@ -0,0 +1,129 @@
+use crate::audio::helpers::TARGET_SAMPLE_RATE;
+use crate::audio::pipeline::prepare_transcription;
+use crate::audio::resample::resample;
+use crate::audio::types::{
+    JobHandle, JobPriority, JobResult, PreviewBuffer, ResampleQuality, SampleRing,
+    SharedPreviewBuffer,
+};
+use crate::audio::worker::{cancel_transcription, submit_transcription};
+use log::debug;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::RecvTimeoutError;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Only the most recent audio is re-transcribed, so each preview stays cheap.
+const PREVIEW_WINDOW_SECS: usize = 10;
+const PREVIEW_INTERVAL: Duration = Duration::from_millis(800);
+/// Below this, there is not enough audio to recognize a word.
+const MIN_PREVIEW_SECS: f32 = 0.5;
+
+impl PreviewBuffer {
+    pub fn new(sample_rate: usize) -> Self {
+        Self {
+            samples: SampleRing::new(sample_rate * PREVIEW_WINDOW_SECS),
+            sample_rate,
+            pending: 0,
+        }
+    }
+
+    pub fn push(&mut self, sample: f32) {
+        self.samples.push(sample);
+        self.pending += 1;
+    }
+
+    /// Returns the window resampled for the engine, or `None` if no new audio arrived.
+    pub fn snapshot(&mut self) -> Option<Vec<f32>> {
+        let min_samples = (self.sample_rate as f32 * MIN_PREVIEW_SECS) as usize;
+        if self.pending == 0 || self.samples.len() < min_samples {
+            return None;
+        }
+        self.pending = 0;
+
+        // Previews are redone every interval, speed matters more than fidelity
+        Some(resample(
+            &self.samples.to_vec(),
+            self.sample_rate,
+            TARGET_SAMPLE_RATE,
+            ResampleQuality::Fast,
+        ))
+    }
+}
+
+/// Re-transcribes the recording window at a fixed interval and emits the text as
+/// `transcription-partial`, until `running` is cleared by the recorder.
+pub fn start_live_preview(app: &AppHandle, buffer: SharedPreviewBuffer, running: Arc<AtomicBool>) {
+    let app = app.clone();
+    std::thread::spawn(move || {
+        emit_partial(&app, "");
+
+        while running.load(Ordering::SeqCst) {
+            std::thread::sleep(PREVIEW_INTERVAL);
+
+            let Some(samples) = buffer.lock().snapshot() else {
+                continue;
+            };
+            let (samples, params, _) = prepare_transcription(&app, samples, 1);
+            let job = submit_transcription(&app, samples, params, JobPriority::Preview);
+
+            match wait_while_running(&app, job, &running) {
+                Some(Ok(result)) => emit_partial(&app, result.text.trim()),
+                Some(Err(e)) => debug!("Live preview failed: {}", e),
+                None => break,
+            }
+        }
+
+        debug!("Live preview stopped");
+    });
+}
+
+/// Cancels the job once recording stops, leaving the worker to the final transcription.
+fn wait_while_running(app: &AppHandle, job: JobHandle, running: &AtomicBool) -> Option<JobResult> {
+    loop {
+        match job.result.recv_timeout(Duration::from_millis(100)) {
+            Ok(result) => return running.load(Ordering::SeqCst).then_some(result),
+            Err(RecvTimeoutError::Timeout) if running.load(Ordering::SeqCst) => continue,
+            Err(RecvTimeoutError::Timeout) => {
+                cancel_transcription(app, job.id);
+                return None;
+            }
+            Err(RecvTimeoutError::Disconnected) => return None,
+        }
+    }
+}
+
+fn emit_partial(app: &AppHandle, text: &str) {
+    let _ = app.emit("transcription-partial", text);
+    if let Some(overlay_window) = app.get_webview_window("recording_overlay") {
+        let _ = overlay_window.emit("transcription-partial", text);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preview_buffer_keeps_the_last_window() {
+        let mut buffer = PreviewBuffer::new(TARGET_SAMPLE_RATE);
+        let total = TARGET_SAMPLE_RATE * (PREVIEW_WINDOW_SECS + 2);
+        for i in 0..total {
+            buffer.push(i as f32);
+        }
+
+        let window = buffer.snapshot().unwrap();
+        assert_eq!(window.len(), TARGET_SAMPLE_RATE * PREVIEW_WINDOW_SECS);
+        assert_eq!(window[0], (TARGET_SAMPLE_RATE * 2) as f32);
+        assert_eq!(buffer.snapshot(), None);
+    }
+
+    #[test]
+    fn test_preview_buffer_waits_for_enough_audio() {
+        let mut buffer = PreviewBuffer::new(TARGET_SAMPLE_RATE);
+        for _ in 0..TARGET_SAMPLE_RATE / 4 {
+            buffer.push(0.0);
+        }
+        assert_eq!(buffer.snapshot(), None);
+    }
+}
@@ -1,5 +1,15 @@
 use crate::audio::helpers::create_wav_writer;
+use crate::audio::microphone::{channel_strategy_for, negotiate_input_configs};
+use crate::audio::preroll::take_preroll;
+use crate::audio::preview::start_live_preview;
+use crate::audio::resample::resample;
 use crate::audio::sound;
+use crate::audio::types::{
+    AudioState, CaptureOutput, CaptureTaps, ChannelMixer, LimitEvent, PreviewBuffer, RecordedAudio,
+    RecordingLimit, RecordingLimitAction, RecordingMode, ResampleQuality, SampleRing,
+    SharedPreviewBuffer, SharedSamples, SharedStaging, SilenceDetector,
+};
+use crate::audio::vad::SPEECH_THRESHOLD;
 use anyhow::{Context, Error, Result};
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use cpal::Device;
@@ -13,49 +23,363 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tauri::{AppHandle, Emitter, Manager};
 
-const MAX_RECORDING_DURATION_SECS: u64 = 300; // 5 min
-
 type WavWriterType = WavWriter<BufWriter<File>>;
-type SharedWriter = Arc<Mutex<Option<WavWriterType>>>;
+pub type SharedWriter = Arc<Mutex<Option<WavWriterType>>>;
+
+/// Extra in-memory capacity past the recording limit, covering the samples captured
+/// between the limit being reached and the stream being stopped.
+const CAPTURE_HEADROOM_SECS: usize = 2;
+
+impl RecordingLimit {
+    fn new(sample_rate: usize, limit_secs: u32, warning_secs: u32, rollover: bool) -> Self {
+        let max_frames = sample_rate * limit_secs.max(1) as usize;
+        Self {
+            sample_rate,
+            max_frames,
+            warning_frames: max_frames.saturating_sub(sample_rate * warning_secs as usize),
+            rollover,
+            captured_frames: 0,
+            last_warning: None,
+            reached: false,
+        }
+    }
+
+    /// Counts newly captured frames, returns at most one event per call.
+    fn advance(&mut self, frames: usize) -> Option<LimitEvent> {
+        if self.reached {
+            return None;
+        }
+        self.captured_frames += frames;
+
+        if self.captured_frames >= self.max_frames {
+            if self.rollover {
+                self.captured_frames = 0;
+                self.last_warning = None;
+                return Some(LimitEvent::Rollover);
+            }
+            self.reached = true;
+            return Some(LimitEvent::Reached);
+        }
+        if self.warning_frames < self.max_frames && self.captured_frames >= self.warning_frames {
+            let remaining = (self.max_frames - self.captured_frames).div_ceil(self.sample_rate);
+            // Once per second, for the overlay countdown
+            if self.last_warning != Some(remaining as u32) {
+                self.last_warning = Some(remaining as u32);
+                return Some(LimitEvent::Warning(remaining as u32));
+            }
+        }
+        None
+    }
+}
+
+impl CaptureTaps {
+    /// Sends the samples of one callback to the WAV file or memory, and to the preview.
+    /// Returns false once the recording is finalized and nothing is captured anymore.
+    fn capture(&self, block: &[f32]) -> bool {
+        if let Some(staged) = self.staging.lock().as_mut() {
+            staged.extend_from_slice(block);
+            return true;
+        }
+
+        let mut writer = self.writer.lock();
+        if writer.is_none() && self.samples.is_none() {
+            return false;
+        }
+
+        if let Some(writer) = writer.as_mut() {
+            for &sample in block {
+                if let Err(e) = writer.write_sample((sample * i16::MAX as f32) as i16) {
+                    error!("Error writing sample: {}", e);
+                }
+            }
+        }
+        drop(writer);
+
+        if let Some(samples) = &self.samples {
+            let mut samples = samples.lock();
+            block.iter().for_each(|&sample| samples.push(sample));
+        }
+        if let Some(preview) = &self.preview {
+            let mut preview = preview.lock();
+            block.iter().for_each(|&sample| preview.push(sample));
+        }
+        true
+    }
+}
+
+/// Writes audio captured outside the stream callback, e.g. the pre-roll buffer.
+fn write_captured(
+    writer: &SharedWriter,
+    samples: &Option<SharedSamples>,
+    preview: &Option<SharedPreviewBuffer>,
+    audio: &[f32],
+) -> Result<()> {
+    if let Some(writer) = writer.lock().as_mut() {
+        for &sample in audio {
+            writer.write_sample((sample * i16::MAX as f32) as i16)?;
+        }
+    }
+    if let Some(samples) = samples {
+        let mut samples = samples.lock();
+        audio.iter().for_each(|&sample| samples.push(sample));
+    }
+    if let Some(preview) = preview {
+        let mut preview = preview.lock();
+        audio.iter().for_each(|&sample| preview.push(sample));
+    }
+    Ok(())
+}
+
+/// Drops the end of the pre-roll that the recording stream also heard, `staged`
+/// being the number of samples it captured before the pre-roll was taken.
+fn trim_preroll_overlap(mut preroll: Vec<f32>, staged: usize) -> Vec<f32> {
+    preroll.truncate(preroll.len().saturating_sub(staged));
+    preroll
+}
+
+/// Takes the pre-roll once the recording stream runs, so it also covers the time
+/// the device took to open, and writes it before the samples the stream staged
+/// meanwhile. The stream writes directly from then on. Returns the pre-roll length
+/// in milliseconds.
+fn splice_preroll(
+    app: &AppHandle,
+    writer: &SharedWriter,
+    samples: &Option<SharedSamples>,
+    preview: &Option<SharedPreviewBuffer>,
+    staging: &SharedStaging,
+    sample_rate: usize,
+) -> Result<u32> {
+    let (preroll, staged) = {
+        let staging = staging.lock();
+        (take_preroll(app), staging.as_ref().map_or(0, Vec::len))
+    };
+    let preroll = preroll.map_or_else(Vec::new, |(preroll, preroll_rate)| {
+        let preroll = resample(
+            &preroll,
+            preroll_rate,
+            sample_rate,
+            ResampleQuality::Balanced,
+        );
+        trim_preroll_overlap(preroll, staged)
+    });
+
+    let mut staging = staging.lock();
+    let staged = staging.take().unwrap_or_default();
+    debug!(
+        "Prepending {} pre-roll samples before {} staged ones",
+        preroll.len(),
+        staged.len()
+    );
+    write_captured(writer, samples, preview, &preroll)?;
+    write_captured(writer, samples, preview, &staged)?;
+    Ok((preroll.len() * 1000 / sample_rate.max(1)) as u32)
+}
+
+/// Takes the in-memory capture, reporting any samples lost because the buffer was full.
+fn take_samples(samples: &SharedSamples) -> Vec<f32> {
+    let mut samples = samples.lock();
+    if samples.dropped() > 0 {
+        warn!(
+            "In-memory capture was full, the first {} samples of the recording were lost",
+            samples.dropped()
+        );
+    }
+    samples.take()
+}
 
 // Wrapper to safely store Stream. Stream on macOS doesn't implement Send.
 pub struct SendStream(pub Option<cpal::Stream>);
 unsafe impl Send for SendStream {}
 unsafe impl Sync for SendStream {}
 
+impl CaptureOutput {
+    /// Hands over the audio captured so far. Later samples go to `next_file`,
+    /// or stay in memory when capturing in memory.
+    fn rollover(&mut self, next_file: Option<&Path>) -> Result<Option<RecordedAudio>> {
+        let mut writer_guard = self.writer.lock();
+        if let Some(writer) = writer_guard.take() {
+            let spec = writer.spec();
+            writer.finalize().context("Failed to finalize WAV file")?;
+            let finished = self.file_path.take().map(RecordedAudio::File);
+            if let Some(path) = next_file {
+                *writer_guard =
+                    Some(WavWriter::create(path, spec).context("Failed to create WAV file")?);
+                self.file_path = Some(path.to_path_buf());
+            }
+            return Ok(finished);
+        }
+
+        Ok(self.samples.as_ref().map(|samples| RecordedAudio::Samples {
+            samples: take_samples(samples),
+            sample_rate: self.sample_rate,
+        }))
+    }
+
+    /// Finalizes the capture and returns its audio, only on the first call.
+    fn finish(&mut self) -> Result<Option<RecordedAudio>> {
+        let mut writer_guard = self.writer.lock();
+        if let Some(writer) = writer_guard.take() {
+            writer.finalize().context("Failed to finalize WAV file")?;
+            return Ok(self.file_path.take().map(RecordedAudio::File));
+        }
+
+        Ok(self
+            .samples
+            .take()
+            .map(|samples| take_samples(&samples))
+            .map(|samples| RecordedAudio::Samples {
+                samples,
+                sample_rate: self.sample_rate,
+            }))
+    }
+}
+
 pub struct AudioRecorder {
-    writer: SharedWriter,
+    output: CaptureOutput,
+    /// Pre-roll at the start of the audio not handed over yet
+    preroll_ms: u32,
     stream: SendStream,
     app_handle: AppHandle,
     start_time: Option<std::time::Instant>,
+    preview_buffer: Option<SharedPreviewBuffer>,
+    /// Cleared on stop to end the live preview thread
+    preview_running: Arc<AtomicBool>,
+    paused: Arc<AtomicBool>,
 }
 
 impl AudioRecorder {
-    pub fn new(app: AppHandle, file_path: &Path, limit_reached: Arc<AtomicBool>) -> Result<Self> {
+    /// Records to the WAV file at `file_path`, or in memory when it is `None`.
+    pub fn new(
+        app: AppHandle,
+        file_path: Option<&Path>,
+        limit_reached: Arc<AtomicBool>,
+    ) -> Result<Self> {
         // Reset the limit flag at the start of each recording
         limit_reached.store(false, Ordering::SeqCst);
 
         let device = Self::get_device(app.clone())?;
-        let config = device
-            .default_input_config()
-            .context("No input config available")?;
 
-        let writer = create_wav_writer(file_path, &config)?;
-        let writer_arc = Arc::new(Mutex::new(Some(writer)));
+        let strategy = channel_strategy_for(&crate::settings::load_settings(&app));
+        let mut last_error = None;
+        // Some drivers list configs they then refuse to open or start
+        for config in negotiate_input_configs(&device, strategy.min_channels())? {
+            match Self::with_config(&app, &device, &config, file_path, &limit_reached) {
+                Ok(recorder) => return Ok(recorder),
+                Err(e) => {
+                    warn!(
+                        "Failed to open {} Hz/{} ch stream ({:#}), trying the next config",
+                        config.sample_rate(),
+                        config.channels(),
+                        e
+                    );
+                    last_error = Some(e);
+                }
+            }
+        }
+        Err(last_error.unwrap_or_else(|| anyhow::anyhow!("No input config available")))
+    }
+
+    fn with_config(
+        app: &AppHandle,
+        device: &Device,
+        config: &cpal::SupportedStreamConfig,
+        file_path: Option<&Path>,
+        limit_reached: &Arc<AtomicBool>,
+    ) -> Result<Self> {
+        let sample_rate = config.sample_rate() as usize;
+        debug!(
+            "Capturing at {} Hz, {} channel(s), {:?}",
+            sample_rate,
+            config.channels(),
+            config.sample_format()
+        );
+
+        let writer = match file_path {
+            Some(path) => Some(create_wav_writer(path, config)?),
+            None => None,
+        };
+        let writer_arc = Arc::new(Mutex::new(writer));
+        let settings = crate::settings::load_settings(app);
+        // Sized for the longest recording: the pre-roll, then up to the limit, where the
+        // stream stops or rolls over, plus headroom for the time the stop takes
+        let samples = file_path.is_none().then(|| {
+            let capacity = sample_rate * settings.preroll_ms as usize / 1000
+                + sample_rate * (settings.recording_limit_secs as usize + CAPTURE_HEADROOM_SECS);
+            Arc::new(Mutex::new(SampleRing::new(capacity)))
+        });
+
+        // Chunks of LLM and command recordings would not make sense on their own
+        let rollover = settings.recording_limit_action == RecordingLimitAction::Rollover
+            && app.state::<AudioState>().get_recording_mode() == RecordingMode::Standard;
+        let limit = RecordingLimit::new(
+            sample_rate,
+            settings.recording_limit_secs,
+            settings.recording_limit_warning_secs,
+            rollover,
+        );
+        let silence_detector = (settings.record_mode == "hands_free").then(|| {
+            SilenceDetector::new(
+                sample_rate,
+                SPEECH_THRESHOLD,
+                settings.hands_free_silence_ms,
+            )
+        });
+
+        let preview_buffer = settings
+            .live_preview_enabled
+            .then(|| Arc::new(Mutex::new(PreviewBuffer::new(sample_rate))));
+        let mixer = ChannelMixer::new(
+            channel_strategy_for(&settings),
+            config.channels() as usize,
+            sample_rate,
+        );
+
+        let paused = Arc::new(AtomicBool::new(false));
+        let staging: SharedStaging = Arc::new(Mutex::new(Some(Vec::new())));
+        let taps = CaptureTaps {
+            writer: writer_arc.clone(),
+            samples: samples.clone(),
+            preview: preview_buffer.clone(),
+            silence_detector,
+            paused: paused.clone(),
+            staging: staging.clone(),
+        };
 
         let stream = build_stream(
-            &device,
-            &config,
-            writer_arc.clone(),
+            device,
+            config,
+            taps,
+            mixer,
+            limit,
             app.clone(),
-            limit_reached,
+            limit_reached.clone(),
         )?;
+        stream.play().context("Failed to start stream")?;
+        // Audio heard just before the shortcut, so the first syllable is not clipped
+        let preroll_ms = splice_preroll(
+            app,
+            &writer_arc,
+            &samples,
+            &preview_buffer,
+            &staging,
+            sample_rate,
+        )
+        .context("Failed to write pre-roll audio")?;
 
         Ok(Self {
-            writer: writer_arc,
+            output: CaptureOutput {
+                writer: writer_arc,
+                file_path: file_path.map(Path::to_path_buf),
+                samples,
+                sample_rate,
+            },
+            preroll_ms,
             stream: SendStream(Some(stream)),
-            app_handle: app,
+            app_handle: app.clone(),
             start_time: None,
+            preview_buffer,
+            preview_running: Arc::new(AtomicBool::new(false)),
+            paused,
         })
     }
 
@@ -70,7 +394,7 @@ impl AudioRecorder {
     ///
     /// # Returns
     /// * `Result<Device, Error>` - The audio input device or an error if none is available.
-    fn get_device(app: AppHandle) -> Result<Device, Error> {
+    pub fn get_device(app: AppHandle) -> Result<Device, Error> {
         let audio_state = app.state::<crate::audio::types::AudioState>();
 
         // Check if we have a cached device (user selected a specific mic)
@@ -85,8 +409,8 @@ impl AudioRecorder {
             warn!("Cached microphone unavailable, trying configured microphone/default fallback");
 
             let settings = crate::settings::load_settings(&app);
-            if let Some(mic_id) = settings.mic_id {
-                if let Some(resolved) = crate::audio::microphone::resolve_input_device(&mic_id) {
+            if let Some(mic_id) = &settings.mic_id {
+                if let Some(resolved) = crate::audio::microphone::resolve_input_device(mic_id) {
                     if resolved.default_input_config().is_ok() {
                         if let Ok(desc) = resolved.description() {
                             debug!(
@@ -101,6 +425,9 @@ impl AudioRecorder {
             }
 
             audio_state.set_cached_device(None);
+            if let Some(mic_id) = &settings.mic_id {
+                crate::audio::microphone::emit_mic_fallback(&app, mic_id);
+            }
         }
 
         // No cached device - use system default
@@ -115,57 +442,94 @@ impl AudioRecorder {
     }
 
     pub fn start(&mut self) -> Result<()> {
-        if let Some(stream) = &self.stream.0 {
-            stream.play().context("Failed to start stream")?;
+        if self.stream.0.is_some() {
             self.start_time = Some(std::time::Instant::now());
             let settings = crate::settings::load_settings(&self.app_handle);
             if settings.sound_enabled {
                 sound::play_sound(&self.app_handle, sound::Sound::StartRecording);
             }
+            if let Some(buffer) = &self.preview_buffer {
+                self.preview_running.store(true, Ordering::SeqCst);
+                start_live_preview(
+                    &self.app_handle,
+                    buffer.clone(),
+                    self.preview_running.clone(),
+                );
+            }
         }
         Ok(())
     }
 
-    pub fn stop(&mut self) -> Result<()> {
+    /// Stops writing samples while keeping the stream and the WAV file open.
+    /// Returns false when already paused.
+    pub fn pause(&self) -> bool {
+        !self.paused.swap(true, Ordering::SeqCst)
+    }
+
+    /// Continues writing to the same file or buffer. Returns false when not paused.
+    pub fn resume(&self) -> bool {
+        self.paused.swap(false, Ordering::SeqCst)
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+
+    /// Duration of the pre-roll in the audio returned by the next `rollover` or
+    /// `stop`, only the first chunk of a recording has one.
+    pub fn take_preroll_ms(&mut self) -> u32 {
+        std::mem::take(&mut self.preroll_ms)
+    }
+
+    /// Hands over the audio recorded so far while the stream keeps running. Later
+    /// samples go to `next_file`, or stay in memory when capturing in memory.
+    pub fn rollover(&mut self, next_file: Option<&Path>) -> Result<Option<RecordedAudio>> {
+        self.output.rollover(next_file)
+    }
+
+    /// Stops the stream and returns the recorded audio, only on the first call.
+    pub fn stop(&mut self) -> Result<Option<RecordedAudio>> {
         // Drop stream first to stop recording
         self.stream.0 = None;
+        self.preview_running.store(false, Ordering::SeqCst);
         self.start_time = None;
 
-        // Finalize writer
-        let mut writer_guard = self.writer.lock();
-        if let Some(writer) = writer_guard.take() {
-            writer.finalize().context("Failed to finalize WAV file")?;
+        let recorded = self.output.finish()?;
+
+        if recorded.is_some() {
             let settings = crate::settings::load_settings(&self.app_handle);
             if settings.sound_enabled {
                 sound::play_sound(&self.app_handle, sound::Sound::StopRecording);
             }
         }
-        Ok(())
+        Ok(recorded)
     }
 }
 
 fn build_stream(
     device: &cpal::Device,
     config: &cpal::SupportedStreamConfig,
-    writer: SharedWriter,
+    taps: CaptureTaps,
+    mixer: ChannelMixer,
+    limit: RecordingLimit,
     app: AppHandle,
     limit_reached: Arc<AtomicBool>,
 ) -> Result<cpal::Stream> {
     match config.sample_format() {
         cpal::SampleFormat::F32 => {
-            build_stream_impl::<f32>(device, config, writer, app, limit_reached.clone())
+            build_stream_impl::<f32>(device, config, taps, mixer, limit, app, limit_reached)
         }
         cpal::SampleFormat::I16 => {
-            build_stream_impl::<i16>(device, config, writer, app, limit_reached.clone())
+            build_stream_impl::<i16>(device, config, taps, mixer, limit, app, limit_reached)
         }
         cpal::SampleFormat::I32 => {
-            build_stream_impl::<i32>(device, config, writer, app, limit_reached.clone())
+            build_stream_impl::<i32>(device, config, taps, mixer, limit, app, limit_reached)
         }
         cpal::SampleFormat::U16 => {
-            build_stream_impl::<u16>(device, config, writer, app, limit_reached.clone())
+            build_stream_impl::<u16>(device, config, taps, mixer, limit, app, limit_reached)
         }
         cpal::SampleFormat::U8 => {
-            build_stream_impl::<u8>(device, config, writer, app, limit_reached.clone())
+            build_stream_impl::<u8>(device, config, taps, mixer, limit, app, limit_reached)
         }
         f => Err(anyhow::anyhow!("Unsupported sample format: {:?}", f)),
     }
@@ -174,7 +538,9 @@ fn build_stream(
 fn build_stream_impl<T>(
     device: &cpal::Device,
     config: &cpal::SupportedStreamConfig,
-    writer: SharedWriter,
+    mut taps: CaptureTaps,
+    mut mixer: ChannelMixer,
+    mut limit: RecordingLimit,
     app: AppHandle,
     limit_reached_flag: Arc<AtomicBool>,
 ) -> Result<cpal::Stream>
@@ -191,46 +557,54 @@ where
     let mut ema_level: f32 = 0.0;
     let alpha: f32 = 0.35; // smoothing factor
     let mut last_emit = std::time::Instant::now();
-    let start_time = std::time::Instant::now();
-    let mut local_limit_triggered = false;
+    // Mono samples of the current callback, reused to avoid allocating on the audio thread
+    let mut block: Vec<f32> = Vec::new();
 
     let app_handle = app.clone();
-    let writer_clone = writer.clone();
 
     let stream = device.build_input_stream(
         &config.clone().into(),
         move |data: &[T], _: &cpal::InputCallbackInfo| {
-            // Check for duration limit
-            if !local_limit_triggered
-                && start_time.elapsed()
-                    >= std::time::Duration::from_secs(MAX_RECORDING_DURATION_SECS)
-            {
-                local_limit_triggered = true;
-                // Set the shared atomic flag - this is the reliable cross-thread communication
-                limit_reached_flag.store(true, Ordering::SeqCst);
-                // Also emit event for UI updates
-                let _ = app_handle.emit("recording-limit-reached", ());
+            block.clear();
+            if !taps.paused.load(Ordering::Relaxed) {
+                block.extend(
+                    data.chunks_exact(channels)
+                        .map(|frame| mixer.mix(frame.iter().map(|&s| s.to_sample::<f32>()))),
+                );
+            }
+            if !taps.capture(&block) {
+                block.clear();
             }
+            let captured_frames = block.len();
 
-            let mut recorder = writer_clone.lock();
-            if let Some(writer) = recorder.as_mut() {
-                for frame in data.chunks_exact(channels) {
-                    let sample = if channels == 1 {
-                        frame[0].to_sample::<f32>()
-                    } else {
-                        frame.iter().map(|&s| s.to_sample::<f32>()).sum::<f32>() / channels as f32
-                    };
-
-                    // write to WAV
-                    let sample_i16 = (sample * i16::MAX as f32) as i16;
-                    if let Err(e) = writer.write_sample(sample_i16) {
-                        error!("Error writing sample: {}", e);
-                    }
+            // Analysis runs once the locks are released, so it never holds up the writer
+            acc_sum_squares += block.iter().map(|sample| sample * sample).sum::<f32>();
+            acc_count += block.len();
+            let silence_detected = taps
+                .silence_detector
+                .as_mut()
+                .is_some_and(|detector| detector.push_block(&block));
 
-                    // accumulate for RMS
-                    acc_sum_squares += sample * sample;
-                    acc_count += 1;
+            // Check for duration limit
+            match limit.advance(captured_frames) {
+                Some(LimitEvent::Warning(remaining_secs)) => {
+                    let _ = app_handle.emit("recording-limit-warning", remaining_secs);
+                }
+                Some(LimitEvent::Reached) => {
+                    // Set the shared atomic flag - this is the reliable cross-thread communication
+                    limit_reached_flag.store(true, Ordering::SeqCst);
+                    // Also emit event for UI updates
+                    let _ = app_handle.emit("recording-limit-reached", ());
                 }
+                Some(LimitEvent::Rollover) => {
+                    let _ = app_handle.emit("recording-rollover", ());
+                }
+                None => {}
+            }
+
+            // Hands-free mode: stop once the speaker has gone quiet
+            if silence_detected {
+                let _ = app_handle.emit("recording-silence-detected", ());
             }
 
             // Throttle to ~30 FPS
@@ -269,3 +643,134 @@ where
 
     Ok(stream)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_RATE: usize = 16000;
+
+    fn run(limit: &mut RecordingLimit, secs: usize) -> Vec<LimitEvent> {
+        // 10 ms callbacks, like a typical input stream
+        (0..secs * 100)
+            .filter_map(|_| limit.advance(SAMPLE_RATE / 100))
+            .collect()
+    }
+
+    #[test]
+    fn test_recording_limit_counts_down_then_stops() {
+        let mut limit = RecordingLimit::new(SAMPLE_RATE, 30, 3, false);
+        let events = run(&mut limit, 40);
+        assert_eq!(
+            events,
+            vec![
+                LimitEvent::Warning(3),
+                LimitEvent::Warning(2),
+                LimitEvent::Warning(1),
+                LimitEvent::Reached,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_recording_limit_rolls_over_and_ignores_pauses() {
+        let mut limit = RecordingLimit::new(SAMPLE_RATE, 30, 0, true);
+        assert_eq!(run(&mut limit, 25), vec![]);
+        // Paused callbacks capture nothing
+        assert_eq!(limit.advance(0), None);
+        assert_eq!(run(&mut limit, 5), vec![LimitEvent::Rollover]);
+        assert_eq!(run(&mut limit, 30), vec![LimitEvent::Rollover]);
+    }
+
+    #[test]
+    fn test_rollover_twice_keeps_the_final_chunk() {
+        use crate::audio::helpers::{read_fixture, remove_recording};
+
+        let dir = std::env::temp_dir().join(format!("murmure-rollover-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let paths: Vec<_> = (0..3)
+            .map(|i| dir.join(format!("chunk-{}.wav", i)))
+            .collect();
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: SAMPLE_RATE as u32,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut output = CaptureOutput {
+            writer: Arc::new(Mutex::new(Some(
+                WavWriter::create(&paths[0], spec).unwrap(),
+            ))),
+            file_path: Some(paths[0].clone()),
+            samples: None,
+            sample_rate: SAMPLE_RATE,
+        };
+        let taps = CaptureTaps {
+            writer: output.writer.clone(),
+            samples: None,
+            preview: None,
+            silence_detector: None,
+            paused: Arc::new(AtomicBool::new(false)),
+            staging: Arc::new(Mutex::new(None)),
+        };
+
+        for next in &paths[1..] {
+            assert!(taps.capture(&[0.25; 1600]));
+            let Some(RecordedAudio::File(chunk)) = output.rollover(Some(next)).unwrap() else {
+                panic!("Rollover did not hand over a file");
+            };
+            // Each chunk is processed and deleted while the next one is recorded
+            assert_eq!(read_fixture(&chunk).0.len(), 1600);
+            remove_recording(&chunk);
+        }
+        assert!(taps.capture(&[0.5; 800]));
+
+        let Some(RecordedAudio::File(last)) = output.finish().unwrap() else {
+            panic!("Stop did not hand over the final chunk");
+        };
+        assert_eq!(last, paths[2]);
+        let (samples, _) = read_fixture(&last);
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(samples.len(), 800);
+        assert!(samples.iter().all(|&s| (s - 0.5).abs() < 1e-3));
+        assert!(!taps.capture(&[0.5; 10]));
+    }
+
+    #[test]
+    fn test_staged_samples_follow_the_preroll() {
+        let samples: SharedSamples = Arc::new(Mutex::new(SampleRing::new(16)));
+        let writer: SharedWriter = Arc::new(Mutex::new(None));
+        let staging: SharedStaging = Arc::new(Mutex::new(Some(Vec::new())));
+        let taps = CaptureTaps {
+            writer: writer.clone(),
+            samples: Some(samples.clone()),
+            preview: None,
+            silence_detector: None,
+            paused: Arc::new(AtomicBool::new(false)),
+            staging: staging.clone(),
+        };
+
+        // The recording stream starts while the pre-roll stream is still running
+        assert!(taps.capture(&[3.0, 4.0]));
+        assert!(samples.lock().take().is_empty());
+
+        let preroll = trim_preroll_overlap(vec![1.0, 2.0, 3.0, 4.0], 2);
+        let staged = staging.lock().take().unwrap();
+        let samples_tap = Some(samples.clone());
+        write_captured(&writer, &samples_tap, &None, &preroll).unwrap();
+        write_captured(&writer, &samples_tap, &None, &staged).unwrap();
+        assert!(taps.capture(&[5.0]));
+
+        assert_eq!(samples.lock().take(), vec![1.0, 2.0, 3.0, 4.0, 5.0]);
+    }
+
+    #[test]
+    fn test_take_samples_resets_overflow() {
+        let samples: SharedSamples = Arc::new(Mutex::new(SampleRing::new(4)));
+        (0..6).for_each(|i| samples.lock().push(i as f32));
+        assert_eq!(samples.lock().dropped(), 2);
+
+        assert_eq!(take_samples(&samples), vec![2.0, 3.0, 4.0, 5.0]);
+        assert_eq!(samples.lock().dropped(), 0);
+    }
+}
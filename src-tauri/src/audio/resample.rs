@@ -0,0 +1,368 @@
+use crate::audio::types::{Kernel, ResampleQuality};
+
+/// Above this many filter phases, e.g. for unusual device rates with no common divisor,
+/// the coefficients are tabulated on a grid of this many phases and interpolated.
+const MAX_TABLE_PHASES: usize = 1024;
+
+impl ResampleQuality {
+    /// Zero crossings of the sinc on each side, Kaiser beta and passband edge
+    /// as a fraction of the output Nyquist frequency.
+    fn filter(self) -> (f64, f64, f64) {
+        match self {
+            ResampleQuality::Fast => (8.0, 5.0, 0.85),
+            ResampleQuality::Balanced => (16.0, 8.0, 0.9),
+            ResampleQuality::Best => (32.0, 10.0, 0.94),
+        }
+    }
+}
+
+/// Converts mono samples from `src_hz` to `dst_hz` with a Kaiser-windowed sinc filter.
+/// When downsampling, the filter cuts below the output Nyquist frequency so high
+/// frequencies are removed instead of folding back into the speech band.
+pub fn resample(input: &[f32], src_hz: usize, dst_hz: usize, quality: ResampleQuality) -> Vec<f32> {
+    if input.is_empty() || src_hz == 0 || dst_hz == 0 {
+        return Vec::new();
+    }
+    if src_hz == dst_hz {
+        return input.to_vec();
+    }
+
+    let divisor = gcd(src_hz, dst_hz);
+    let (up, down) = (dst_hz / divisor, src_hz / divisor);
+    let kernel = Kernel::new(src_hz, dst_hz, quality);
+    let exact = up <= MAX_TABLE_PHASES;
+    // The grid also holds the phase one input sample ahead, to interpolate towards it
+    let table: Vec<Vec<f32>> = match exact {
+        true => (0..up)
+            .map(|phase| kernel.coefficients(phase as f64 / up as f64))
+            .collect(),
+        false => (0..=MAX_TABLE_PHASES)
+            .map(|phase| kernel.coefficients(phase as f64 / MAX_TABLE_PHASES as f64))
+            .collect(),
+    };
+
+    let out_len = (input.len() * up).div_ceil(down);
+    let mut output = Vec::with_capacity(out_len);
+    let mut interpolated = vec![0.0; 2 * kernel.half_taps];
+    for i in 0..out_len {
+        let position = i * down;
+        let (index, phase) = (position / up, position % up);
+        let coefficients = match exact {
+            true => &table[phase],
+            false => {
+                let grid_position = phase as f64 * MAX_TABLE_PHASES as f64 / up as f64;
+                let grid_phase = grid_position as usize;
+                let weight = (grid_position - grid_phase as f64) as f32;
+                let (before, after) = (&table[grid_phase], &table[grid_phase + 1]);
+                for (tap, coefficient) in interpolated.iter_mut().enumerate() {
+                    *coefficient = before[tap] + (after[tap] - before[tap]) * weight;
+                }
+                &interpolated
+            }
+        };
+
+        // Taps falling outside the input are treated as silence
+        let first = index as isize - kernel.half_taps as isize + 1;
+        let sum: f32 = coefficients
+            .iter()
+            .enumerate()
+            .filter_map(|(tap, coefficient)| {
+                let source = usize::try_from(first + tap as isize).ok()?;
+                input.get(source).map(|sample| sample * coefficient)
+            })
+            .sum();
+        output.push(sum);
+    }
+
+    output
+}
+
+impl Kernel {
+    fn new(src_hz: usize, dst_hz: usize, quality: ResampleQuality) -> Self {
+        let (zero_crossings, beta, passband) = quality.filter();
+        let cutoff = passband * (dst_hz as f64 / src_hz as f64).min(1.0);
+        let half_width = zero_crossings / cutoff;
+        Self {
+            cutoff,
+            half_width,
+            half_taps: half_width.ceil() as usize,
+            beta,
+        }
+    }
+
+    /// Taps for an output sample lying `frac` input samples after an input sample,
+    /// normalized so a constant signal keeps its level.
+    fn coefficients(&self, frac: f64) -> Vec<f32> {
+        let taps: Vec<f64> = (0..2 * self.half_taps)
+            .map(|tap| {
+                let distance = tap as f64 - self.half_taps as f64 + 1.0 - frac;
+                self.cutoff * sinc(self.cutoff * distance) * self.window(distance)
+            })
+            .collect();
+        let sum: f64 = taps.iter().sum();
+        taps.iter().map(|tap| (tap / sum) as f32).collect()
+    }
+
+    fn window(&self, distance: f64) -> f64 {
+        let x = distance / self.half_width;
+        if x.abs() >= 1.0 {
+            return 0.0;
+        }
+        bessel_i0(self.beta * (1.0 - x * x).sqrt()) / bessel_i0(self.beta)
+    }
+}
+
+fn sinc(x: f64) -> f64 {
+    if x.abs() < 1e-9 {
+        return 1.0;
+    }
+    let x = std::f64::consts::PI * x;
+    x.sin() / x
+}
+
+/// Zeroth-order modified Bessel function of the first kind, by its power series.
+fn bessel_i0(x: f64) -> f64 {
+    let mut sum = 1.0;
+    let mut term = 1.0;
+    let half_x = x / 2.0;
+    for k in 1..50 {
+        term *= half_x / k as f64;
+        sum += term * term;
+        if term * term < sum * 1e-12 {
+            break;
+        }
+    }
+    sum
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::helpers::{benchmark_fixtures, read_fixture, TARGET_SAMPLE_RATE};
+
+    fn tone(freq: f64, sample_rate: usize, seconds: f64) -> Vec<f32> {
+        (0..(sample_rate as f64 * seconds) as usize)
+            .map(|i| {
+                (0.5 * (2.0 * std::f64::consts::PI * freq * i as f64 / sample_rate as f64).sin())
+                    as f32
+            })
+            .collect()
+    }
+
+    /// Amplitude of `freq` in `samples`, by correlation with a sine and a cosine.
+    fn amplitude(samples: &[f32], freq: f64, sample_rate: usize) -> f64 {
+        // Skip the filter transients at both ends
+        let margin = samples.len() / 10;
+        let samples = &samples[margin..samples.len() - margin];
+        let (mut re, mut im) = (0.0, 0.0);
+        for (i, &sample) in samples.iter().enumerate() {
+            let angle = 2.0 * std::f64::consts::PI * freq * i as f64 / sample_rate as f64;
+            re += sample as f64 * angle.cos();
+            im += sample as f64 * angle.sin();
+        }
+        2.0 * (re * re + im * im).sqrt() / samples.len() as f64
+    }
+
+    fn db(ratio: f64) -> f64 {
+        20.0 * ratio.log10()
+    }
+
+    /// The former linear interpolation, as a baseline.
+    fn linear(input: &[f32], src_hz: usize, dst_hz: usize) -> Vec<f32> {
+        let ratio = src_hz as f64 / dst_hz as f64;
+        (0..input.len() * dst_hz / src_hz)
+            .map(|i| {
+                let t = i as f64 * ratio;
+                let index = t as usize;
+                let next = input[(index + 1).min(input.len() - 1)];
+                input[index] + (next - input[index]) * (t - index as f64) as f32
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_resample_output_length_and_passthrough() {
+        let input = tone(440.0, 48000, 1.0);
+        assert_eq!(
+            resample(&input, 48000, TARGET_SAMPLE_RATE, ResampleQuality::Fast).len(),
+            TARGET_SAMPLE_RATE
+        );
+        assert_eq!(
+            resample(
+                &input[..44100],
+                44100,
+                TARGET_SAMPLE_RATE,
+                ResampleQuality::Fast
+            )
+            .len(),
+            TARGET_SAMPLE_RATE
+        );
+        assert_eq!(resample(&input, 48000, 48000, ResampleQuality::Best), input);
+    }
+
+    #[test]
+    fn test_resample_keeps_the_speech_band() {
+        for src_hz in [44100, 48000] {
+            let input = tone(1000.0, src_hz, 0.5);
+            let output = resample(
+                &input,
+                src_hz,
+                TARGET_SAMPLE_RATE,
+                ResampleQuality::Balanced,
+            );
+            let gain = amplitude(&output, 1000.0, TARGET_SAMPLE_RATE) / 0.5;
+            assert!(db(gain).abs() < 0.1, "{} Hz: {:.2} dB", src_hz, db(gain));
+        }
+    }
+
+    #[test]
+    fn test_resample_rejects_aliasing() {
+        // 12 kHz at 48 kHz folds back to 4 kHz once sampled at 16 kHz
+        let input = tone(12000.0, 48000, 0.5);
+        let alias = |output: &[f32]| db(amplitude(output, 4000.0, TARGET_SAMPLE_RATE) / 0.5);
+
+        let baseline = alias(&linear(&input, 48000, TARGET_SAMPLE_RATE));
+        for (quality, min_rejection) in [
+            (ResampleQuality::Fast, 60.0),
+            (ResampleQuality::Balanced, 90.0),
+            (ResampleQuality::Best, 110.0),
+        ] {
+            let rejection = -alias(&resample(&input, 48000, TARGET_SAMPLE_RATE, quality));
+            assert!(
+                rejection >= min_rejection,
+                "{:?}: {:.1} dB of rejection",
+                quality,
+                rejection
+            );
+            assert!(rejection > -baseline + 30.0);
+        }
+    }
+
+    #[test]
+    fn test_resample_without_phase_table() {
+        // No common divisor with 16 kHz, so 16000 phases, above MAX_TABLE_PHASES
+        let src_hz = 44101;
+        assert!(TARGET_SAMPLE_RATE / gcd(src_hz, TARGET_SAMPLE_RATE) > MAX_TABLE_PHASES);
+
+        let input = tone(1000.0, src_hz, 0.5);
+        let output = resample(
+            &input,
+            src_hz,
+            TARGET_SAMPLE_RATE,
+            ResampleQuality::Balanced,
+        );
+        assert_eq!(
+            output.len(),
+            (input.len() * TARGET_SAMPLE_RATE).div_ceil(src_hz)
+        );
+        let gain = amplitude(&output, 1000.0, TARGET_SAMPLE_RATE) / 0.5;
+        assert!(db(gain).abs() < 0.1, "{:.2} dB", db(gain));
+
+        // 12 kHz folds back to 4 kHz once sampled at 16 kHz
+        let input = tone(12000.0, src_hz, 0.5);
+        let output = resample(
+            &input,
+            src_hz,
+            TARGET_SAMPLE_RATE,
+            ResampleQuality::Balanced,
+        );
+        let rejection = -db(amplitude(&output, 4000.0, TARGET_SAMPLE_RATE) / 0.5);
+        assert!(rejection >= 90.0, "{:.1} dB of rejection", rejection);
+    }
+
+    /// Compares the word error rate of linear interpolation and each preset on real speech.
+    ///
+    /// Needs the model and the WAV recordings described in `fixtures/README.md`, recorded
+    /// above 16 kHz, so it is ignored by default:
+    /// `cargo test --release compare_resampler_wer -- --ignored --nocapture`
+    #[test]
+    #[ignore]
+    fn compare_resampler_wer() {
+        use crate::engine::{DecodingOptions, ModelParams, ParakeetModel};
+
+        let Some((model_dir, wavs)) = benchmark_fixtures() else {
+            return;
+        };
+
+        let mut model =
+            ParakeetModel::new(&model_dir, &ModelParams::int8()).expect("Failed to load model");
+
+        let methods: [(&str, Option<ResampleQuality>); 4] = [
+            ("linear", None),
+            ("fast", Some(ResampleQuality::Fast)),
+            ("balanced", Some(ResampleQuality::Balanced)),
+            ("best", Some(ResampleQuality::Best)),
+        ];
+        let mut errors = [0usize; 4];
+        let mut reference_words = 0;
+
+        for wav in wavs {
+            let reference = std::fs::read_to_string(wav.with_extension("txt"))
+                .expect("Missing reference transcript");
+            let (samples, src_hz) = read_fixture(&wav);
+
+            reference_words += normalize_words(&reference).len();
+            for (method_errors, (name, quality)) in errors.iter_mut().zip(methods) {
+                let resampled = match quality {
+                    Some(quality) => resample(&samples, src_hz, TARGET_SAMPLE_RATE, quality),
+                    None => linear(&samples, src_hz, TARGET_SAMPLE_RATE),
+                };
+                let text = model
+                    .transcribe_samples(resampled, &DecodingOptions::default())
+                    .expect("Transcription failed")
+                    .text;
+                let distance = word_distance(&normalize_words(&reference), &normalize_words(&text));
+                *method_errors += distance;
+                println!(
+                    "{} | {} | {} errors | {}",
+                    wav.display(),
+                    name,
+                    distance,
+                    text
+                );
+            }
+        }
+
+        for ((name, _), method_errors) in methods.iter().zip(errors) {
+            println!(
+                "{}: WER {:.2}%",
+                name,
+                100.0 * method_errors as f64 / reference_words.max(1) as f64
+            );
+        }
+    }
+
+    fn normalize_words(text: &str) -> Vec<String> {
+        text.split_whitespace()
+            .map(|word| {
+                word.chars()
+                    .filter(|c| c.is_alphanumeric() || *c == '\'')
+                    .collect::<String>()
+                    .to_lowercase()
+            })
+            .filter(|word| !word.is_empty())
+            .collect()
+    }
+
+    /// Word-level Levenshtein distance: substitutions, insertions and deletions.
+    fn word_distance(reference: &[String], hypothesis: &[String]) -> usize {
+        let mut previous: Vec<usize> = (0..=hypothesis.len()).collect();
+        for (i, reference_word) in reference.iter().enumerate() {
+            let mut current = vec![i + 1; hypothesis.len() + 1];
+            for (j, hypothesis_word) in hypothesis.iter().enumerate() {
+                let substitution = previous[j] + usize::from(reference_word != hypothesis_word);
+                current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+            }
+            previous = current;
+        }
+        previous[hypothesis.len()]
+    }
+}
@@ -1,12 +1,20 @@
-use crate::audio::recorder::AudioRecorder;
-use crate::engine::ParakeetEngine;
+use crate::audio::microphone::MicDevice;
+use crate::audio::recorder::{AudioRecorder, SendStream, SharedWriter};
+use crate::engine::transcription_engine::TranscriptionResult;
+use crate::engine::{BenchmarkResult, InferenceParams};
 use cpal::Device;
-use parking_lot::Mutex;
-use std::sync::atomic::{AtomicBool, Ordering};
+use parking_lot::{Condvar, Mutex};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::{Receiver, Sender};
+use std::sync::Arc;
+use std::time::SystemTime;
 
 pub struct AudioState {
     pub recorder: Mutex<Option<AudioRecorder>>,
-    pub engine: Mutex<Option<ParakeetEngine>>,
+    pub engine_status: Mutex<EngineStatus>,
     pub current_file_name: Mutex<Option<String>>,
     recording_mode: std::sync::atomic::AtomicU8,
     /// Flag indicating recording duration limit has been reached
@@ -17,9 +25,428 @@ pub struct AudioState {
     pub invert_enter_signal: std::sync::Arc<AtomicBool>,
     /// Tracks whether invert feedback was already shown on second click.
     pub invert_feedback_shown_early: std::sync::Arc<AtomicBool>,
+    /// Warm input stream buffering audio between recordings, when enabled
+    pub preroll: Mutex<Option<PrerollCapture>>,
+    /// Wakes the pre-roll monitor when its settings or policy inputs change
+    pub preroll_wakeup: Mutex<Option<Sender<()>>>,
+    /// Processing of the last rolled-over chunk, later chunks wait for it so pastes keep their order
+    pub pending_chunk: Mutex<Option<std::thread::JoinHandle<()>>>,
+    /// Last recordings, newest first, kept for reprocessing
+    pub recent_recordings: Mutex<VecDeque<RetainedRecording>>,
+    /// Archive files still being written, a removal takes the name out and the
+    /// writer then deletes what it wrote
+    pub archive_writes: Mutex<HashSet<String>>,
+}
+
+/// When the microphone may be kept open between recordings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PrerollPolicy {
+    /// As long as the app is running
+    #[default]
+    Always,
+    /// Only while the main window has focus
+    Foreground,
+    /// Only while the recording overlay is shown, e.g. with the "always" overlay mode
+    OverlayVisible,
+}
+
+/// Payload of the `engine-status` event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EngineStatus {
+    Loading,
+    Ready,
+    Unloaded,
+}
+
+/// Order in which queued transcriptions are served, highest first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobPriority {
+    /// Live partial transcripts, a waiting one is dropped when a newer preview
+    /// or a dictation arrives
+    Preview,
+    /// Work nobody is waiting on right now, e.g. HTTP API requests
+    Batch,
+    /// Dictation the user is waiting for
+    Interactive,
+}
+
+pub type JobResult = Result<TranscriptionResult, String>;
+
+/// A transcription waiting in the worker queue.
+pub struct TranscriptionJob {
+    pub id: u64,
+    pub priority: JobPriority,
+    pub samples: Vec<f32>,
+    pub params: InferenceParams,
+    pub cancelled: Arc<AtomicBool>,
+    pub reply: Sender<JobResult>,
+}
+
+/// Requests handled by the transcription worker, which owns the engine.
+pub enum WorkerRequest {
+    /// Load the configured engine, or reload it if the settings changed
+    Load(Sender<Result<(), String>>),
+    Unload,
+    Transcribe(TranscriptionJob),
+    Benchmark(BenchmarkRequest),
+}
+
+/// A benchmark run on the worker, so it never loads a model next to the one in use.
+pub struct BenchmarkRequest {
+    pub samples: Vec<f32>,
+    pub on_result: Box<dyn FnMut(&BenchmarkResult) + Send>,
+    pub reply: Sender<Result<Vec<BenchmarkResult>, String>>,
+}
+
+/// Payload of the `transcription-queue-depth` event.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct QueueDepth {
+    /// Jobs not finished yet, the running one included
+    pub depth: usize,
+    pub running: Option<u64>,
+    /// Waiting jobs in the order they will run
+    pub pending: Vec<u64>,
+}
+
+/// Returned when a job is submitted, to wait for its result.
+pub struct JobHandle {
+    pub id: u64,
+    pub result: Receiver<JobResult>,
+}
+
+impl JobHandle {
+    pub fn wait(self) -> JobResult {
+        self.result
+            .recv()
+            .unwrap_or_else(|_| Err("Transcription worker stopped".to_string()))
+    }
+}
+
+/// Audio of a finished recording, handed to `process_recording`.
+pub enum RecordedAudio {
+    /// 16-bit WAV written by the recorder
+    File(PathBuf),
+    /// Mono samples captured in memory, nothing was written to disk
+    Samples {
+        samples: Vec<f32>,
+        sample_rate: usize,
+    },
+}
+
+/// A recording kept in memory for reprocessing.
+#[derive(Clone)]
+pub struct RetainedRecording {
+    /// 16 kHz mono samples
+    pub samples: Vec<f32>,
+    /// History entry holding its transcription, replaced when it is reprocessed
+    pub history_id: Option<u64>,
+}
+
+/// What `process_samples` does with the text besides returning it.
+pub enum ProcessTarget {
+    /// A new recording, archived, retained and counted in the stats and history
+    New {
+        duration_seconds: f64,
+        size_bytes: u64,
+    },
+    /// A retained recording run again, its history entry gets the new text
+    Reprocess { history_id: Option<u64> },
+}
+
+/// A file found in the audio archive by the retention policy.
+pub struct ArchivedFile {
+    pub path: PathBuf,
+    pub modified: SystemTime,
+    pub size: u64,
+    /// Linked to the history, unlinked files are deleted
+    pub referenced: bool,
+}
+
+/// Fixed-capacity sample buffer, the oldest samples are dropped once full.
+pub struct SampleRing {
+    samples: VecDeque<f32>,
+    capacity: usize,
+    /// Samples dropped to make room since the last `take`
+    dropped: usize,
+}
+
+impl SampleRing {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            samples: VecDeque::new(),
+            capacity,
+            dropped: 0,
+        }
+    }
+
+    pub fn push(&mut self, sample: f32) {
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+            self.dropped += 1;
+        }
+        self.samples.push_back(sample);
+    }
+
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn to_vec(&self) -> Vec<f32> {
+        self.samples.iter().copied().collect()
+    }
+
+    pub fn take(&mut self) -> Vec<f32> {
+        self.dropped = 0;
+        std::mem::take(&mut self.samples).into()
+    }
+}
+
+pub type SharedSamples = Arc<Mutex<SampleRing>>;
+
+/// Where the stream callback sends the captured mono samples, besides the level meter.
+pub struct CaptureTaps {
+    /// WAV file, `None` when capturing in memory or once finalized
+    pub writer: SharedWriter,
+    /// In-memory capture, replaces the WAV file
+    pub samples: Option<SharedSamples>,
+    pub preview: Option<SharedPreviewBuffer>,
+    pub silence_detector: Option<SilenceDetector>,
+    /// Set while the recording is paused, samples are then dropped
+    pub paused: Arc<AtomicBool>,
+    /// Holds the first samples until the pre-roll is written before them, `None` after
+    pub staging: SharedStaging,
+}
+
+pub type SharedStaging = Arc<Mutex<Option<Vec<f32>>>>;
+
+/// Where a recording ends up: the WAV file currently written, or the in-memory
+/// capture. Shares its writer and samples with the `CaptureTaps` of the stream.
+pub struct CaptureOutput {
+    pub writer: SharedWriter,
+    /// File behind `writer`, replaced on each rollover
+    pub file_path: Option<PathBuf>,
+    pub samples: Option<SharedSamples>,
+    pub sample_rate: usize,
+}
+
+/// Why a recording was dropped before transcription, sent with `recording-discarded`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(tag = "reason", rename_all = "snake_case")]
+pub enum DiscardReason {
+    TooShort {
+        duration_ms: u32,
+    },
+    /// No frame reached the minimum level
+    TooQuiet,
+}
+
+/// What happens when a recording reaches `recording_limit_secs`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordingLimitAction {
+    #[default]
+    Stop,
+    /// Transcribe and paste what was recorded so far, and keep recording into a new chunk
+    Rollover,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitEvent {
+    /// Whole seconds left before the limit
+    Warning(u32),
+    Reached,
+    Rollover,
+}
+
+/// Tracks the captured duration against the recording limit. Counted in frames
+/// so time spent paused does not count.
+pub struct RecordingLimit {
+    pub sample_rate: usize,
+    pub max_frames: usize,
+    pub warning_frames: usize,
+    pub rollover: bool,
+    pub captured_frames: usize,
+    pub last_warning: Option<u32>,
+    pub reached: bool,
+}
+
+/// Energy-based voice activity detection over fixed-size frames.
+#[derive(Debug, Clone, Copy)]
+pub struct Vad {
+    pub frame_len: usize,
+    pub threshold: f32,
+}
+
+/// Streaming detector for hands-free recordings: reports when speech has been
+/// heard and then followed by `silence_ms` of silence.
+pub struct SilenceDetector {
+    pub vad: Vad,
+    pub frame: Vec<f32>,
+    pub silence_frames_limit: usize,
+    pub silent_frames: usize,
+    pub heard_speech: bool,
+    pub triggered: bool,
+}
+
+/// Sliding window of the last seconds of the recording, at the device sample rate.
+pub struct PreviewBuffer {
+    pub samples: SampleRing,
+    pub sample_rate: usize,
+    /// Samples pushed since the last snapshot, to skip previews when nothing changed
+    pub pending: usize,
+}
+
+pub type SharedPreviewBuffer = Arc<Mutex<PreviewBuffer>>;
+
+/// The last moments of audio before a recording, at the device sample rate.
+pub struct PrerollBuffer {
+    pub samples: SampleRing,
+    pub sample_rate: usize,
+}
+
+pub type SharedPrerollBuffer = Arc<Mutex<PrerollBuffer>>;
+
+/// Always-warm input stream feeding the pre-roll buffer, stored in `AudioState`.
+pub struct PrerollCapture {
+    pub _stream: SendStream,
+    pub buffer: SharedPrerollBuffer,
+    pub duration_ms: u32,
+    /// Set by the stream error callback, e.g. when the device is unplugged
+    pub failed: Arc<AtomicBool>,
+}
+
+/// How the level of a recording is adjusted before transcription.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Normalization {
+    #[default]
+    Off,
+    /// One gain for the whole recording, loudest sample at -1 dBFS
+    Peak,
+    /// One gain for the whole recording, average level at -20 dBFS
+    Rms,
+    /// Gain following the level over time, for speakers moving away from the mic
+    Agc,
+}
+
+/// Conditioning applied to 16 kHz mono samples before transcription:
+/// high-pass filter, noise gate, then normalization.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DspChain {
+    /// Cutoff of the high-pass filter, 0 disables it
+    pub high_pass_hz: u32,
+    /// Gate threshold in dBFS, `None` disables the gate
+    pub noise_gate_db: Option<f32>,
+    pub normalization: Normalization,
+}
+
+/// Trade-off between speed and anti-aliasing of the windowed-sinc resampler.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ResampleQuality {
+    /// Short filter for live previews, about 65 dB of stopband rejection
+    Fast,
+    /// About 95 dB of stopband rejection, used for dictations
+    #[default]
+    Balanced,
+    /// Long filter with a steep transition band, for offline files
+    Best,
+}
+
+/// Windowed-sinc low-pass evaluated at fractional input positions.
+pub struct Kernel {
+    /// Cutoff relative to the input rate, scaled down when downsampling
+    pub cutoff: f64,
+    /// Half-length of the filter in input samples
+    pub half_width: f64,
+    pub half_taps: usize,
+    pub beta: f64,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum DecodeError {
+    #[error("Failed to read audio file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Unsupported audio format: {0}")]
+    UnsupportedFormat(String),
+    #[error("Unsupported audio codec: {0}")]
+    UnsupportedCodec(String),
+    #[error("No audio track found")]
+    NoAudioTrack,
+    #[error("Audio track has no sample rate")]
+    UnknownSampleRate,
+    #[error("Corrupt audio data: {0}")]
+    Corrupt(String),
+}
+
+/// How multichannel audio is turned into the mono signal sent to the engine.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChannelStrategy {
+    /// Mean of all channels
+    #[default]
+    Average,
+    Left,
+    Right,
+    /// Zero-based channel index, for interfaces with more than two inputs
+    Channel(u16),
+    /// Channel with the most energy over the last half second
+    Loudest,
+}
+
+/// Downmixes interleaved frames to mono according to a `ChannelStrategy`.
+/// A channel missing from the stream falls back to the last one.
+pub struct ChannelMixer {
+    pub strategy: ChannelStrategy,
+    pub channels: usize,
+    pub energy: Vec<f32>,
+    pub decay: f32,
+    pub loudest: usize,
+}
+
+/// Payload of `mic-fallback`: the preferred microphone is gone and recordings
+/// use the system default until it comes back.
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MicFallback {
+    pub preferred_id: String,
+    pub fallback_label: Option<String>,
+}
+
+/// Microphones known to the device watcher. Some drivers briefly drop devices
+/// from the list, so one only counts as removed after missing several lists in a row.
+pub struct MicTracker {
+    pub known: Option<Vec<MicDevice>>,
+    pub misses: HashMap<String, u32>,
+    pub misses_before_removal: u32,
+}
+
+/// Pending work of the transcription worker.
+/// Engine load and unload requests are served before transcriptions,
+/// transcriptions by priority then in submission order.
+#[derive(Default)]
+pub struct JobQueue {
+    pub control: VecDeque<WorkerRequest>,
+    pub jobs: Vec<TranscriptionJob>,
+    pub running: Option<(u64, Arc<AtomicBool>)>,
+}
+
+/// Handle to the thread owning the transcription engine, managed as Tauri state.
+pub struct TranscriptionWorker {
+    pub queue: Mutex<JobQueue>,
+    pub wakeup: Condvar,
+    pub next_id: AtomicU64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 #[repr(u8)]
 pub enum RecordingMode {
     Standard = 0,
@@ -42,13 +469,18 @@ impl AudioState {
     pub fn new() -> Self {
         Self {
             recorder: Mutex::new(None),
-            engine: Mutex::new(None),
+            engine_status: Mutex::new(EngineStatus::Unloaded),
             current_file_name: Mutex::new(None),
             recording_mode: std::sync::atomic::AtomicU8::new(RecordingMode::Standard as u8),
             limit_reached: std::sync::Arc::new(AtomicBool::new(false)),
             cached_device: Mutex::new(None),
             invert_enter_signal: std::sync::Arc::new(AtomicBool::new(false)),
             invert_feedback_shown_early: std::sync::Arc::new(AtomicBool::new(false)),
+            preroll: Mutex::new(None),
+            preroll_wakeup: Mutex::new(None),
+            pending_chunk: Mutex::new(None),
+            recent_recordings: Mutex::new(VecDeque::new()),
+            archive_writes: Mutex::new(HashSet::new()),
         }
     }
 
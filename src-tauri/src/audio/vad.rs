@@ -0,0 +1,225 @@
+use crate::audio::types::{DiscardReason, SilenceDetector, Vad};
+use std::ops::Range;
+
+/// Analysis frame length, short enough to follow syllables.
+const FRAME_MS: usize = 30;
+/// RMS level above which a frame counts as speech, about -40 dBFS.
+pub const SPEECH_THRESHOLD: f32 = 0.01;
+/// Lowest threshold `Vad::adaptive` picks, about -60 dBFS.
+const MIN_SPEECH_THRESHOLD: f32 = 0.001;
+/// Speech must be this many times louder than the noise floor, about 10 dB.
+const NOISE_FLOOR_MARGIN: f32 = 3.0;
+/// Frames quieter than this share of the recording give its noise floor.
+const NOISE_FLOOR_PERCENTILE: f32 = 0.1;
+/// Audio kept around the detected speech so word onsets and endings are not clipped.
+const TRIM_PADDING_MS: usize = 200;
+
+impl Vad {
+    pub fn new(sample_rate: usize, threshold: f32) -> Self {
+        Self {
+            frame_len: (sample_rate * FRAME_MS / 1000).max(1),
+            threshold,
+        }
+    }
+
+    /// Threshold set above the noise floor of `samples`, so quiet speech on a quiet
+    /// microphone is still found. It is never stricter than `SPEECH_THRESHOLD`.
+    pub fn adaptive(samples: &[f32], sample_rate: usize) -> Self {
+        let mut vad = Self::new(sample_rate, SPEECH_THRESHOLD);
+        let mut levels: Vec<f32> = samples.chunks(vad.frame_len).map(rms).collect();
+        if levels.is_empty() {
+            return vad;
+        }
+
+        levels.sort_by(f32::total_cmp);
+        let noise_floor = levels[(levels.len() as f32 * NOISE_FLOOR_PERCENTILE) as usize];
+        vad.threshold =
+            (noise_floor * NOISE_FLOOR_MARGIN).clamp(MIN_SPEECH_THRESHOLD, SPEECH_THRESHOLD);
+        vad
+    }
+
+    pub fn is_speech(&self, frame: &[f32]) -> bool {
+        rms(frame) >= self.threshold
+    }
+
+    /// Range of `samples` from the first to the last speech frame, padded on both sides.
+    /// Returns `None` when no speech is found.
+    pub fn speech_range(&self, samples: &[f32], sample_rate: usize) -> Option<Range<usize>> {
+        let mut frames = samples.chunks(self.frame_len).enumerate();
+        let first = frames
+            .clone()
+            .find(|(_, frame)| self.is_speech(frame))
+            .map(|(index, _)| index)?;
+        let last = frames
+            .rfind(|(_, frame)| self.is_speech(frame))
+            .map(|(index, _)| index)
+            .unwrap_or(first);
+
+        let padding = sample_rate * TRIM_PADDING_MS / 1000;
+        let start = (first * self.frame_len).saturating_sub(padding);
+        let end = ((last + 1) * self.frame_len + padding).min(samples.len());
+        Some(start..end)
+    }
+}
+
+impl SilenceDetector {
+    pub fn new(sample_rate: usize, threshold: f32, silence_ms: u32) -> Self {
+        let vad = Vad::new(sample_rate, threshold);
+        Self {
+            frame: Vec::with_capacity(vad.frame_len),
+            silence_frames_limit: (silence_ms as usize).div_ceil(FRAME_MS).max(1),
+            vad,
+            silent_frames: 0,
+            heard_speech: false,
+            triggered: false,
+        }
+    }
+
+    /// Feeds a block of mono samples. Returns true once, when the silence limit is reached.
+    pub fn push_block(&mut self, mut block: &[f32]) -> bool {
+        let mut triggered = false;
+        while !block.is_empty() {
+            let take = (self.vad.frame_len - self.frame.len()).min(block.len());
+            self.frame.extend_from_slice(&block[..take]);
+            block = &block[take..];
+            if self.frame.len() == self.vad.frame_len {
+                triggered |= self.end_frame();
+            }
+        }
+        triggered
+    }
+
+    fn end_frame(&mut self) -> bool {
+        if self.vad.is_speech(&self.frame) {
+            self.heard_speech = true;
+            self.silent_frames = 0;
+        } else {
+            self.silent_frames += 1;
+        }
+        self.frame.clear();
+
+        // Silence before the first word never stops the recording
+        if !self.triggered && self.heard_speech && self.silent_frames >= self.silence_frames_limit {
+            self.triggered = true;
+            return true;
+        }
+        false
+    }
+}
+
+/// Guard against accidental taps of the shortcut, whose few samples of noise the
+/// model may turn into a hallucinated word. `min_level_db` is in dBFS, `None` disables
+/// the level check.
+pub fn discard_reason(
+    samples: &[f32],
+    sample_rate: usize,
+    min_duration_ms: u32,
+    min_level_db: Option<f32>,
+) -> Option<DiscardReason> {
+    let duration_ms = (samples.len() * 1000 / sample_rate.max(1)) as u32;
+    if duration_ms < min_duration_ms {
+        return Some(DiscardReason::TooShort { duration_ms });
+    }
+
+    let threshold = 10f32.powf(min_level_db? / 20.0);
+    let vad = Vad::new(sample_rate, threshold);
+    if samples
+        .chunks(vad.frame_len)
+        .any(|frame| vad.is_speech(frame))
+    {
+        None
+    } else {
+        Some(DiscardReason::TooQuiet)
+    }
+}
+
+fn rms(frame: &[f32]) -> f32 {
+    if frame.is_empty() {
+        return 0.0;
+    }
+    (frame.iter().map(|sample| sample * sample).sum::<f32>() / frame.len() as f32).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_RATE: usize = 16000;
+
+    fn tone(ms: usize) -> Vec<f32> {
+        (0..SAMPLE_RATE * ms / 1000)
+            .map(|i| 0.3 * (i as f32 * 0.1).sin())
+            .collect()
+    }
+
+    fn silence(ms: usize) -> Vec<f32> {
+        vec![0.0; SAMPLE_RATE * ms / 1000]
+    }
+
+    #[test]
+    fn test_speech_range_trims_leading_and_trailing_silence() {
+        let samples = [silence(1000), tone(500), silence(1000)].concat();
+        let vad = Vad::new(SAMPLE_RATE, SPEECH_THRESHOLD);
+
+        let range = vad.speech_range(&samples, SAMPLE_RATE).unwrap();
+        let padding = SAMPLE_RATE * TRIM_PADDING_MS / 1000;
+        let frame_len = SAMPLE_RATE * FRAME_MS / 1000;
+        assert!(range.start <= SAMPLE_RATE - padding);
+        assert!(range.start >= SAMPLE_RATE - padding - frame_len);
+        assert!(range.end >= SAMPLE_RATE * 3 / 2 + padding);
+        assert!(range.end <= SAMPLE_RATE * 3 / 2 + padding + frame_len);
+    }
+
+    #[test]
+    fn test_speech_range_is_none_for_silence() {
+        let vad = Vad::new(SAMPLE_RATE, SPEECH_THRESHOLD);
+        assert_eq!(vad.speech_range(&silence(500), SAMPLE_RATE), None);
+    }
+
+    #[test]
+    fn test_discard_reason() {
+        assert_eq!(
+            discard_reason(&tone(100), SAMPLE_RATE, 300, Some(-50.0)),
+            Some(DiscardReason::TooShort { duration_ms: 100 })
+        );
+        assert_eq!(
+            discard_reason(&silence(1000), SAMPLE_RATE, 300, Some(-50.0)),
+            Some(DiscardReason::TooQuiet)
+        );
+        assert_eq!(discard_reason(&silence(1000), SAMPLE_RATE, 300, None), None);
+
+        let speech = [silence(1000), tone(100)].concat();
+        assert_eq!(discard_reason(&speech, SAMPLE_RATE, 300, Some(-50.0)), None);
+    }
+
+    #[test]
+    fn test_silence_detector_waits_for_speech_then_silence() {
+        let mut detector = SilenceDetector::new(SAMPLE_RATE, SPEECH_THRESHOLD, 300);
+        // Blocks of an odd size, as audio callbacks deliver them
+        let mut feed = |samples: Vec<f32>| {
+            samples
+                .chunks(333)
+                .filter(|block| detector.push_block(block))
+                .count()
+        };
+
+        assert_eq!(feed(silence(1000)), 0);
+        assert_eq!(feed(tone(300)), 0);
+        assert_eq!(feed(silence(200)), 0);
+        assert_eq!(feed(silence(200)), 1);
+        assert_eq!(feed(silence(1000)), 0);
+    }
+
+    #[test]
+    fn test_adaptive_vad_finds_quiet_speech() {
+        let quiet_tone: Vec<f32> = tone(500).iter().map(|sample| sample * 0.02).collect();
+        let samples = [silence(1000), quiet_tone, silence(1000)].concat();
+
+        let fixed = Vad::new(SAMPLE_RATE, SPEECH_THRESHOLD);
+        assert_eq!(fixed.speech_range(&samples, SAMPLE_RATE), None);
+
+        let adaptive = Vad::adaptive(&samples, SAMPLE_RATE);
+        let range = adaptive.speech_range(&samples, SAMPLE_RATE).unwrap();
+        assert!(range.start < SAMPLE_RATE && range.end > SAMPLE_RATE * 3 / 2);
+    }
+}
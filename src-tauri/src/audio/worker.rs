@@ -0,0 +1,446 @@
+use crate::audio::lifecycle::{
+    ensure_engine_loaded, model_params_from_settings, release_engine, resolve_engine_model_path,
+};
+use crate::audio::types::{
+    AudioState, BenchmarkRequest, JobHandle, JobPriority, JobQueue, QueueDepth, TranscriptionJob,
+    TranscriptionWorker, WorkerRequest,
+};
+use crate::engine::benchmark;
+use crate::engine::transcription_engine::TranscriptionEngine;
+use crate::engine::{BenchmarkResult, InferenceParams, QuantizationType};
+use crate::model::helpers::has_fp32_files;
+use log::{debug, error, info};
+use parking_lot::{Condvar, Mutex};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+
+const IDLE_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+const CANCELLED_MESSAGE: &str = "Transcription cancelled";
+const SUPERSEDED_MESSAGE: &str = "Preview superseded by newer audio";
+
+impl JobQueue {
+    pub fn push(&mut self, request: WorkerRequest) {
+        match request {
+            WorkerRequest::Transcribe(job) => {
+                // A waiting preview is stale once newer audio or the final dictation is queued
+                if matches!(
+                    job.priority,
+                    JobPriority::Preview | JobPriority::Interactive
+                ) {
+                    self.drop_waiting_previews();
+                }
+                self.jobs.push(job);
+            }
+            control => self.control.push_back(control),
+        }
+    }
+
+    fn drop_waiting_previews(&mut self) {
+        let (previews, jobs) = std::mem::take(&mut self.jobs)
+            .into_iter()
+            .partition(|job| job.priority == JobPriority::Preview);
+        self.jobs = jobs;
+        for preview in previews {
+            debug!("Dropping stale preview job {}", preview.id);
+            let _ = preview.reply.send(Err(SUPERSEDED_MESSAGE.to_string()));
+        }
+    }
+
+    /// Takes the next request. A transcription is tracked as running until `finish` is called.
+    pub fn pop(&mut self) -> Option<WorkerRequest> {
+        if let Some(control) = self.control.pop_front() {
+            return Some(control);
+        }
+
+        let next = self
+            .jobs
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.priority.cmp(&b.priority).then(b.id.cmp(&a.id)))
+            .map(|(index, _)| index)?;
+        let job = self.jobs.remove(next);
+        self.running = Some((job.id, job.cancelled.clone()));
+        Some(WorkerRequest::Transcribe(job))
+    }
+
+    pub fn finish(&mut self) {
+        self.running = None;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.control.is_empty() && self.jobs.is_empty()
+    }
+
+    /// Removes a waiting job, or flags the running one so its result is discarded.
+    pub fn cancel(&mut self, id: u64) -> bool {
+        if let Some(index) = self.jobs.iter().position(|job| job.id == id) {
+            let job = self.jobs.remove(index);
+            let _ = job.reply.send(Err(CANCELLED_MESSAGE.to_string()));
+            return true;
+        }
+
+        match &self.running {
+            Some((running_id, cancelled)) if *running_id == id => {
+                cancelled.store(true, Ordering::SeqCst);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn depth(&self) -> QueueDepth {
+        let mut pending: Vec<&TranscriptionJob> = self.jobs.iter().collect();
+        pending.sort_by(|a, b| b.priority.cmp(&a.priority).then(a.id.cmp(&b.id)));
+
+        QueueDepth {
+            depth: pending.len() + usize::from(self.running.is_some()),
+            running: self.running.as_ref().map(|(id, _)| *id),
+            pending: pending.iter().map(|job| job.id).collect(),
+        }
+    }
+}
+
+impl TranscriptionWorker {
+    pub fn new() -> Self {
+        Self {
+            queue: Mutex::new(JobQueue::default()),
+            wakeup: Condvar::new(),
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    fn send(&self, request: WorkerRequest) {
+        self.queue.lock().push(request);
+        self.wakeup.notify_one();
+    }
+}
+
+/// Spawns the worker thread. `TranscriptionWorker` must already be managed.
+pub fn start_transcription_worker(app: &AppHandle) {
+    let app = app.clone();
+    std::thread::spawn(move || run_worker(&app));
+}
+
+/// Queues a transcription, the result is delivered through the returned handle.
+pub fn submit_transcription(
+    app: &AppHandle,
+    samples: Vec<f32>,
+    params: InferenceParams,
+    priority: JobPriority,
+) -> JobHandle {
+    let worker = app.state::<TranscriptionWorker>();
+    let id = worker.next_id.fetch_add(1, Ordering::SeqCst);
+    let (reply, result) = mpsc::channel();
+
+    worker.send(WorkerRequest::Transcribe(TranscriptionJob {
+        id,
+        priority,
+        samples,
+        params,
+        cancelled: Arc::new(AtomicBool::new(false)),
+        reply,
+    }));
+    emit_queue_depth(app);
+
+    JobHandle { id, result }
+}
+
+pub fn cancel_transcription(app: &AppHandle, id: u64) -> bool {
+    let cancelled = app.state::<TranscriptionWorker>().queue.lock().cancel(id);
+    if cancelled {
+        info!("Transcription job {} cancelled", id);
+        emit_queue_depth(app);
+    }
+    cancelled
+}
+
+pub fn get_queue_depth(app: &AppHandle) -> QueueDepth {
+    app.state::<TranscriptionWorker>().queue.lock().depth()
+}
+
+/// Asks the worker to load the configured engine and waits until it is ready.
+pub fn request_engine_load(app: &AppHandle) -> Result<(), String> {
+    let (reply, result) = mpsc::channel();
+    app.state::<TranscriptionWorker>()
+        .send(WorkerRequest::Load(reply));
+    result
+        .recv()
+        .unwrap_or_else(|_| Err("Transcription worker stopped".to_string()))
+}
+
+pub fn request_engine_unload(app: &AppHandle) {
+    app.state::<TranscriptionWorker>()
+        .send(WorkerRequest::Unload);
+}
+
+/// Benchmarks the configured engine on the worker and waits for every result.
+/// `on_result` is called as each configuration finishes.
+pub fn request_benchmark(
+    app: &AppHandle,
+    samples: Vec<f32>,
+    on_result: impl FnMut(&BenchmarkResult) + Send + 'static,
+) -> Result<Vec<BenchmarkResult>, String> {
+    let (reply, result) = mpsc::channel();
+    app.state::<TranscriptionWorker>()
+        .send(WorkerRequest::Benchmark(BenchmarkRequest {
+            samples,
+            on_result: Box::new(on_result),
+            reply,
+        }));
+    result
+        .recv()
+        .unwrap_or_else(|_| Err("Transcription worker stopped".to_string()))
+}
+
+fn run_worker(app: &AppHandle) {
+    let worker = app.state::<TranscriptionWorker>();
+    let mut engine: Option<Box<dyn TranscriptionEngine>> = None;
+    let mut last_used = Instant::now();
+
+    loop {
+        let request = {
+            let mut queue = worker.queue.lock();
+            if queue.is_empty() {
+                worker.wakeup.wait_for(&mut queue, IDLE_CHECK_INTERVAL);
+            }
+            queue.pop()
+        };
+
+        match request {
+            Some(WorkerRequest::Load(reply)) => {
+                let loaded = ensure_engine_loaded(app, &mut engine)
+                    .map(|_| ())
+                    .map_err(|e| format!("{:#}", e));
+                let _ = reply.send(loaded);
+                last_used = Instant::now();
+            }
+            Some(WorkerRequest::Unload) => release_engine(app, &mut engine),
+            Some(WorkerRequest::Benchmark(mut request)) => {
+                let results = run_benchmark(app, &mut engine, &mut request);
+                let _ = request.reply.send(results);
+                last_used = Instant::now();
+            }
+            Some(WorkerRequest::Transcribe(job)) => {
+                run_job(app, &mut engine, job);
+                worker.queue.lock().finish();
+                emit_queue_depth(app);
+                last_used = Instant::now();
+            }
+            None => {
+                if engine.is_some() && is_idle_expired(app, last_used) {
+                    debug!("Engine idle for {:?}, unloading", last_used.elapsed());
+                    release_engine(app, &mut engine);
+                }
+            }
+        }
+    }
+}
+
+fn run_job(
+    app: &AppHandle,
+    engine: &mut Option<Box<dyn TranscriptionEngine>>,
+    job: TranscriptionJob,
+) {
+    if job.cancelled.load(Ordering::SeqCst) {
+        let _ = job.reply.send(Err(CANCELLED_MESSAGE.to_string()));
+        return;
+    }
+
+    debug!("Running transcription job {} ({:?})", job.id, job.priority);
+    let started = Instant::now();
+    // Cancelling stops the engine between decoding steps, so a preview
+    // running when recording stops does not hold up the final transcription
+    let mut params = job.params;
+    params.decoding.cancelled = Some(job.cancelled.clone());
+    let result = ensure_engine_loaded(app, engine)
+        .map_err(|e| format!("Model not available: {:#}", e))
+        .and_then(|engine| {
+            engine
+                .transcribe_samples(job.samples, Some(params))
+                .map_err(|e| e.to_string())
+        });
+    debug!(
+        "Transcription job {} done in {:?}",
+        job.id,
+        started.elapsed()
+    );
+
+    let result = match job.cancelled.load(Ordering::SeqCst) {
+        true => Err(CANCELLED_MESSAGE.to_string()),
+        false => result,
+    };
+    let _ = job.reply.send(result);
+}
+
+/// Measures the configured engine first, reusing it when it is already loaded, then
+/// releases it so the other configurations never run next to a second model copy.
+/// It is loaded again by the next transcription.
+fn run_benchmark(
+    app: &AppHandle,
+    engine: &mut Option<Box<dyn TranscriptionEngine>>,
+    request: &mut BenchmarkRequest,
+) -> Result<Vec<BenchmarkResult>, String> {
+    let mut settings = crate::settings::load_settings(app);
+    let model_path =
+        resolve_engine_model_path(app, &mut settings).map_err(|e| format!("{:#}", e))?;
+    let current = model_params_from_settings(&settings, &model_path);
+
+    let started = Instant::now();
+    let loaded =
+        ensure_engine_loaded(app, engine).map_err(|e| format!("Model not available: {:#}", e))?;
+    let load_seconds = started.elapsed().as_secs_f32();
+    let mut results = vec![BenchmarkResult {
+        load_seconds,
+        ..benchmark::benchmark_loaded_engine(loaded, current.clone(), &request.samples)
+    }];
+    (request.on_result)(&results[0]);
+    release_engine(app, engine);
+
+    let cores = std::thread::available_parallelism()
+        .map(|cores| cores.get())
+        .unwrap_or(1);
+    let has_fp32 = has_fp32_files(&model_path, &settings.engine);
+    let configs = benchmark::default_benchmark_configs(cores)
+        .into_iter()
+        .filter(|params| *params != current)
+        .filter(|params| has_fp32 || params.quantization != QuantizationType::FP32)
+        .collect();
+
+    results.extend(benchmark::run_benchmark(
+        &settings.engine,
+        &model_path,
+        &request.samples,
+        configs,
+        &mut request.on_result,
+    ));
+    Ok(results)
+}
+
+/// The engine is kept while recording, the next transcription needs it.
+fn is_idle_expired(app: &AppHandle, last_used: Instant) -> bool {
+    let idle_minutes = crate::settings::load_settings(app).engine_idle_unload_minutes;
+    if idle_minutes == 0 {
+        return false;
+    }
+
+    let is_recording = app.state::<AudioState>().recorder.lock().is_some();
+    !is_recording && last_used.elapsed() >= Duration::from_secs(idle_minutes as u64 * 60)
+}
+
+fn emit_queue_depth(app: &AppHandle) {
+    if let Err(e) = app.emit("transcription-queue-depth", get_queue_depth(app)) {
+        error!("Failed to emit transcription queue depth: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::types::JobResult;
+
+    fn job(id: u64, priority: JobPriority) -> (TranscriptionJob, mpsc::Receiver<JobResult>) {
+        let (reply, result) = mpsc::channel();
+        let job = TranscriptionJob {
+            id,
+            priority,
+            samples: Vec::new(),
+            params: InferenceParams::default(),
+            cancelled: Arc::new(AtomicBool::new(false)),
+            reply,
+        };
+        (job, result)
+    }
+
+    fn pop_job_id(queue: &mut JobQueue) -> Option<u64> {
+        match queue.pop()? {
+            WorkerRequest::Transcribe(job) => {
+                queue.finish();
+                Some(job.id)
+            }
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_interactive_jobs_run_before_batch_jobs() {
+        let mut queue = JobQueue::default();
+        for (id, priority) in [
+            (1, JobPriority::Batch),
+            (2, JobPriority::Interactive),
+            (3, JobPriority::Batch),
+            (4, JobPriority::Interactive),
+        ] {
+            queue.push(WorkerRequest::Transcribe(job(id, priority).0));
+        }
+
+        assert_eq!(queue.depth().pending, vec![2, 4, 1, 3]);
+        let order: Vec<u64> = std::iter::from_fn(|| pop_job_id(&mut queue)).collect();
+        assert_eq!(order, vec![2, 4, 1, 3]);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_newer_jobs_drop_waiting_previews() {
+        let mut queue = JobQueue::default();
+        let (first, first_result) = job(1, JobPriority::Preview);
+        let (second, second_result) = job(2, JobPriority::Preview);
+        queue.push(WorkerRequest::Transcribe(first));
+        queue.push(WorkerRequest::Transcribe(job(3, JobPriority::Batch).0));
+        queue.push(WorkerRequest::Transcribe(second));
+
+        assert_eq!(
+            first_result.recv().unwrap().unwrap_err(),
+            SUPERSEDED_MESSAGE
+        );
+        assert_eq!(queue.depth().pending, vec![3, 2]);
+
+        queue.push(WorkerRequest::Transcribe(
+            job(4, JobPriority::Interactive).0,
+        ));
+        assert_eq!(
+            second_result.recv().unwrap().unwrap_err(),
+            SUPERSEDED_MESSAGE
+        );
+        assert_eq!(queue.depth().pending, vec![4, 3]);
+    }
+
+    #[test]
+    fn test_control_requests_run_first() {
+        let mut queue = JobQueue::default();
+        queue.push(WorkerRequest::Transcribe(
+            job(1, JobPriority::Interactive).0,
+        ));
+        queue.push(WorkerRequest::Unload);
+
+        assert!(matches!(queue.pop(), Some(WorkerRequest::Unload)));
+        assert_eq!(pop_job_id(&mut queue), Some(1));
+    }
+
+    #[test]
+    fn test_cancel_pending_and_running_jobs() {
+        let mut queue = JobQueue::default();
+        let (first, _) = job(1, JobPriority::Batch);
+        let (second, second_result) = job(2, JobPriority::Batch);
+        queue.push(WorkerRequest::Transcribe(first));
+        queue.push(WorkerRequest::Transcribe(second));
+
+        let Some(WorkerRequest::Transcribe(running)) = queue.pop() else {
+            panic!("expected a transcription job");
+        };
+        assert_eq!(queue.depth().depth, 2);
+
+        assert!(queue.cancel(2));
+        assert_eq!(
+            second_result.recv().unwrap().unwrap_err(),
+            CANCELLED_MESSAGE
+        );
+        assert!(queue.cancel(1));
+        assert!(running.cancelled.load(Ordering::SeqCst));
+        assert!(!queue.cancel(3));
+
+        queue.finish();
+        assert_eq!(queue.depth(), QueueDepth::default());
+    }
+}
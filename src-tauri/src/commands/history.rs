@@ -1,9 +1,18 @@
+use crate::audio::archive;
 use crate::history::{self, HistoryEntry};
 use tauri::{command, AppHandle};
 
+/// Entries only keep their `audio_file` while the archived file still exists.
 #[command]
 pub fn get_recent_transcriptions(app: AppHandle) -> Result<Vec<HistoryEntry>, String> {
-    history::get_recent_transcriptions(&app).map_err(|e| format!("{:#}", e))
+    let mut entries = history::get_recent_transcriptions(&app).map_err(|e| format!("{:#}", e))?;
+    for entry in &mut entries {
+        entry.audio_file = entry
+            .audio_file
+            .take()
+            .filter(|name| matches!(archive::archived_file_path(&app, name), Ok(Some(_))));
+    }
+    Ok(entries)
 }
 
 #[command]
@@ -28,3 +37,71 @@ pub fn set_persist_history(app: AppHandle, enabled: bool) -> Result<(), String>
     }
     Ok(())
 }
+
+#[command]
+pub fn get_archive_enabled(app: AppHandle) -> Result<bool, String> {
+    let s = crate::settings::load_settings(&app);
+    Ok(s.archive_enabled)
+}
+
+#[command]
+pub fn set_archive_enabled(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut s = crate::settings::load_settings(&app);
+    s.archive_enabled = enabled;
+    crate::settings::save_settings(&app, &s)
+}
+
+#[command]
+pub fn get_archive_max_age_days(app: AppHandle) -> Result<Option<u32>, String> {
+    let s = crate::settings::load_settings(&app);
+    Ok(s.archive_max_age_days)
+}
+
+#[command]
+pub fn set_archive_max_age_days(app: AppHandle, days: Option<u32>) -> Result<(), String> {
+    if days == Some(0) {
+        return Err("Archive retention must be at least 1 day".to_string());
+    }
+
+    let mut s = crate::settings::load_settings(&app);
+    s.archive_max_age_days = days;
+    crate::settings::save_settings(&app, &s)?;
+    archive::enforce_retention(&app).map_err(|e| format!("{:#}", e))
+}
+
+#[command]
+pub fn get_archive_max_size_mb(app: AppHandle) -> Result<Option<u64>, String> {
+    let s = crate::settings::load_settings(&app);
+    Ok(s.archive_max_size_mb)
+}
+
+#[command]
+pub fn set_archive_max_size_mb(app: AppHandle, size_mb: Option<u64>) -> Result<(), String> {
+    if size_mb.is_some_and(|mb| mb < 10) {
+        return Err("Archive size limit must be at least 10 MB".to_string());
+    }
+
+    let mut s = crate::settings::load_settings(&app);
+    s.archive_max_size_mb = size_mb;
+    crate::settings::save_settings(&app, &s)?;
+    archive::enforce_retention(&app).map_err(|e| format!("{:#}", e))
+}
+
+/// Path of the archived audio for a history entry, `None` if it was never archived
+/// or has since been removed by the retention policy.
+#[command]
+pub fn get_history_audio_path(app: AppHandle, id: u64) -> Result<Option<String>, String> {
+    let entries = history::get_recent_transcriptions(&app).map_err(|e| format!("{:#}", e))?;
+    let file_name = entries
+        .into_iter()
+        .find(|entry| entry.id == id)
+        .and_then(|entry| entry.audio_file);
+
+    match file_name {
+        Some(name) => {
+            let path = archive::archived_file_path(&app, &name).map_err(|e| format!("{:#}", e))?;
+            Ok(path.map(|p| p.to_string_lossy().to_string()))
+        }
+        None => Ok(None),
+    }
+}
@@ -8,9 +8,11 @@ pub mod model;
 pub mod onboarding;
 pub mod overlay;
 pub mod record_mode;
+pub mod recording;
 pub mod settings;
 pub mod shortcuts;
 pub mod stats;
+pub mod transcription;
 
 pub use clipboard::*;
 pub use dictionary::*;
@@ -22,6 +24,8 @@ pub use model::*;
 pub use onboarding::*;
 pub use overlay::*;
 pub use record_mode::*;
+pub use recording::*;
 pub use settings::*;
 pub use shortcuts::*;
 pub use stats::*;
+pub use transcription::*;
@@ -1,6 +1,7 @@
-use crate::model::Model;
+use crate::engine::{benchmark, BenchmarkResult};
+use crate::model::{manager, Model, ModelInfo};
 use std::sync::Arc;
-use tauri::{command, State};
+use tauri::{command, AppHandle, Emitter, State};
 
 #[command]
 pub fn is_model_available(model: State<Arc<Model>>) -> bool {
@@ -13,3 +14,75 @@ pub fn get_model_path(model: State<Arc<Model>>) -> Result<String, String> {
 
     Ok(path.to_string_lossy().to_string())
 }
+
+#[command]
+pub fn get_engine_status(app: AppHandle) -> crate::audio::types::EngineStatus {
+    crate::audio::get_engine_status(&app)
+}
+
+#[command]
+pub fn unload_engine(app: AppHandle) {
+    crate::audio::unload_engine(&app);
+}
+
+#[command]
+pub fn get_installed_models(app: AppHandle) -> Result<Vec<ModelInfo>, String> {
+    manager::list_models(&app).map_err(|e| format!("{:#}", e))
+}
+
+#[command]
+pub fn get_active_model(app: AppHandle) -> Result<String, String> {
+    let s = crate::settings::load_settings(&app);
+    Ok(s.active_model
+        .unwrap_or_else(|| crate::model::MODEL_FILENAME.to_string()))
+}
+
+#[command]
+pub async fn import_model(app: AppHandle, archive_path: String) -> Result<ModelInfo, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        manager::import_model(&app, std::path::Path::new(&archive_path))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| format!("{:#}", e))
+}
+
+/// Verifying checksums reads the whole model, so this runs off the main thread.
+#[command]
+pub async fn set_active_model(app: AppHandle, id: String) -> Result<ModelInfo, String> {
+    tauri::async_runtime::spawn_blocking(move || manager::set_active_model(&app, &id))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| format!("{:#}", e))
+}
+
+/// Measures the real-time factor of the configured engine for several precisions,
+/// thread counts and optimization levels. Each result is also emitted as
+/// `engine-benchmark-progress` while the benchmark runs.
+/// Without `audio_path`, a synthetic ten-second signal is used.
+/// The benchmark runs on the transcription worker, which reuses the loaded model.
+#[command]
+pub async fn benchmark_engine(
+    app: AppHandle,
+    audio_path: Option<String>,
+) -> Result<Vec<BenchmarkResult>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let settings = crate::settings::load_settings(&app);
+        let samples = match audio_path {
+            Some(path) => crate::audio::decode::decode_audio_file(
+                std::path::Path::new(&path),
+                settings.file_channel_strategy,
+                settings.resample_quality,
+            )
+            .map_err(|e| format!("{:#}", e))?,
+            None => benchmark::benchmark_signal(10.0),
+        };
+        let progress_app = app.clone();
+
+        crate::audio::request_benchmark(&app, samples, move |result| {
+            let _ = progress_app.emit("engine-benchmark-progress", result);
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
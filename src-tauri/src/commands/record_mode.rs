@@ -10,11 +10,7 @@ pub fn get_record_mode(app: AppHandle) -> Result<String, String> {
 
 #[command]
 pub fn set_record_mode(app_handle: AppHandle, mode: String) {
-    let activation_mode = if mode == "toggle_to_talk" {
-        ActivationMode::ToggleToTalk
-    } else {
-        ActivationMode::PushToTalk
-    };
+    let activation_mode = ActivationMode::from_record_mode(&mode);
 
     app_handle
         .state::<ShortcutRegistryState>()
@@ -0,0 +1,32 @@
+use crate::audio::types::RecordingMode;
+use tauri::{command, AppHandle};
+
+/// Returns false when nothing is recording or it is already paused.
+#[command]
+pub fn pause_recording(app: AppHandle) -> bool {
+    crate::audio::set_recording_paused(&app, true)
+}
+
+/// Returns false when nothing is recording or it is not paused.
+#[command]
+pub fn resume_recording(app: AppHandle) -> bool {
+    crate::audio::set_recording_paused(&app, false)
+}
+
+/// Runs a kept recording through the pipeline again and returns the text without
+/// pasting it. `index` 0, the default, is the last recording. `llm_mode` is used
+/// for this run only, the active LLM mode stays unchanged.
+#[command]
+pub async fn reprocess_recording(
+    app: AppHandle,
+    mode: RecordingMode,
+    llm_mode: Option<usize>,
+    index: Option<usize>,
+) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::audio::reprocess_recording(&app, index.unwrap_or(0), mode, llm_mode)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| format!("{:#}", e))
+}
@@ -1,4 +1,9 @@
-use tauri::{command, AppHandle};
+use crate::audio::microphone::DEFAULT_MIC_KEY;
+use crate::audio::types::{
+    ChannelStrategy, Normalization, PrerollPolicy, RecordingLimitAction, ResampleQuality,
+};
+use crate::engine::{OptimizationLevel, QuantizationType};
+use tauri::{command, AppHandle, Manager};
 
 #[command]
 pub fn get_current_language(app: AppHandle) -> Result<String, String> {
@@ -31,6 +36,7 @@ pub fn set_current_mic_id(app: AppHandle, mic_id: Option<String>) -> Result<(),
     s.mic_id = mic_id.clone();
     crate::settings::save_settings(&app, &s)?;
     crate::audio::microphone::update_mic_cache(&app, mic_id);
+    crate::audio::preroll::restart_preroll(&app);
     Ok(())
 }
 
@@ -76,3 +82,464 @@ pub fn set_log_level(app: AppHandle, level: String) -> Result<(), String> {
 
     Ok(())
 }
+
+#[command]
+pub fn get_beam_size(app: AppHandle) -> Result<usize, String> {
+    let s = crate::settings::load_settings(&app);
+    Ok(s.beam_size)
+}
+
+#[command]
+pub fn set_beam_size(app: AppHandle, beam_size: usize) -> Result<(), String> {
+    if !(1..=16).contains(&beam_size) {
+        return Err("Beam size must be between 1 and 16".to_string());
+    }
+
+    let mut s = crate::settings::load_settings(&app);
+    s.beam_size = beam_size;
+    crate::settings::save_settings(&app, &s)
+}
+
+#[command]
+pub fn get_context_biasing_enabled(app: AppHandle) -> Result<bool, String> {
+    let s = crate::settings::load_settings(&app);
+    Ok(s.context_biasing_enabled)
+}
+
+#[command]
+pub fn set_context_biasing_enabled(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut s = crate::settings::load_settings(&app);
+    s.context_biasing_enabled = enabled;
+    crate::settings::save_settings(&app, &s)
+}
+
+#[command]
+pub fn get_context_biasing_weight(app: AppHandle) -> Result<f32, String> {
+    let s = crate::settings::load_settings(&app);
+    Ok(s.context_biasing_weight)
+}
+
+#[command]
+pub fn set_context_biasing_weight(app: AppHandle, weight: f32) -> Result<(), String> {
+    if !(0.0..=10.0).contains(&weight) {
+        return Err("Context biasing weight must be between 0 and 10".to_string());
+    }
+
+    let mut s = crate::settings::load_settings(&app);
+    s.context_biasing_weight = weight;
+    crate::settings::save_settings(&app, &s)
+}
+
+#[command]
+pub fn get_engine_idle_unload_minutes(app: AppHandle) -> Result<u32, String> {
+    let s = crate::settings::load_settings(&app);
+    Ok(s.engine_idle_unload_minutes)
+}
+
+#[command]
+pub fn set_engine_idle_unload_minutes(app: AppHandle, minutes: u32) -> Result<(), String> {
+    let mut s = crate::settings::load_settings(&app);
+    s.engine_idle_unload_minutes = minutes;
+    crate::settings::save_settings(&app, &s)
+}
+
+#[command]
+pub fn get_available_engines() -> Result<Vec<crate::engine::EngineInfo>, String> {
+    Ok(crate::engine::registry::available_engines())
+}
+
+#[command]
+pub fn get_engine(app: AppHandle) -> Result<String, String> {
+    let s = crate::settings::load_settings(&app);
+    Ok(s.engine)
+}
+
+/// The new engine is loaded on the next transcription.
+#[command]
+pub fn set_engine(app: AppHandle, engine: String) -> Result<(), String> {
+    if !crate::engine::registry::is_registered(&engine) {
+        return Err(format!("Unknown transcription engine: {}", engine));
+    }
+
+    let mut s = crate::settings::load_settings(&app);
+    s.engine = engine;
+    crate::settings::save_settings(&app, &s)
+}
+
+#[command]
+pub fn get_engine_model_path(app: AppHandle) -> Result<Option<String>, String> {
+    let s = crate::settings::load_settings(&app);
+    Ok(s.engine_model_path)
+}
+
+#[command]
+pub fn set_engine_model_path(app: AppHandle, path: Option<String>) -> Result<(), String> {
+    if let Some(path) = &path {
+        if !std::path::Path::new(path).is_dir() {
+            return Err(format!("Model directory not found: {}", path));
+        }
+    }
+
+    let mut s = crate::settings::load_settings(&app);
+    s.engine_model_path = path;
+    crate::settings::save_settings(&app, &s)
+}
+
+#[command]
+pub fn get_model_precision(app: AppHandle) -> Result<QuantizationType, String> {
+    let s = crate::settings::load_settings(&app);
+    Ok(s.model_precision)
+}
+
+/// Session options are applied when the model is loaded, so the engine is
+/// unloaded and picks them up on the next transcription.
+#[command]
+pub fn set_model_precision(app: AppHandle, precision: QuantizationType) -> Result<(), String> {
+    let mut s = crate::settings::load_settings(&app);
+    if precision == QuantizationType::FP32 {
+        let model_path = crate::audio::resolve_engine_model_path(&app, &mut s)
+            .map_err(|e| format!("{:#}", e))?;
+        if !crate::model::helpers::has_fp32_files(&model_path, &s.engine) {
+            return Err("The current model has no FP32 files, only Int8 is available".to_string());
+        }
+    }
+
+    s.model_precision = precision;
+    crate::settings::save_settings(&app, &s)?;
+    crate::audio::unload_engine(&app);
+    Ok(())
+}
+
+#[command]
+pub fn get_onnx_threads(app: AppHandle) -> Result<(usize, usize), String> {
+    let s = crate::settings::load_settings(&app);
+    Ok((s.onnx_intra_threads, s.onnx_inter_threads))
+}
+
+#[command]
+pub fn set_onnx_threads(
+    app: AppHandle,
+    intra_threads: usize,
+    inter_threads: usize,
+) -> Result<(), String> {
+    let mut s = crate::settings::load_settings(&app);
+    s.onnx_intra_threads = intra_threads;
+    s.onnx_inter_threads = inter_threads;
+    crate::settings::save_settings(&app, &s)?;
+    crate::audio::unload_engine(&app);
+    Ok(())
+}
+
+#[command]
+pub fn get_onnx_optimization_level(app: AppHandle) -> Result<OptimizationLevel, String> {
+    let s = crate::settings::load_settings(&app);
+    Ok(s.onnx_optimization_level)
+}
+
+#[command]
+pub fn set_onnx_optimization_level(app: AppHandle, level: OptimizationLevel) -> Result<(), String> {
+    let mut s = crate::settings::load_settings(&app);
+    s.onnx_optimization_level = level;
+    crate::settings::save_settings(&app, &s)?;
+    crate::audio::unload_engine(&app);
+    Ok(())
+}
+
+#[command]
+pub fn get_vad_trim_silence(app: AppHandle) -> Result<bool, String> {
+    let s = crate::settings::load_settings(&app);
+    Ok(s.vad_trim_silence)
+}
+
+#[command]
+pub fn set_vad_trim_silence(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut s = crate::settings::load_settings(&app);
+    s.vad_trim_silence = enabled;
+    crate::settings::save_settings(&app, &s)
+}
+
+#[command]
+pub fn get_hands_free_silence_ms(app: AppHandle) -> Result<u32, String> {
+    let s = crate::settings::load_settings(&app);
+    Ok(s.hands_free_silence_ms)
+}
+
+#[command]
+pub fn set_hands_free_silence_ms(app: AppHandle, silence_ms: u32) -> Result<(), String> {
+    if !(300..=10_000).contains(&silence_ms) {
+        return Err("Silence duration must be between 300 and 10000 ms".to_string());
+    }
+
+    let mut s = crate::settings::load_settings(&app);
+    s.hands_free_silence_ms = silence_ms;
+    crate::settings::save_settings(&app, &s)
+}
+
+#[command]
+pub fn get_live_preview_enabled(app: AppHandle) -> Result<bool, String> {
+    let s = crate::settings::load_settings(&app);
+    Ok(s.live_preview_enabled)
+}
+
+#[command]
+pub fn set_live_preview_enabled(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut s = crate::settings::load_settings(&app);
+    s.live_preview_enabled = enabled;
+    crate::settings::save_settings(&app, &s)
+}
+
+#[command]
+pub fn get_in_memory_capture(app: AppHandle) -> Result<bool, String> {
+    let s = crate::settings::load_settings(&app);
+    Ok(s.in_memory_capture)
+}
+
+#[command]
+pub fn set_in_memory_capture(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut s = crate::settings::load_settings(&app);
+    s.in_memory_capture = enabled;
+    crate::settings::save_settings(&app, &s)
+}
+
+#[command]
+pub fn get_preroll_ms(app: AppHandle) -> Result<u32, String> {
+    let s = crate::settings::load_settings(&app);
+    Ok(s.preroll_ms)
+}
+
+#[command]
+pub fn set_preroll_ms(app: AppHandle, preroll_ms: u32) -> Result<(), String> {
+    if preroll_ms > 2000 {
+        return Err("Pre-roll duration must be at most 2000 ms".to_string());
+    }
+
+    let mut s = crate::settings::load_settings(&app);
+    s.preroll_ms = preroll_ms;
+    crate::settings::save_settings(&app, &s)?;
+    crate::audio::preroll::refresh_preroll(&app);
+    Ok(())
+}
+
+#[command]
+pub fn get_preroll_policy(app: AppHandle) -> Result<PrerollPolicy, String> {
+    let s = crate::settings::load_settings(&app);
+    Ok(s.preroll_policy)
+}
+
+#[command]
+pub fn set_preroll_policy(app: AppHandle, policy: PrerollPolicy) -> Result<(), String> {
+    let mut s = crate::settings::load_settings(&app);
+    s.preroll_policy = policy;
+    crate::settings::save_settings(&app, &s)?;
+    crate::audio::preroll::refresh_preroll(&app);
+    Ok(())
+}
+
+#[command]
+pub fn get_dsp_high_pass_hz(app: AppHandle) -> Result<u32, String> {
+    let s = crate::settings::load_settings(&app);
+    Ok(s.dsp_high_pass_hz)
+}
+
+#[command]
+pub fn set_dsp_high_pass_hz(app: AppHandle, cutoff_hz: u32) -> Result<(), String> {
+    if cutoff_hz > 500 {
+        return Err("High-pass cutoff must be at most 500 Hz".to_string());
+    }
+
+    let mut s = crate::settings::load_settings(&app);
+    s.dsp_high_pass_hz = cutoff_hz;
+    crate::settings::save_settings(&app, &s)
+}
+
+#[command]
+pub fn get_dsp_noise_gate_db(app: AppHandle) -> Result<Option<f32>, String> {
+    let s = crate::settings::load_settings(&app);
+    Ok(s.dsp_noise_gate_db)
+}
+
+#[command]
+pub fn set_dsp_noise_gate_db(app: AppHandle, threshold_db: Option<f32>) -> Result<(), String> {
+    if threshold_db.is_some_and(|db| !(-90.0..=-10.0).contains(&db)) {
+        return Err("Noise gate threshold must be between -90 and -10 dBFS".to_string());
+    }
+
+    let mut s = crate::settings::load_settings(&app);
+    s.dsp_noise_gate_db = threshold_db;
+    crate::settings::save_settings(&app, &s)
+}
+
+#[command]
+pub fn get_dsp_normalization(app: AppHandle) -> Result<Normalization, String> {
+    let s = crate::settings::load_settings(&app);
+    Ok(s.dsp_normalization)
+}
+
+#[command]
+pub fn set_dsp_normalization(app: AppHandle, normalization: Normalization) -> Result<(), String> {
+    let mut s = crate::settings::load_settings(&app);
+    s.dsp_normalization = normalization;
+    crate::settings::save_settings(&app, &s)
+}
+
+#[command]
+pub fn get_resample_quality(app: AppHandle) -> Result<ResampleQuality, String> {
+    let s = crate::settings::load_settings(&app);
+    Ok(s.resample_quality)
+}
+
+#[command]
+pub fn set_resample_quality(app: AppHandle, quality: ResampleQuality) -> Result<(), String> {
+    let mut s = crate::settings::load_settings(&app);
+    s.resample_quality = quality;
+    crate::settings::save_settings(&app, &s)
+}
+
+#[command]
+pub fn get_recording_limit_secs(app: AppHandle) -> Result<u32, String> {
+    let s = crate::settings::load_settings(&app);
+    Ok(s.recording_limit_secs)
+}
+
+#[command]
+pub fn set_recording_limit_secs(app: AppHandle, limit_secs: u32) -> Result<(), String> {
+    if !(30..=3600).contains(&limit_secs) {
+        return Err("Recording limit must be between 30 and 3600 seconds".to_string());
+    }
+
+    let mut s = crate::settings::load_settings(&app);
+    s.recording_limit_secs = limit_secs;
+    crate::settings::save_settings(&app, &s)
+}
+
+#[command]
+pub fn get_recording_limit_warning_secs(app: AppHandle) -> Result<u32, String> {
+    let s = crate::settings::load_settings(&app);
+    Ok(s.recording_limit_warning_secs)
+}
+
+#[command]
+pub fn set_recording_limit_warning_secs(app: AppHandle, warning_secs: u32) -> Result<(), String> {
+    if warning_secs > 60 {
+        return Err("Recording limit warning must be at most 60 seconds".to_string());
+    }
+
+    let mut s = crate::settings::load_settings(&app);
+    s.recording_limit_warning_secs = warning_secs;
+    crate::settings::save_settings(&app, &s)
+}
+
+#[command]
+pub fn get_recording_limit_action(app: AppHandle) -> Result<RecordingLimitAction, String> {
+    let s = crate::settings::load_settings(&app);
+    Ok(s.recording_limit_action)
+}
+
+#[command]
+pub fn set_recording_limit_action(
+    app: AppHandle,
+    action: RecordingLimitAction,
+) -> Result<(), String> {
+    let mut s = crate::settings::load_settings(&app);
+    s.recording_limit_action = action;
+    crate::settings::save_settings(&app, &s)
+}
+
+#[command]
+pub fn get_min_recording_ms(app: AppHandle) -> Result<u32, String> {
+    let s = crate::settings::load_settings(&app);
+    Ok(s.min_recording_ms)
+}
+
+#[command]
+pub fn set_min_recording_ms(app: AppHandle, min_ms: u32) -> Result<(), String> {
+    if min_ms > 5000 {
+        return Err("Minimum recording duration must be at most 5000 ms".to_string());
+    }
+
+    let mut s = crate::settings::load_settings(&app);
+    s.min_recording_ms = min_ms;
+    crate::settings::save_settings(&app, &s)
+}
+
+#[command]
+pub fn get_min_recording_level_db(app: AppHandle) -> Result<Option<f32>, String> {
+    let s = crate::settings::load_settings(&app);
+    Ok(s.min_recording_level_db)
+}
+
+#[command]
+pub fn set_min_recording_level_db(app: AppHandle, level_db: Option<f32>) -> Result<(), String> {
+    if level_db.is_some_and(|db| !(-90.0..=-20.0).contains(&db)) {
+        return Err("Minimum recording level must be between -90 and -20 dBFS".to_string());
+    }
+
+    let mut s = crate::settings::load_settings(&app);
+    s.min_recording_level_db = level_db;
+    crate::settings::save_settings(&app, &s)
+}
+
+#[command]
+pub fn get_retained_recordings(app: AppHandle) -> Result<usize, String> {
+    let s = crate::settings::load_settings(&app);
+    Ok(s.retained_recordings)
+}
+
+#[command]
+pub fn set_retained_recordings(app: AppHandle, count: usize) -> Result<(), String> {
+    if count > 10 {
+        return Err("At most 10 recordings can be kept".to_string());
+    }
+
+    let mut s = crate::settings::load_settings(&app);
+    s.retained_recordings = count;
+    crate::settings::save_settings(&app, &s)?;
+    app.state::<crate::audio::types::AudioState>()
+        .recent_recordings
+        .lock()
+        .truncate(count);
+    Ok(())
+}
+
+#[command]
+pub fn get_mic_channel_count(mic_id: Option<String>) -> Result<u16, String> {
+    crate::audio::microphone::get_channel_count(mic_id.as_deref())
+        .ok_or_else(|| "Microphone not found".to_string())
+}
+
+#[command]
+pub fn get_mic_channel_strategy(
+    app: AppHandle,
+    mic_id: Option<String>,
+) -> Result<ChannelStrategy, String> {
+    let s = crate::settings::load_settings(&app);
+    let key = mic_id.as_deref().unwrap_or(DEFAULT_MIC_KEY);
+    Ok(s.channel_strategies.get(key).copied().unwrap_or_default())
+}
+
+#[command]
+pub fn set_mic_channel_strategy(
+    app: AppHandle,
+    mic_id: Option<String>,
+    strategy: ChannelStrategy,
+) -> Result<(), String> {
+    let mut s = crate::settings::load_settings(&app);
+    let key = mic_id.unwrap_or_else(|| DEFAULT_MIC_KEY.to_string());
+    s.channel_strategies.insert(key, strategy);
+    crate::settings::save_settings(&app, &s)?;
+    crate::audio::preroll::restart_preroll(&app);
+    Ok(())
+}
+
+#[command]
+pub fn get_file_channel_strategy(app: AppHandle) -> Result<ChannelStrategy, String> {
+    let s = crate::settings::load_settings(&app);
+    Ok(s.file_channel_strategy)
+}
+
+#[command]
+pub fn set_file_channel_strategy(app: AppHandle, strategy: ChannelStrategy) -> Result<(), String> {
+    let mut s = crate::settings::load_settings(&app);
+    s.file_channel_strategy = strategy;
+    crate::settings::save_settings(&app, &s)
+}
@@ -276,6 +276,64 @@ pub fn set_cancel_recording_shortcut(app: AppHandle, binding: String) -> Result<
     Ok(normalized)
 }
 
+// ============================================================================
+// Pause Recording Shortcut
+// ============================================================================
+
+#[command]
+pub fn get_pause_recording_shortcut(app: AppHandle) -> Result<String, String> {
+    let s = settings::load_settings(&app);
+    Ok(s.pause_recording_shortcut)
+}
+
+#[command]
+pub fn set_pause_recording_shortcut(app: AppHandle, binding: String) -> Result<String, String> {
+    // An empty binding turns the shortcut off
+    let keys = parse_binding_keys(&binding);
+    if keys.is_empty() && !binding.trim().is_empty() {
+        return Err("Invalid shortcut".to_string());
+    }
+    let normalized = keys_to_string(&keys);
+
+    let mut s = settings::load_settings(&app);
+    s.pause_recording_shortcut = normalized.clone();
+    settings::save_settings(&app, &s)?;
+
+    app.state::<ShortcutRegistryState>()
+        .update_binding(ShortcutAction::PauseRecording, keys);
+
+    Ok(normalized)
+}
+
+// ============================================================================
+// Reprocess Shortcut
+// ============================================================================
+
+#[command]
+pub fn get_reprocess_shortcut(app: AppHandle) -> Result<String, String> {
+    let s = settings::load_settings(&app);
+    Ok(s.reprocess_shortcut)
+}
+
+#[command]
+pub fn set_reprocess_shortcut(app: AppHandle, binding: String) -> Result<String, String> {
+    // An empty binding turns the shortcut off
+    let keys = parse_binding_keys(&binding);
+    if keys.is_empty() && !binding.trim().is_empty() {
+        return Err("Invalid shortcut".to_string());
+    }
+    let normalized = keys_to_string(&keys);
+
+    let mut s = settings::load_settings(&app);
+    s.reprocess_shortcut = normalized.clone();
+    settings::save_settings(&app, &s)?;
+
+    app.state::<ShortcutRegistryState>()
+        .update_binding(ShortcutAction::ReprocessLastRecording, keys);
+
+    Ok(normalized)
+}
+
 // ============================================================================
 // Accessibility (macOS only)
 // ============================================================================
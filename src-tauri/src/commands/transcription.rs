@@ -0,0 +1,13 @@
+use crate::audio::types::QueueDepth;
+use tauri::{command, AppHandle};
+
+#[command]
+pub fn get_transcription_queue_depth(app: AppHandle) -> QueueDepth {
+    crate::audio::get_queue_depth(&app)
+}
+
+/// Returns false when the job already finished or does not exist.
+#[command]
+pub fn cancel_transcription(app: AppHandle, job_id: u64) -> bool {
+    crate::audio::cancel_transcription(&app, job_id)
+}
@@ -0,0 +1,177 @@
+use std::path::Path;
+use std::time::Instant;
+
+use super::engine::SAMPLE_RATE;
+use super::registry;
+use super::transcription_engine::TranscriptionEngine;
+use super::types::{
+    BenchmarkResult, ModelParams, OptimizationLevel, QuantizationType, SessionOptions,
+};
+
+/// Each configuration transcribes the audio this many times, the fastest run is kept.
+const BENCHMARK_RUNS: usize = 3;
+
+/// Configurations worth comparing on a CPU with `cores` logical cores:
+/// both precisions with one thread, half and all cores, then the optimization levels.
+pub fn default_benchmark_configs(cores: usize) -> Vec<ModelParams> {
+    let mut thread_counts = vec![1, cores / 2, cores];
+    thread_counts.retain(|&threads| threads > 0);
+    thread_counts.dedup();
+
+    let mut configs = Vec::new();
+    for quantization in [QuantizationType::Int8, QuantizationType::FP32] {
+        for &threads in &thread_counts {
+            configs.push(ModelParams {
+                quantization,
+                session: SessionOptions {
+                    intra_threads: Some(threads),
+                    ..Default::default()
+                },
+            });
+        }
+    }
+
+    for optimization_level in [
+        OptimizationLevel::Disable,
+        OptimizationLevel::Basic,
+        OptimizationLevel::Extended,
+    ] {
+        configs.push(ModelParams {
+            quantization: QuantizationType::Int8,
+            session: SessionOptions {
+                intra_threads: Some(cores.max(1)),
+                optimization_level,
+                ..Default::default()
+            },
+        });
+    }
+
+    configs
+}
+
+/// Speech-like test signal: a few harmonics with a syllable-rate envelope.
+pub fn benchmark_signal(seconds: f32) -> Vec<f32> {
+    let len = (seconds * SAMPLE_RATE as f32) as usize;
+    (0..len)
+        .map(|i| {
+            let t = i as f32 / SAMPLE_RATE as f32;
+            let envelope = 0.5 + 0.5 * (2.0 * std::f32::consts::PI * 4.0 * t).sin();
+            let voice: f32 = [180.0, 360.0, 720.0, 1440.0]
+                .iter()
+                .map(|freq| (2.0 * std::f32::consts::PI * freq * t).sin())
+                .sum();
+            0.05 * envelope * voice
+        })
+        .collect()
+}
+
+/// Loads the model once per configuration and measures its real-time factor on `samples`.
+/// A configuration failing to load or run is reported with its error, the others still run.
+pub fn run_benchmark(
+    engine_id: &str,
+    model_path: &Path,
+    samples: &[f32],
+    configs: Vec<ModelParams>,
+    mut on_result: impl FnMut(&BenchmarkResult),
+) -> Vec<BenchmarkResult> {
+    configs
+        .into_iter()
+        .map(|params| {
+            let result = benchmark_config(engine_id, model_path, samples, params);
+            on_result(&result);
+            result
+        })
+        .collect()
+}
+
+fn benchmark_config(
+    engine_id: &str,
+    model_path: &Path,
+    samples: &[f32],
+    params: ModelParams,
+) -> BenchmarkResult {
+    let mut result = BenchmarkResult {
+        params: params.clone(),
+        load_seconds: 0.0,
+        real_time_factor: None,
+        error: None,
+    };
+
+    let Some(mut engine) = registry::create_engine(engine_id) else {
+        result.error = Some(format!("Unknown transcription engine: {}", engine_id));
+        return result;
+    };
+
+    let started = Instant::now();
+    if let Err(e) = engine.load_model_with_params(model_path, params.clone()) {
+        result.error = Some(format!("Failed to load model: {}", e));
+        return result;
+    }
+    let load_seconds = started.elapsed().as_secs_f32();
+
+    BenchmarkResult {
+        load_seconds,
+        ..benchmark_loaded_engine(engine.as_mut(), params, samples)
+    }
+}
+
+/// Measures the real-time factor of an engine already loaded with `params`.
+/// `load_seconds` is left at zero for the caller to fill in.
+pub fn benchmark_loaded_engine(
+    engine: &mut dyn TranscriptionEngine,
+    params: ModelParams,
+    samples: &[f32],
+) -> BenchmarkResult {
+    let audio_seconds = samples.len() as f32 / SAMPLE_RATE as f32;
+    let mut result = BenchmarkResult {
+        params,
+        load_seconds: 0.0,
+        real_time_factor: None,
+        error: None,
+    };
+
+    // The first run builds ONNX Runtime execution plans, it is not measured
+    let warmup = samples[..samples.len().min(SAMPLE_RATE)].to_vec();
+    if let Err(e) = engine.transcribe_samples(warmup, None) {
+        result.error = Some(format!("Transcription failed: {}", e));
+        return result;
+    }
+
+    let mut fastest = f32::MAX;
+    for _ in 0..BENCHMARK_RUNS {
+        let started = Instant::now();
+        if let Err(e) = engine.transcribe_samples(samples.to_vec(), None) {
+            result.error = Some(format!("Transcription failed: {}", e));
+            return result;
+        }
+        fastest = fastest.min(started.elapsed().as_secs_f32());
+    }
+
+    result.real_time_factor = Some(fastest / audio_seconds.max(f32::EPSILON));
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_benchmark_configs_skip_duplicate_thread_counts() {
+        let configs = default_benchmark_configs(2);
+        // 1 and 2 threads for both precisions, then 3 optimization levels
+        assert_eq!(configs.len(), 7);
+
+        let single_core = default_benchmark_configs(1);
+        assert_eq!(single_core.len(), 5);
+        assert!(single_core
+            .iter()
+            .all(|params| params.session.intra_threads == Some(1)));
+    }
+
+    #[test]
+    fn test_benchmark_signal_length_and_range() {
+        let signal = benchmark_signal(2.0);
+        assert_eq!(signal.len(), 2 * SAMPLE_RATE);
+        assert!(signal.iter().all(|sample| sample.abs() <= 0.2));
+    }
+}
@@ -0,0 +1,175 @@
+use super::types::{HotwordNode, HotwordState, HotwordTrie};
+use std::collections::HashMap;
+
+const ROOT: usize = 0;
+
+impl HotwordTrie {
+    /// Builds the tree from words tokenized with the model vocabulary.
+    /// Words that cannot be spelled with the vocabulary are skipped.
+    pub fn from_words(words: &[String], vocab: &[String], weight: f32) -> Self {
+        let mut trie = Self {
+            nodes: vec![HotwordNode::default()],
+            weight,
+        };
+
+        let pieces: HashMap<&str, i32> = vocab
+            .iter()
+            .enumerate()
+            .filter(|(_, piece)| !piece.is_empty() && !piece.starts_with('<'))
+            .map(|(id, piece)| (piece.as_str(), id as i32))
+            .collect();
+
+        for word in words {
+            let word = word.trim();
+            if word.is_empty() {
+                continue;
+            }
+            // SentencePiece marks the start of a word with a space
+            match tokenize_word(&format!(" {}", word), &pieces) {
+                Some(tokens) => trie.insert(&tokens),
+                None => log::debug!("Cannot spell hotword '{}' with the model vocabulary", word),
+            }
+        }
+
+        trie
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes[ROOT].children.is_empty()
+    }
+
+    fn insert(&mut self, tokens: &[i32]) {
+        let mut node = ROOT;
+        for &token in tokens {
+            node = match self.nodes[node].children.get(&token) {
+                Some(&child) => child,
+                None => {
+                    self.nodes.push(HotwordNode::default());
+                    let child = self.nodes.len() - 1;
+                    self.nodes[node].children.insert(token, child);
+                    child
+                }
+            };
+        }
+        self.nodes[node].is_end = true;
+    }
+
+    /// Adds the hotword bonus to the tokens that continue or start a hotword.
+    pub fn boost(&self, state: HotwordState, log_probs: &mut [f32]) {
+        let roots = &self.nodes[ROOT].children;
+        let continuations = match state.node {
+            ROOT => None,
+            node => Some(
+                self.nodes[node]
+                    .children
+                    .keys()
+                    .filter(|token| !roots.contains_key(token)),
+            ),
+        };
+
+        for &token in roots.keys().chain(continuations.into_iter().flatten()) {
+            if let Some(log_prob) = log_probs.get_mut(token as usize) {
+                *log_prob += self.weight;
+            }
+        }
+    }
+
+    /// Moves along the tree after `token` is emitted and returns the new state with
+    /// the score adjustment to apply: a bonus when the token matches a hotword,
+    /// minus the bonus of the partial match it abandons.
+    pub fn advance(&self, state: HotwordState, token: i32) -> (HotwordState, f32) {
+        if let Some(&child) = self.nodes[state.node].children.get(&token) {
+            return (self.enter(child, state.matched + 1), self.weight);
+        }
+
+        let penalty = -self.weight * state.matched as f32;
+        match self.nodes[ROOT].children.get(&token) {
+            Some(&child) => (self.enter(child, 1), penalty + self.weight),
+            None => (HotwordState::default(), penalty),
+        }
+    }
+
+    fn enter(&self, node: usize, matched: usize) -> HotwordState {
+        let entered = &self.nodes[node];
+        match (entered.is_end, entered.children.is_empty()) {
+            // Completed hotword, nothing longer to follow
+            (true, true) => HotwordState::default(),
+            // Completed hotword that is also the prefix of a longer one
+            (true, false) => HotwordState { node, matched: 0 },
+            (false, _) => HotwordState { node, matched },
+        }
+    }
+}
+
+/// Splits a word into vocabulary pieces by greedy longest match.
+fn tokenize_word(word: &str, pieces: &HashMap<&str, i32>) -> Option<Vec<i32>> {
+    let mut tokens = Vec::new();
+    let mut rest = word;
+
+    while !rest.is_empty() {
+        let (len, token) = rest
+            .char_indices()
+            .map(|(i, c)| i + c.len_utf8())
+            .rev()
+            .find_map(|len| pieces.get(&rest[..len]).map(|&token| (len, token)))?;
+        tokens.push(token);
+        rest = &rest[len..];
+    }
+
+    Some(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vocab() -> Vec<String> {
+        ["<unk>", " mur", "m", "ure", " m", " the", "ur", "e"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_from_words_uses_longest_pieces() {
+        let trie = HotwordTrie::from_words(&["murmure".to_string()], &vocab(), 1.0);
+        let mut state = HotwordState::default();
+        let mut total = 0.0;
+        for token in [1, 2, 3] {
+            let (next, bonus) = trie.advance(state, token);
+            state = next;
+            total += bonus;
+        }
+        assert_eq!(state, HotwordState::default());
+        assert_eq!(total, 3.0);
+    }
+
+    #[test]
+    fn test_advance_takes_back_bonus_of_broken_match() {
+        let trie = HotwordTrie::from_words(&["murmure".to_string()], &vocab(), 1.0);
+        let (state, first) = trie.advance(HotwordState::default(), 1);
+        let (state, second) = trie.advance(state, 2);
+        let (state, broken) = trie.advance(state, 5);
+        assert_eq!(first + second + broken, 0.0);
+        assert_eq!(state, HotwordState::default());
+    }
+
+    #[test]
+    fn test_boost_only_touches_matching_tokens() {
+        let trie = HotwordTrie::from_words(&["murmure".to_string()], &vocab(), 2.0);
+        let mut log_probs = vec![0.0; 8];
+        trie.boost(HotwordState::default(), &mut log_probs);
+        assert_eq!(log_probs, vec![0.0, 2.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0]);
+
+        let (state, _) = trie.advance(HotwordState::default(), 1);
+        let mut log_probs = vec![0.0; 8];
+        trie.boost(state, &mut log_probs);
+        assert_eq!(log_probs, vec![0.0, 2.0, 2.0, 0.0, 0.0, 0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_words_outside_vocabulary_are_skipped() {
+        let trie = HotwordTrie::from_words(&["xyz".to_string()], &vocab(), 1.0);
+        assert!(trie.is_empty());
+    }
+}
@@ -0,0 +1,194 @@
+use ndarray::{Array1, Array2, ArrayD, ArrayViewD};
+use ort::inputs;
+use ort::value::TensorRef;
+use std::path::Path;
+
+use super::engine::{decode_chunked, decode_tokens, SUBSAMPLING_FACTOR};
+use super::helpers::{convert_timestamps, log_softmax};
+use super::transcription_engine::{TranscriptionEngine, TranscriptionResult};
+use super::types::{
+    ChunkingParams, CtcEngine, CtcModel, DecodedTokens, DecodingOptions, InferenceParams,
+    ModelParams, ParakeetError, ParakeetModel, QuantizationType, TimestampedResult,
+};
+
+impl CtcModel {
+    pub fn new<P: AsRef<Path>>(model_dir: P, params: &ModelParams) -> Result<Self, ParakeetError> {
+        let quantized = params.quantization == QuantizationType::Int8;
+        let model = ParakeetModel::init_session(&model_dir, "model", &params.session, quantized)?;
+        let preprocessor =
+            ParakeetModel::init_session(&model_dir, "nemo128", &params.session, false)?;
+        let (vocab, blank_idx) = ParakeetModel::load_vocab(&model_dir)?;
+
+        log::trace!(
+            "Loaded CTC vocabulary with {} tokens, blank_idx={}",
+            vocab.len(),
+            blank_idx
+        );
+
+        Ok(Self {
+            model,
+            preprocessor,
+            vocab,
+            blank_idx,
+        })
+    }
+
+    /// Transcribes `samples`, in overlapping windows when `chunking` is set and the
+    /// audio is longer than one window, like the Parakeet model.
+    pub fn transcribe_samples(
+        &mut self,
+        samples: Vec<f32>,
+        chunking: Option<&ChunkingParams>,
+        decoding: &DecodingOptions,
+    ) -> Result<TimestampedResult, ParakeetError> {
+        let chunked = match chunking {
+            Some(chunking) => decode_chunked(&samples, chunking, decoding, |window| {
+                self.decode_window(window)
+            })?,
+            None => None,
+        };
+        let decoded = match chunked {
+            Some(decoded) => decoded,
+            None => {
+                decoding.check_cancelled()?;
+                self.decode_window(&samples)?
+            }
+        };
+        Ok(decode_tokens(&self.vocab, decoded))
+    }
+
+    /// Greedy decoding of a single window.
+    fn decode_window(&mut self, samples: &[f32]) -> Result<DecodedTokens, ParakeetError> {
+        let waveforms = Array2::from_shape_vec((1, samples.len()), samples.to_vec())?.into_dyn();
+        let waveforms_lens = Array1::from_vec(vec![samples.len() as i64]).into_dyn();
+
+        let (features, features_lens) =
+            self.preprocess(&waveforms.view(), &waveforms_lens.view())?;
+        let features_len = features_lens.iter().next().copied().unwrap_or(0) as usize;
+
+        let log_probs = self.forward(&features.view(), &features_lens.view())?;
+        let frames_len = features_len
+            .div_ceil(SUBSAMPLING_FACTOR)
+            .min(log_probs.shape()[1]);
+
+        Ok(self.greedy_decode(&log_probs, frames_len))
+    }
+
+    fn preprocess(
+        &mut self,
+        waveforms: &ArrayViewD<f32>,
+        waveforms_lens: &ArrayViewD<i64>,
+    ) -> Result<(ArrayD<f32>, ArrayD<i64>), ParakeetError> {
+        let inputs = inputs![
+            "waveforms" => TensorRef::from_array_view(waveforms.view())?,
+            "waveforms_lens" => TensorRef::from_array_view(waveforms_lens.view())?,
+        ];
+        let outputs = self.preprocessor.run(inputs)?;
+
+        let features = outputs
+            .get("features")
+            .ok_or_else(|| ParakeetError::OutputNotFound("features".to_string()))?
+            .try_extract_array()?;
+        let features_lens = outputs
+            .get("features_lens")
+            .ok_or_else(|| ParakeetError::OutputNotFound("features_lens".to_string()))?
+            .try_extract_array()?;
+
+        Ok((features.to_owned(), features_lens.to_owned()))
+    }
+
+    /// Runs the acoustic model and returns per-frame scores, `[batch, time, vocab]`.
+    fn forward(
+        &mut self,
+        audio_signal: &ArrayViewD<f32>,
+        length: &ArrayViewD<i64>,
+    ) -> Result<ArrayD<f32>, ParakeetError> {
+        log::trace!("Running CTC model inference...");
+        let inputs = inputs![
+            "audio_signal" => TensorRef::from_array_view(audio_signal.view())?,
+            "length" => TensorRef::from_array_view(length.view())?,
+        ];
+        let outputs = self.model.run(inputs)?;
+
+        let log_probs = outputs
+            .get("logprobs")
+            .ok_or_else(|| ParakeetError::OutputNotFound("logprobs".to_string()))?
+            .try_extract_array()?;
+
+        Ok(log_probs.to_owned())
+    }
+
+    /// Takes the best token of every frame, then collapses repeats and drops blanks.
+    fn greedy_decode(&self, log_probs: &ArrayD<f32>, frames_len: usize) -> DecodedTokens {
+        let mut decoded = DecodedTokens::default();
+        let mut previous = self.blank_idx;
+
+        for t in 0..frames_len {
+            let frame = log_probs.slice(ndarray::s![0, t, ..]).to_vec();
+            // Exported models may return raw logits instead of log-probabilities
+            let frame = log_softmax(&frame);
+            let (token, log_prob) = frame
+                .iter()
+                .enumerate()
+                .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+                .map(|(idx, &log_prob)| (idx as i32, log_prob))
+                .unwrap_or((self.blank_idx, 0.0));
+
+            decoded.score += log_prob;
+            if token != self.blank_idx && token != previous {
+                decoded.tokens.push(token);
+                decoded.frames.push(t);
+                decoded.confidences.push(log_prob.exp());
+            }
+            previous = token;
+        }
+
+        decoded
+    }
+}
+
+impl TranscriptionEngine for CtcEngine {
+    fn id(&self) -> &'static str {
+        "ctc"
+    }
+
+    fn loaded_model_path(&self) -> Option<&Path> {
+        self.loaded_model_path.as_deref()
+    }
+
+    fn load_model_with_params(
+        &mut self,
+        model_path: &Path,
+        params: ModelParams,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let model = CtcModel::new(model_path, &params)?;
+
+        self.model = Some(model);
+        self.loaded_model_path = Some(model_path.to_path_buf());
+        Ok(())
+    }
+
+    /// CTC decoding is always greedy: beam search and biasing options are ignored.
+    /// Long audio is chunked and cancelled between windows like with Parakeet.
+    fn transcribe_samples(
+        &mut self,
+        samples: Vec<f32>,
+        params: Option<InferenceParams>,
+    ) -> Result<TranscriptionResult, Box<dyn std::error::Error>> {
+        let model = self
+            .model
+            .as_mut()
+            .ok_or("Model not loaded. Call load_model_with_params() first.")?;
+
+        let params = params.unwrap_or_default();
+        let timestamped_result =
+            model.transcribe_samples(samples, params.chunking.as_ref(), &params.decoding)?;
+        let segments = convert_timestamps(&timestamped_result, params.timestamp_granularity);
+
+        Ok(TranscriptionResult {
+            text: timestamped_result.text,
+            segments,
+            alternatives: Vec::new(),
+        })
+    }
+}
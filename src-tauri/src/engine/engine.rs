@@ -1,23 +1,27 @@
 use ndarray::{Array, Array1, Array2, ArrayD, ArrayViewD, IxDyn};
-use once_cell::sync::Lazy;
 use ort::execution_providers::CPUExecutionProvider;
 use ort::inputs;
 use ort::session::builder::GraphOptimizationLevel;
 use ort::session::Session;
 use ort::value::TensorRef;
-use regex::Regex;
 
 use std::fs;
 use std::path::Path;
 
-use super::types::{DecoderState, ParakeetError, ParakeetModel, TimestampedResult};
+use super::helpers::{log_softmax, merge_chunk_decodings, tokens_to_text, top_k_indices};
+use super::transcription_engine::TranscriptionAlternative;
+use super::types::{
+    BeamHypothesis, ChunkDecoding, ChunkingParams, DecodedTokens, DecoderState, DecodingOptions,
+    DecodingStrategy, HotwordState, HotwordTrie, ModelParams, OptimizationLevel, ParakeetError,
+    ParakeetModel, QuantizationType, SessionOptions, TimestampedResult,
+};
 
-const SUBSAMPLING_FACTOR: usize = 8;
-const WINDOW_SIZE: f32 = 0.01;
+pub(super) const SAMPLE_RATE: usize = 16000;
+pub(super) const SUBSAMPLING_FACTOR: usize = 8;
+pub(super) const WINDOW_SIZE: f32 = 0.01;
 const MAX_TOKENS_PER_STEP: usize = 10;
-
-static DECODE_SPACE_RE: Lazy<Result<Regex, regex::Error>> =
-    Lazy::new(|| Regex::new(r"\A\s|\s\B|(\s)\b"));
+/// Frame skips predicted by the duration head of Parakeet TDT models.
+const TDT_DURATIONS: [usize; 5] = [0, 1, 2, 3, 4];
 
 impl Drop for ParakeetModel {
     fn drop(&mut self) {
@@ -29,10 +33,16 @@ impl Drop for ParakeetModel {
 }
 
 impl ParakeetModel {
-    pub fn new<P: AsRef<Path>>(model_dir: P, quantized: bool) -> Result<Self, ParakeetError> {
-        let encoder = Self::init_session(&model_dir, "encoder-model", None, quantized)?;
-        let decoder_joint = Self::init_session(&model_dir, "decoder_joint-model", None, quantized)?;
-        let preprocessor = Self::init_session(&model_dir, "nemo128", None, false)?;
+    pub fn new<P: AsRef<Path>>(model_dir: P, params: &ModelParams) -> Result<Self, ParakeetError> {
+        let quantized = params.quantization == QuantizationType::Int8;
+        let encoder = Self::init_session(&model_dir, "encoder-model", &params.session, quantized)?;
+        let decoder_joint = Self::init_session(
+            &model_dir,
+            "decoder_joint-model",
+            &params.session,
+            quantized,
+        )?;
+        let preprocessor = Self::init_session(&model_dir, "nemo128", &params.session, false)?;
 
         let (vocab, blank_idx) = Self::load_vocab(&model_dir)?;
         let vocab_size = vocab.len();
@@ -50,13 +60,15 @@ impl ParakeetModel {
             vocab,
             blank_idx,
             vocab_size,
+            #[cfg(test)]
+            decoder_joint_calls: 0,
         })
     }
 
-    fn init_session<P: AsRef<Path>>(
+    pub(super) fn init_session<P: AsRef<Path>>(
         model_dir: P,
         model_name: &str,
-        intra_threads: Option<usize>,
+        options: &SessionOptions,
         try_quantized: bool,
     ) -> Result<Session, ParakeetError> {
         let providers = vec![CPUExecutionProvider::default().build()];
@@ -82,17 +94,26 @@ impl ParakeetModel {
             regular_name
         };
 
+        let optimization_level = match options.optimization_level {
+            OptimizationLevel::Disable => GraphOptimizationLevel::Disable,
+            OptimizationLevel::Basic => GraphOptimizationLevel::Level1,
+            OptimizationLevel::Extended => GraphOptimizationLevel::Level2,
+            OptimizationLevel::All => GraphOptimizationLevel::Level3,
+        };
+        let parallel = options.inter_threads.is_some_and(|threads| threads > 1);
+
         let mut builder = Session::builder()?
             .with_config_entry("session.log_severity_level", "3")?
-            .with_optimization_level(GraphOptimizationLevel::Level3)?
+            .with_optimization_level(optimization_level)?
             .with_execution_providers(providers)?
             .with_memory_pattern(false)?
-            .with_parallel_execution(false)?;
+            .with_parallel_execution(parallel)?;
 
-        if let Some(threads) = intra_threads {
-            builder = builder
-                .with_intra_threads(threads)?
-                .with_inter_threads(threads)?;
+        if let Some(threads) = options.intra_threads {
+            builder = builder.with_intra_threads(threads)?;
+        }
+        if let Some(threads) = options.inter_threads {
+            builder = builder.with_inter_threads(threads)?;
         }
 
         let session = builder.commit_from_file(model_dir.as_ref().join(&model_filename))?;
@@ -109,7 +130,9 @@ impl ParakeetModel {
         Ok(session)
     }
 
-    fn load_vocab<P: AsRef<Path>>(model_dir: P) -> Result<(Vec<String>, i32), ParakeetError> {
+    pub(super) fn load_vocab<P: AsRef<Path>>(
+        model_dir: P,
+    ) -> Result<(Vec<String>, i32), ParakeetError> {
         let vocab_path = model_dir.as_ref().join("vocab.txt");
         let content = fs::read_to_string(vocab_path)?;
 
@@ -241,6 +264,10 @@ impl ParakeetModel {
         encoder_out: &ArrayViewD<f32>, // [time_steps, 1024]
     ) -> Result<(ArrayD<f32>, DecoderState), ParakeetError> {
         log::trace!("Running decoder inference...");
+        #[cfg(test)]
+        {
+            self.decoder_joint_calls += 1;
+        }
 
         // Get last token or blank_idx if empty
         let target_token = prev_tokens.last().copied().unwrap_or(self.blank_idx);
@@ -295,131 +322,332 @@ impl ParakeetModel {
         &mut self,
         waveforms: &ArrayViewD<f32>,
         waveforms_len: &ArrayViewD<i64>,
+        decoding: &DecodingOptions,
     ) -> Result<Vec<TimestampedResult>, ParakeetError> {
+        let decodings = self.decode_batch(waveforms, waveforms_len, decoding)?;
+
+        let mut results = Vec::new();
+        for hypotheses in decodings {
+            let mut hypotheses = hypotheses.into_iter();
+            let best = hypotheses.next().unwrap_or_default();
+            let mut result = decode_tokens(&self.vocab, best);
+            result.alternatives = hypotheses
+                .map(|hypothesis| TranscriptionAlternative {
+                    score: hypothesis.score,
+                    text: decode_tokens(&self.vocab, hypothesis).text,
+                })
+                .collect();
+            results.push(result);
+        }
+
+        Ok(results)
+    }
+
+    fn decode_batch(
+        &mut self,
+        waveforms: &ArrayViewD<f32>,
+        waveforms_len: &ArrayViewD<i64>,
+        decoding: &DecodingOptions,
+    ) -> Result<Vec<Vec<DecodedTokens>>, ParakeetError> {
         // Preprocess and encode
+        decoding.check_cancelled()?;
         let (features, features_lens) = self.preprocess(waveforms, waveforms_len)?;
         let (encoder_out, encoder_out_lens) =
             self.encode(&features.view(), &features_lens.view())?;
 
         // Decode for each batch item
-        let mut results = Vec::new();
+        let mut decodings = Vec::new();
         for (encodings, &encodings_len) in encoder_out.outer_iter().zip(encoder_out_lens.iter()) {
-            let (tokens, timestamps) =
-                self.decode_sequence(&encodings.view(), encodings_len as usize)?;
-            let result = self.decode_tokens(tokens, timestamps);
-            results.push(result);
+            decodings.push(self.decode_sequence(
+                &encodings.view(),
+                encodings_len as usize,
+                decoding,
+            )?);
         }
 
-        Ok(results)
+        Ok(decodings)
     }
 
+    /// Decodes one encoded sequence and returns the ranked hypotheses, best first.
     fn decode_sequence(
         &mut self,
         encodings: &ArrayViewD<f32>, // [time_steps, 1024]
         encodings_len: usize,
-    ) -> Result<(Vec<i32>, Vec<usize>), ParakeetError> {
+        decoding: &DecodingOptions,
+    ) -> Result<Vec<DecodedTokens>, ParakeetError> {
+        let hotwords = decoding
+            .biasing
+            .as_ref()
+            .map(|biasing| HotwordTrie::from_words(&biasing.words, &self.vocab, biasing.weight))
+            .filter(|trie| !trie.is_empty());
+
+        match decoding.strategy {
+            DecodingStrategy::Greedy => Ok(vec![self.greedy_decode(
+                encodings,
+                encodings_len,
+                decoding,
+                hotwords.as_ref(),
+            )?]),
+            DecodingStrategy::BeamSearch { beam_size, n_best } => self.beam_search_decode(
+                encodings,
+                encodings_len,
+                decoding,
+                hotwords.as_ref(),
+                beam_size.max(1),
+                n_best.max(1),
+            ),
+        }
+    }
+
+    fn greedy_decode(
+        &mut self,
+        encodings: &ArrayViewD<f32>, // [time_steps, 1024]
+        encodings_len: usize,
+        decoding: &DecodingOptions,
+        hotwords: Option<&HotwordTrie>,
+    ) -> Result<DecodedTokens, ParakeetError> {
         let mut prev_state = self.create_decoder_state()?;
         let mut tokens = Vec::new();
         let mut timestamps = Vec::new();
+        let mut confidences = Vec::new();
+        let mut score = 0.0;
+        let mut hotword_state = HotwordState::default();
 
         let mut t = 0;
         let mut emitted_tokens = 0;
 
         while t < encodings_len {
+            decoding.check_cancelled()?;
             let encoder_step = encodings.slice(ndarray::s![t, ..]);
             // Convert to dynamic dimension to match decode_step parameter type
             let encoder_step_dyn = encoder_step.to_owned().into_dyn();
             let (probs, new_state) =
                 self.decode_step(&tokens, &prev_state, &encoder_step_dyn.view())?;
-
-            // For TDT models, split output into vocab logits and duration logits
-            // output[:vocab_size] = vocabulary logits
-            // output[vocab_size:] = duration logits
-            let vocab_logits_slice = probs.as_slice().ok_or_else(|| {
-                ParakeetError::Shape(ndarray::ShapeError::from_kind(
-                    ndarray::ErrorKind::IncompatibleShape,
-                ))
-            })?;
-
-            let vocab_logits = if probs.len() > self.vocab_size {
-                // TDT model - extract only vocabulary logits
-                log::trace!(
-                    "TDT model detected: splitting {} logits into vocab({}) + duration",
-                    probs.len(),
-                    self.vocab_size
-                );
-                &vocab_logits_slice[..self.vocab_size]
-            } else {
-                // Regular RNN-T model
-                vocab_logits_slice
+            let vocab_logits = self.vocab_logits(&probs)?;
+            let step = match decoding.use_tdt_durations {
+                true => self.duration_step(&probs),
+                false => 0,
             };
 
+            let log_probs = log_softmax(vocab_logits);
+            let mut biased_log_probs = log_probs.clone();
+            if let Some(trie) = hotwords {
+                trie.boost(hotword_state, &mut biased_log_probs);
+            }
+
             // Get argmax token from vocabulary logits only
-            let token = vocab_logits
+            let token = biased_log_probs
                 .iter()
                 .enumerate()
                 .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
                 .map(|(idx, _)| idx as i32)
                 .unwrap_or(self.blank_idx);
+            let log_prob = log_probs[token as usize];
+            score += log_prob;
 
             if token != self.blank_idx {
+                if let Some(trie) = hotwords {
+                    let (next_state, bonus) = trie.advance(hotword_state, token);
+                    hotword_state = next_state;
+                    score += bonus;
+                }
                 prev_state = new_state;
                 tokens.push(token);
                 timestamps.push(t);
+                confidences.push(log_prob.exp());
                 emitted_tokens += 1;
             }
 
-            // Step logic from Python - simplified since step is always -1
-            if token == self.blank_idx || emitted_tokens == MAX_TOKENS_PER_STEP {
+            // Step logic from Python: a predicted duration skips frames directly,
+            // otherwise stay on the frame until blank or too many tokens
+            if step > 0 {
+                t += step;
+                emitted_tokens = 0;
+            } else if token == self.blank_idx || emitted_tokens == MAX_TOKENS_PER_STEP {
                 t += 1;
                 emitted_tokens = 0;
             }
         }
 
-        Ok((tokens, timestamps))
+        Ok(DecodedTokens {
+            tokens,
+            frames: timestamps,
+            confidences,
+            score,
+        })
     }
 
-    fn decode_tokens(&self, ids: Vec<i32>, timestamps: Vec<usize>) -> TimestampedResult {
-        let tokens: Vec<String> = ids
-            .iter()
-            .filter_map(|&id| {
-                let idx = id as usize;
-                if idx < self.vocab.len() {
-                    Some(self.vocab[idx].clone())
-                } else {
-                    None
+    /// Beam search over the transducer lattice.
+    ///
+    /// Every hypothesis carries its own decoder state and frame index. At each
+    /// iteration the hypotheses that are furthest behind are expanded with blank
+    /// (move to the next frame) and with their `beam_size` best tokens, then the
+    /// pool is pruned back to `beam_size`. Identical hypotheses are merged.
+    fn beam_search_decode(
+        &mut self,
+        encodings: &ArrayViewD<f32>, // [time_steps, 1024]
+        encodings_len: usize,
+        decoding: &DecodingOptions,
+        hotwords: Option<&HotwordTrie>,
+        beam_size: usize,
+        n_best: usize,
+    ) -> Result<Vec<DecodedTokens>, ParakeetError> {
+        if encodings_len == 0 {
+            return Ok(vec![DecodedTokens::default()]);
+        }
+
+        let mut beam = vec![BeamHypothesis {
+            tokens: Vec::new(),
+            frames: Vec::new(),
+            confidences: Vec::new(),
+            state: self.create_decoder_state()?,
+            hotword_state: HotwordState::default(),
+            score: 0.0,
+            t: 0,
+            emitted_at_t: 0,
+        }];
+        let mut finished: Vec<BeamHypothesis> = Vec::new();
+
+        while !beam.is_empty() {
+            decoding.check_cancelled()?;
+            let t = beam.iter().map(|h| h.t).min().unwrap_or(encodings_len);
+            let (current, mut candidates): (Vec<_>, Vec<_>) =
+                beam.into_iter().partition(|h| h.t == t);
+
+            let encoder_step_dyn = encodings.slice(ndarray::s![t, ..]).to_owned().into_dyn();
+
+            for hypothesis in current {
+                let (probs, new_state) = self.decode_step(
+                    &hypothesis.tokens,
+                    &hypothesis.state,
+                    &encoder_step_dyn.view(),
+                )?;
+                let log_probs = log_softmax(self.vocab_logits(&probs)?);
+                let mut biased_log_probs = log_probs.clone();
+                if let Some(trie) = hotwords {
+                    trie.boost(hypothesis.hotword_state, &mut biased_log_probs);
                 }
-            })
-            .collect();
-
-        let text = match &*DECODE_SPACE_RE {
-            Ok(regex) => regex
-                .replace_all(&tokens.join(""), |caps: &regex::Captures| {
-                    if caps.get(1).is_some() {
-                        " "
-                    } else {
-                        ""
+                let step = match decoding.use_tdt_durations {
+                    true => self.duration_step(&probs),
+                    false => 0,
+                };
+
+                // Blank: stay on the same tokens and move to the next frame
+                let mut blank = hypothesis.clone();
+                blank.score += log_probs[self.blank_idx as usize];
+                blank.t += step.max(1);
+                blank.emitted_at_t = 0;
+                candidates.push(blank);
+
+                // Non-blank: extend the hypothesis with one of the best tokens
+                for token in top_k_indices(&biased_log_probs, beam_size + 1) {
+                    let token = token as i32;
+                    if token == self.blank_idx {
+                        continue;
                     }
-                })
-                .to_string(),
-            Err(_) => tokens.join(""), // Fallback if regex failed to compile
+                    let mut extended = hypothesis.clone();
+                    extended.state = new_state.clone();
+                    extended.tokens.push(token);
+                    extended.frames.push(t);
+                    extended.confidences.push(log_probs[token as usize].exp());
+                    extended.score += log_probs[token as usize];
+                    if let Some(trie) = hotwords {
+                        let (hotword_state, bonus) = trie.advance(hypothesis.hotword_state, token);
+                        extended.hotword_state = hotword_state;
+                        extended.score += bonus;
+                    }
+                    extended.emitted_at_t += 1;
+                    if step > 0 {
+                        extended.t += step;
+                        extended.emitted_at_t = 0;
+                    } else if extended.emitted_at_t == MAX_TOKENS_PER_STEP {
+                        extended.t += 1;
+                        extended.emitted_at_t = 0;
+                    }
+                    candidates.push(extended);
+                }
+            }
+
+            let candidates = merge_beam_hypotheses(candidates);
+            let (done, active): (Vec<_>, Vec<_>) =
+                candidates.into_iter().partition(|h| h.t >= encodings_len);
+            finished.extend(done);
+            finished = merge_beam_hypotheses(finished);
+            finished.truncate(beam_size.max(n_best));
+
+            beam = active;
+            beam.truncate(beam_size);
+
+            // Stop early once no active hypothesis can beat the finished ones
+            if finished.len() >= beam_size {
+                let worst_finished = finished.last().map(|h| h.score).unwrap_or(f32::MIN);
+                beam.retain(|h| h.score > worst_finished);
+            }
+        }
+
+        log::trace!(
+            "Beam search kept {} hypotheses, best score {:?}",
+            finished.len(),
+            finished.first().map(|h| h.score)
+        );
+
+        Ok(finished
+            .into_iter()
+            .take(n_best)
+            .map(|h| DecodedTokens {
+                tokens: h.tokens,
+                frames: h.frames,
+                confidences: h.confidences,
+                score: h.score,
+            })
+            .collect())
+    }
+
+    /// Returns the number of frames to skip predicted by the TDT duration head,
+    /// or 0 for plain RNN-T outputs that carry no duration logits.
+    fn duration_step(&self, probs: &ArrayD<f32>) -> usize {
+        let duration_logits = match probs.as_slice() {
+            Some(slice) if slice.len() > self.vocab_size => &slice[self.vocab_size..],
+            _ => return 0,
         };
 
-        let float_timestamps: Vec<f32> = timestamps
+        duration_logits
             .iter()
-            .map(|&t| WINDOW_SIZE * SUBSAMPLING_FACTOR as f32 * t as f32)
-            .collect();
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|(idx, _)| TDT_DURATIONS.get(idx).copied().unwrap_or(idx))
+            .unwrap_or(0)
+    }
 
-        TimestampedResult {
-            text,
-            timestamps: float_timestamps,
-            tokens,
+    /// Returns the vocabulary part of a decoder_joint output.
+    fn vocab_logits<'a>(&self, probs: &'a ArrayD<f32>) -> Result<&'a [f32], ParakeetError> {
+        // For TDT models, split output into vocab logits and duration logits
+        // output[:vocab_size] = vocabulary logits
+        // output[vocab_size:] = duration logits
+        let vocab_logits_slice = probs.as_slice().ok_or_else(|| {
+            ParakeetError::Shape(ndarray::ShapeError::from_kind(
+                ndarray::ErrorKind::IncompatibleShape,
+            ))
+        })?;
+
+        if probs.len() > self.vocab_size {
+            // TDT model - extract only vocabulary logits
+            log::trace!(
+                "TDT model detected: splitting {} logits into vocab({}) + duration",
+                probs.len(),
+                self.vocab_size
+            );
+            Ok(&vocab_logits_slice[..self.vocab_size])
+        } else {
+            // Regular RNN-T model
+            Ok(vocab_logits_slice)
         }
     }
 
     pub fn transcribe_samples(
         &mut self,
         samples: Vec<f32>,
+        decoding: &DecodingOptions,
     ) -> Result<TimestampedResult, ParakeetError> {
         let batch_size = 1;
         let samples_len = samples.len();
@@ -431,7 +659,7 @@ impl ParakeetModel {
         let waveforms_lens = Array1::from_vec(vec![samples_len as i64]).into_dyn();
 
         // Run recognition to get detailed results
-        let results = self.recognize_batch(&waveforms.view(), &waveforms_lens.view())?;
+        let results = self.recognize_batch(&waveforms.view(), &waveforms_lens.view(), decoding)?;
 
         // Extract the first (and only) result
         let timestamped_result = results.into_iter().next().ok_or_else(|| {
@@ -443,30 +671,188 @@ impl ParakeetModel {
 
         Ok(timestamped_result)
     }
+
+    /// Transcribes arbitrarily long audio by running overlapping windows through
+    /// the encoder one at a time, then stitching the decoded tokens back together.
+    /// Only the best hypothesis of each window is kept, so no alternatives are returned.
+    pub fn transcribe_samples_chunked(
+        &mut self,
+        samples: Vec<f32>,
+        chunking: &ChunkingParams,
+        decoding: &DecodingOptions,
+    ) -> Result<TimestampedResult, ParakeetError> {
+        let chunked = decode_chunked(&samples, chunking, decoding, |window| {
+            self.decode_window(window, decoding)
+        })?;
+        match chunked {
+            Some(decoded) => Ok(decode_tokens(&self.vocab, decoded)),
+            None => self.transcribe_samples(samples, decoding),
+        }
+    }
+
+    /// Best hypothesis of a single window.
+    fn decode_window(
+        &mut self,
+        window: &[f32],
+        decoding: &DecodingOptions,
+    ) -> Result<DecodedTokens, ParakeetError> {
+        let waveforms = Array2::from_shape_vec((1, window.len()), window.to_vec())?.into_dyn();
+        let waveforms_lens = Array1::from_vec(vec![window.len() as i64]).into_dyn();
+
+        self.decode_batch(&waveforms.view(), &waveforms_lens.view(), decoding)?
+            .into_iter()
+            .next()
+            .and_then(|hypotheses| hypotheses.into_iter().next())
+            .ok_or_else(|| {
+                ParakeetError::Io(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    "No transcription result returned",
+                ))
+            })
+    }
+}
+
+/// Splits `samples` into overlapping windows, decodes them one at a time with
+/// `decode_window` and stitches the tokens back together. The cancel flag is
+/// checked before each window. Returns `None` when the audio fits in one window,
+/// so the caller can decode it whole.
+pub(super) fn decode_chunked(
+    samples: &[f32],
+    chunking: &ChunkingParams,
+    decoding: &DecodingOptions,
+    mut decode_window: impl FnMut(&[f32]) -> Result<DecodedTokens, ParakeetError>,
+) -> Result<Option<DecodedTokens>, ParakeetError> {
+    let frame_len = samples_per_frame();
+    let frame_secs = WINDOW_SIZE * SUBSAMPLING_FACTOR as f32;
+    let window_frames = ((chunking.window_secs / frame_secs).round() as usize).max(2);
+    let overlap_frames =
+        ((chunking.overlap_secs.max(0.0) / frame_secs).round() as usize).min(window_frames / 2);
+    let window_len = window_frames * frame_len;
+    let hop_len = (window_frames - overlap_frames) * frame_len;
+
+    if samples.len() <= window_len {
+        return Ok(None);
+    }
+
+    let window_count = (samples.len() - overlap_frames * frame_len).div_ceil(hop_len);
+    let mut chunks = Vec::with_capacity(window_count);
+    let mut start = 0;
+
+    loop {
+        decoding.check_cancelled()?;
+        let end = (start + window_len).min(samples.len());
+        log::debug!(
+            "Transcribing window {}/{} ({:.1}s - {:.1}s)",
+            chunks.len() + 1,
+            window_count,
+            start as f32 / SAMPLE_RATE as f32,
+            end as f32 / SAMPLE_RATE as f32
+        );
+
+        let best = decode_window(&samples[start..end])?;
+        let start_frame = start / frame_len;
+        chunks.push(ChunkDecoding {
+            start_frame,
+            end_frame: start_frame + (end - start).div_ceil(frame_len),
+            decoded: DecodedTokens {
+                frames: best.frames.iter().map(|f| f + start_frame).collect(),
+                ..best
+            },
+        });
+
+        if end == samples.len() {
+            break;
+        }
+        start += hop_len;
+    }
+
+    Ok(Some(merge_chunk_decodings(&chunks)))
+}
+
+/// Merges hypotheses with the same tokens at the same frame, keeping the best score,
+/// and sorts the result best first.
+fn merge_beam_hypotheses(hypotheses: Vec<BeamHypothesis>) -> Vec<BeamHypothesis> {
+    let mut merged: Vec<BeamHypothesis> = Vec::with_capacity(hypotheses.len());
+    for hypothesis in hypotheses {
+        match merged
+            .iter_mut()
+            .find(|h| h.t == hypothesis.t && h.tokens == hypothesis.tokens)
+        {
+            Some(existing) => {
+                if hypothesis.score > existing.score {
+                    *existing = hypothesis;
+                }
+            }
+            None => merged.push(hypothesis),
+        }
+    }
+    merged.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    merged
+}
+
+/// Turns decoded token ids into text and token timestamps using the model vocabulary.
+pub(super) fn decode_tokens(vocab: &[String], decoded: DecodedTokens) -> TimestampedResult {
+    let (tokens, confidences): (Vec<String>, Vec<f32>) = decoded
+        .tokens
+        .iter()
+        .zip(decoded.confidences.iter())
+        .filter_map(|(&id, &confidence)| {
+            let idx = id as usize;
+            if idx < vocab.len() {
+                Some((vocab[idx].clone(), confidence))
+            } else {
+                None
+            }
+        })
+        .unzip();
+
+    let text = tokens_to_text(&tokens);
+
+    let float_timestamps: Vec<f32> = decoded
+        .frames
+        .iter()
+        .map(|&t| WINDOW_SIZE * SUBSAMPLING_FACTOR as f32 * t as f32)
+        .collect();
+
+    TimestampedResult {
+        text,
+        timestamps: float_timestamps,
+        tokens,
+        confidences,
+        alternatives: Vec::new(),
+    }
+}
+
+/// Number of waveform samples covered by a single encoder frame.
+fn samples_per_frame() -> usize {
+    (SAMPLE_RATE as f32 * WINDOW_SIZE).round() as usize * SUBSAMPLING_FACTOR
 }
 
 // TranscriptionEngine trait implementation
 use super::helpers::convert_timestamps;
 use super::transcription_engine::{TranscriptionEngine, TranscriptionResult};
-use super::types::{
-    ParakeetEngine, ParakeetInferenceParams, ParakeetModelParams, QuantizationType,
-};
+use super::types::{InferenceParams, ParakeetEngine};
 use std::path::Path as StdPath;
 
 impl TranscriptionEngine for ParakeetEngine {
-    type InferenceParams = ParakeetInferenceParams;
-    type ModelParams = ParakeetModelParams;
+    fn id(&self) -> &'static str {
+        "parakeet"
+    }
+
+    fn loaded_model_path(&self) -> Option<&StdPath> {
+        self.loaded_model_path.as_deref()
+    }
 
     fn load_model_with_params(
         &mut self,
         model_path: &StdPath,
-        params: Self::ModelParams,
+        params: ModelParams,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        let quantized = match params.quantization {
-            QuantizationType::FP32 => false,
-            QuantizationType::Int8 => true,
-        };
-        let model = ParakeetModel::new(model_path, quantized)?;
+        let model = ParakeetModel::new(model_path, &params)?;
 
         self.model = Some(model);
         self.loaded_model_path = Some(model_path.to_path_buf());
@@ -476,7 +862,7 @@ impl TranscriptionEngine for ParakeetEngine {
     fn transcribe_samples(
         &mut self,
         samples: Vec<f32>,
-        params: Option<Self::InferenceParams>,
+        params: Option<InferenceParams>,
     ) -> Result<TranscriptionResult, Box<dyn std::error::Error>> {
         let model: &mut ParakeetModel = self
             .model
@@ -486,7 +872,11 @@ impl TranscriptionEngine for ParakeetEngine {
         let parakeet_params = params.unwrap_or_default();
 
         // Get the timestamped result from the model
-        let timestamped_result = model.transcribe_samples(samples)?;
+        let decoding = &parakeet_params.decoding;
+        let timestamped_result = match &parakeet_params.chunking {
+            Some(chunking) => model.transcribe_samples_chunked(samples, chunking, decoding)?,
+            None => model.transcribe_samples(samples, decoding)?,
+        };
 
         // Convert timestamps based on requested granularity
         let segments =
@@ -495,6 +885,97 @@ impl TranscriptionEngine for ParakeetEngine {
         Ok(TranscriptionResult {
             text: timestamped_result.text,
             segments,
+            alternatives: timestamped_result.alternatives,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::helpers::{benchmark_fixtures, read_fixture};
+    use crate::audio::resample::resample;
+    use crate::audio::types::ResampleQuality;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::time::Instant;
+
+    fn short_chunking() -> ChunkingParams {
+        ChunkingParams {
+            window_secs: 1.0,
+            overlap_secs: 0.2,
+        }
+    }
+
+    #[test]
+    fn test_decode_chunked_leaves_short_audio_whole() {
+        let samples = vec![0.0; SAMPLE_RATE / 2];
+        let decoded = decode_chunked(
+            &samples,
+            &short_chunking(),
+            &DecodingOptions::default(),
+            |_| panic!("short audio is decoded by the caller"),
+        )
+        .unwrap();
+        assert!(decoded.is_none());
+    }
+
+    #[test]
+    fn test_decode_chunked_stops_between_windows_when_cancelled() {
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let decoding = DecodingOptions {
+            cancelled: Some(cancelled.clone()),
+            ..Default::default()
+        };
+        let samples = vec![0.0; SAMPLE_RATE * 5];
+
+        let mut windows = 0;
+        let result = decode_chunked(&samples, &short_chunking(), &decoding, |_| {
+            windows += 1;
+            cancelled.store(true, Ordering::Relaxed);
+            Ok(DecodedTokens::default())
+        });
+        assert!(matches!(result, Err(ParakeetError::Cancelled)));
+        assert_eq!(windows, 1);
+    }
+
+    /// Compares frame-by-frame decoding with TDT duration skipping on real speech.
+    ///
+    /// Needs the model and the WAV recordings described in `fixtures/README.md`, so it
+    /// is ignored by default: `cargo test --release bench_tdt_durations -- --ignored --nocapture`
+    #[test]
+    #[ignore]
+    fn bench_tdt_durations() {
+        let Some((model_dir, wavs)) = benchmark_fixtures() else {
+            return;
+        };
+
+        let mut model =
+            ParakeetModel::new(&model_dir, &ModelParams::int8()).expect("Failed to load model");
+
+        for wav in wavs {
+            let (samples, sample_rate) = read_fixture(&wav);
+            let samples = resample(&samples, sample_rate, SAMPLE_RATE, ResampleQuality::Best);
+            for use_tdt_durations in [false, true] {
+                let decoding = DecodingOptions {
+                    use_tdt_durations,
+                    ..Default::default()
+                };
+                let calls_before = model.decoder_joint_calls;
+                let started = Instant::now();
+                let result = model
+                    .transcribe_samples(samples.clone(), &decoding)
+                    .expect("Transcription failed");
+
+                println!(
+                    "{} | durations={} | {} decoder_joint calls | {:?} | {}",
+                    wav.display(),
+                    use_tdt_durations,
+                    model.decoder_joint_calls - calls_before,
+                    started.elapsed(),
+                    result.text
+                );
+            }
+        }
+    }
+}
@@ -1,5 +1,29 @@
 use super::transcription_engine::TranscriptionSegment;
-use super::types::{Segment, TimestampGranularity, TimestampedResult, Token, Utterance, Word};
+use super::types::{
+    ChunkDecoding, DecodedTokens, Segment, TimestampGranularity, TimestampedResult, Token,
+    Utterance, Word,
+};
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+static DECODE_SPACE_RE: Lazy<Result<Regex, regex::Error>> =
+    Lazy::new(|| Regex::new(r"\A\s|\s\B|(\s)\b"));
+
+/// Joins vocabulary pieces into text, keeping only the spaces that start a word.
+pub fn tokens_to_text(tokens: &[String]) -> String {
+    match &*DECODE_SPACE_RE {
+        Ok(regex) => regex
+            .replace_all(&tokens.join(""), |caps: &regex::Captures| {
+                if caps.get(1).is_some() {
+                    " "
+                } else {
+                    ""
+                }
+            })
+            .to_string(),
+        Err(_) => tokens.join(""), // Fallback if regex failed to compile
+    }
+}
 
 pub fn convert_timestamps(
     timestamped_result: &TimestampedResult,
@@ -37,6 +61,7 @@ fn convert_to_raw_token_segments(
             start: timestamp,
             end: end_timestamp,
             text: token.clone(), // Raw token text, including spaces and subword pieces
+            confidence: token_confidence(timestamped_result, i),
         });
     }
 
@@ -86,6 +111,7 @@ fn build_utterance_from_tokens(
                     t_start: 0.0,
                     t_end: 0.0,
                     words: Vec::new(),
+                    confidence: 0.0,
                 }]
             },
         };
@@ -127,12 +153,22 @@ fn create_tokens_from_timestamped_result(timestamped_result: &TimestampedResult)
             t_start: timestamp,
             t_end,
             is_blank: token_text.trim().is_empty(),
+            confidence: token_confidence(timestamped_result, i),
         });
     }
 
     tokens
 }
 
+// Results built without confidences are treated as fully confident
+fn token_confidence(timestamped_result: &TimestampedResult, index: usize) -> f32 {
+    timestamped_result
+        .confidences
+        .get(index)
+        .copied()
+        .unwrap_or(1.0)
+}
+
 fn group_tokens_into_words_hierarchical(tokens: &[Token], word_separator: char) -> Vec<Word> {
     let mut words = Vec::new();
     let mut current_word_tokens = Vec::new();
@@ -178,6 +214,7 @@ fn create_word_from_tokens(tokens: &[Token]) -> Word {
             t_start: 0.0,
             t_end: 0.0,
             tokens: Vec::new(),
+            confidence: 0.0,
         };
     }
 
@@ -202,11 +239,18 @@ fn create_word_from_tokens(tokens: &[Token]) -> Word {
         .trim()
         .to_string();
 
+    // A word is only as reliable as its least certain piece
+    let confidence = tokens
+        .iter()
+        .map(|t| t.confidence)
+        .fold(f32::INFINITY, f32::min);
+
     Word {
         text,
         t_start,
         t_end,
         tokens: tokens.to_vec(),
+        confidence,
     }
 }
 
@@ -252,6 +296,7 @@ fn create_segment_from_words(words: &[Word]) -> Segment {
             t_start: 0.0,
             t_end: 0.0,
             words: Vec::new(),
+            confidence: 0.0,
         };
     }
 
@@ -267,11 +312,14 @@ fn create_segment_from_words(words: &[Word]) -> Segment {
         .collect::<Vec<_>>()
         .join(" ");
 
+    let confidence = words.iter().map(|w| w.confidence).sum::<f32>() / words.len() as f32;
+
     Segment {
         text,
         t_start,
         t_end,
         words: words.to_vec(),
+        confidence,
     }
 }
 
@@ -285,6 +333,7 @@ fn extract_word_segments(utterance: &Utterance) -> Vec<TranscriptionSegment> {
                     start: word.t_start,
                     end: word.t_end,
                     text: word.text.clone(),
+                    confidence: word.confidence,
                 });
             }
         }
@@ -302,6 +351,182 @@ fn extract_segment_segments(utterance: &Utterance) -> Vec<TranscriptionSegment>
             start: segment.t_start,
             end: segment.t_end,
             text: segment.text.clone(),
+            confidence: segment.confidence,
         })
         .collect()
 }
+
+/// Converts raw logits into log-probabilities.
+pub fn log_softmax(logits: &[f32]) -> Vec<f32> {
+    let max = logits.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    let log_sum_exp = logits.iter().map(|&l| (l - max).exp()).sum::<f32>().ln() + max;
+    logits.iter().map(|&l| l - log_sum_exp).collect()
+}
+
+/// Returns the indices of the `k` highest values, highest first.
+pub fn top_k_indices(values: &[f32], k: usize) -> Vec<usize> {
+    let mut indices: Vec<usize> = (0..values.len()).collect();
+    indices.sort_unstable_by(|&a, &b| {
+        values[b]
+            .partial_cmp(&values[a])
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    indices.truncate(k);
+    indices
+}
+
+/// Merges the decodings of overlapping windows into a single token sequence.
+///
+/// Two consecutive windows share an overlap region; the seam is placed in the
+/// middle of that region so each side keeps the tokens it saw with the most context.
+/// A token repeated on both sides of a seam (same id, adjacent frames) is kept once.
+pub fn merge_chunk_decodings(chunks: &[ChunkDecoding]) -> DecodedTokens {
+    let mut merged = DecodedTokens::default();
+
+    for (i, chunk) in chunks.iter().enumerate() {
+        let left_seam = match i {
+            0 => 0,
+            _ => seam_frame(&chunks[i - 1], chunk),
+        };
+        let right_seam = match chunks.get(i + 1) {
+            Some(next) => seam_frame(chunk, next),
+            None => usize::MAX,
+        };
+
+        merged.score += chunk.decoded.score;
+
+        let decoded = &chunk.decoded;
+        let mut is_first_of_chunk = true;
+        for ((&token, &frame), &confidence) in decoded
+            .tokens
+            .iter()
+            .zip(decoded.frames.iter())
+            .zip(decoded.confidences.iter())
+        {
+            if frame < left_seam || frame >= right_seam {
+                continue;
+            }
+
+            if is_first_of_chunk {
+                is_first_of_chunk = false;
+                let duplicates_previous = match (merged.tokens.last(), merged.frames.last()) {
+                    (Some(&prev_token), Some(&prev_frame)) => {
+                        prev_token == token && frame.saturating_sub(prev_frame) <= 1
+                    }
+                    _ => false,
+                };
+                if duplicates_previous {
+                    continue;
+                }
+            }
+
+            merged.tokens.push(token);
+            merged.frames.push(frame);
+            merged.confidences.push(confidence);
+        }
+    }
+
+    merged
+}
+
+fn seam_frame(left: &ChunkDecoding, right: &ChunkDecoding) -> usize {
+    if left.end_frame <= right.start_frame {
+        return right.start_frame;
+    }
+    right.start_frame + (left.end_frame - right.start_frame) / 2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(start: usize, end: usize, tokens: &[(i32, usize)]) -> ChunkDecoding {
+        ChunkDecoding {
+            start_frame: start,
+            end_frame: end,
+            decoded: DecodedTokens {
+                tokens: tokens.iter().map(|(t, _)| *t).collect(),
+                frames: tokens.iter().map(|(_, f)| *f).collect(),
+                confidences: vec![1.0; tokens.len()],
+                score: 0.0,
+            },
+        }
+    }
+
+    #[test]
+    fn test_log_softmax_sums_to_one() {
+        let log_probs = log_softmax(&[1.0, 2.0, 3.0, 1000.0]);
+        let total: f32 = log_probs.iter().map(|lp| lp.exp()).sum();
+        assert!((total - 1.0).abs() < 1e-5);
+        assert!(log_probs.iter().all(|lp| lp.is_finite()));
+    }
+
+    #[test]
+    fn test_top_k_indices_orders_by_value() {
+        assert_eq!(top_k_indices(&[0.1, 0.7, 0.3, 0.9], 2), vec![3, 1]);
+        assert_eq!(top_k_indices(&[0.5], 3), vec![0]);
+    }
+
+    #[test]
+    fn test_merge_chunk_decodings_single_chunk_is_unchanged() {
+        let chunks = vec![chunk(0, 100, &[(1, 3), (2, 40), (3, 99)])];
+        let merged = merge_chunk_decodings(&chunks);
+        assert_eq!(merged.tokens, vec![1, 2, 3]);
+        assert_eq!(merged.frames, vec![3, 40, 99]);
+    }
+
+    #[test]
+    fn test_merge_chunk_decodings_splits_overlap_at_midpoint() {
+        // Overlap is frames 80..100, seam at 90
+        let chunks = vec![
+            chunk(0, 100, &[(1, 10), (2, 85), (3, 95)]),
+            chunk(80, 180, &[(2, 84), (3, 94), (4, 150)]),
+        ];
+        let merged = merge_chunk_decodings(&chunks);
+        assert_eq!(merged.tokens, vec![1, 2, 3, 4]);
+        assert_eq!(merged.frames, vec![10, 85, 94, 150]);
+    }
+
+    #[test]
+    fn test_merge_chunk_decodings_drops_token_repeated_across_seam() {
+        let chunks = vec![
+            chunk(0, 100, &[(1, 10), (7, 89)]),
+            chunk(80, 180, &[(7, 90), (4, 150)]),
+        ];
+        let merged = merge_chunk_decodings(&chunks);
+        assert_eq!(merged.tokens, vec![1, 7, 4]);
+        assert_eq!(merged.frames, vec![10, 89, 150]);
+    }
+
+    #[test]
+    fn test_merge_chunk_decodings_keeps_distant_repeated_token() {
+        let chunks = vec![chunk(0, 100, &[(7, 40)]), chunk(80, 180, &[(7, 120)])];
+        let merged = merge_chunk_decodings(&chunks);
+        assert_eq!(merged.tokens, vec![7, 7]);
+    }
+
+    #[test]
+    fn test_word_confidence_is_lowest_token_confidence() {
+        let result = TimestampedResult {
+            text: "hello world".to_string(),
+            timestamps: vec![0.0, 0.1, 0.2, 0.3],
+            tokens: vec![
+                " hel".to_string(),
+                "lo".to_string(),
+                " wor".to_string(),
+                "ld".to_string(),
+            ],
+            confidences: vec![0.9, 0.4, 0.8, 0.6],
+            alternatives: Vec::new(),
+        };
+
+        let words = convert_timestamps(&result, TimestampGranularity::Word);
+        assert_eq!(words.len(), 2);
+        assert!((words[0].confidence - 0.4).abs() < 1e-6);
+        assert!((words[1].confidence - 0.6).abs() < 1e-6);
+
+        let segments = convert_timestamps(&result, TimestampGranularity::Segment);
+        assert_eq!(segments.len(), 1);
+        assert!((segments[0].confidence - 0.5).abs() < 1e-6);
+    }
+}
@@ -1,5 +1,9 @@
+pub mod benchmark;
+pub mod biasing;
+pub mod ctc;
 pub mod engine;
 pub mod helpers;
+pub mod registry;
 pub mod transcription_engine;
 pub mod types;
 
@@ -0,0 +1,39 @@
+use super::transcription_engine::TranscriptionEngine;
+use super::types::{CtcEngine, EngineInfo, ParakeetEngine, RegisteredEngine};
+
+pub const DEFAULT_ENGINE_ID: &str = "parakeet";
+
+const ENGINES: &[RegisteredEngine] = &[
+    RegisteredEngine {
+        id: "parakeet",
+        label: "Parakeet TDT (transducer)",
+        create: || Box::new(ParakeetEngine::new()),
+    },
+    RegisteredEngine {
+        id: "ctc",
+        label: "NeMo CTC",
+        create: || Box::new(CtcEngine::new()),
+    },
+];
+
+/// Creates an engine without a loaded model, or `None` for an unknown id.
+pub fn create_engine(id: &str) -> Option<Box<dyn TranscriptionEngine>> {
+    ENGINES
+        .iter()
+        .find(|engine| engine.id == id)
+        .map(|engine| (engine.create)())
+}
+
+pub fn available_engines() -> Vec<EngineInfo> {
+    ENGINES
+        .iter()
+        .map(|engine| EngineInfo {
+            id: engine.id.to_string(),
+            label: engine.label.to_string(),
+        })
+        .collect()
+}
+
+pub fn is_registered(id: &str) -> bool {
+    ENGINES.iter().any(|engine| engine.id == id)
+}
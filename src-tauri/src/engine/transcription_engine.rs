@@ -1,3 +1,4 @@
+use super::types::{InferenceParams, ModelParams};
 use std::path::Path;
 
 #[derive(Debug)]
@@ -5,29 +6,56 @@ use std::path::Path;
 pub struct TranscriptionResult {
     pub text: String,
     pub segments: Vec<TranscriptionSegment>,
+    pub alternatives: Vec<TranscriptionAlternative>,
 }
 
-#[derive(Debug)]
+impl TranscriptionResult {
+    /// Returns the text of the segments the model is less confident about than `threshold`.
+    pub fn uncertain_words(&self, threshold: f32) -> Vec<String> {
+        self.segments
+            .iter()
+            .filter(|segment| segment.confidence < threshold)
+            .map(|segment| segment.text.clone())
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TranscriptionAlternative {
+    pub text: String,
+    /// Log-probability of the hypothesis, higher is better.
+    pub score: f32,
+}
+
+#[derive(Debug, Clone)]
 #[allow(dead_code)]
 pub struct TranscriptionSegment {
     pub start: f32,
     pub end: f32,
     pub text: String,
+    /// Model confidence between 0 and 1.
+    pub confidence: f32,
 }
 
-pub trait TranscriptionEngine {
-    type InferenceParams;
-    type ModelParams: Default;
+/// A speech recognition backend, used through `Box<dyn TranscriptionEngine>`.
+///
+/// Engines ignore the inference options they do not support, e.g. beam search
+/// or contextual biasing for CTC models.
+pub trait TranscriptionEngine: Send {
+    /// Identifier of the engine in the registry and in the settings.
+    fn id(&self) -> &'static str;
+
+    fn loaded_model_path(&self) -> Option<&Path>;
 
     fn load_model_with_params(
         &mut self,
         model_path: &Path,
-        params: Self::ModelParams,
+        params: ModelParams,
     ) -> Result<(), Box<dyn std::error::Error>>;
 
     fn transcribe_samples(
         &mut self,
         samples: Vec<f32>,
-        params: Option<Self::InferenceParams>,
+        params: Option<InferenceParams>,
     ) -> Result<TranscriptionResult, Box<dyn std::error::Error>>;
 }
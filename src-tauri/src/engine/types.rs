@@ -1,14 +1,48 @@
+use super::transcription_engine::{TranscriptionAlternative, TranscriptionEngine};
 use ndarray::Array3;
 use ort::session::Session;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
 pub type DecoderState = (Array3<f32>, Array3<f32>);
 
+/// Token ids decoded from one waveform, with the encoder frame each one was emitted at.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DecodedTokens {
+    pub tokens: Vec<i32>,
+    pub frames: Vec<usize>,
+    /// Softmax probability of each emitted token, between 0 and 1.
+    pub confidences: Vec<f32>,
+    /// Sum of the log-probabilities of every decoding step.
+    pub score: f32,
+}
+
+/// A partial hypothesis tracked by the beam search decoder.
+#[derive(Debug, Clone)]
+pub struct BeamHypothesis {
+    pub tokens: Vec<i32>,
+    pub frames: Vec<usize>,
+    pub confidences: Vec<f32>,
+    pub state: DecoderState,
+    pub hotword_state: HotwordState,
+    pub score: f32,
+    /// Encoder frame this hypothesis is currently decoding.
+    pub t: usize,
+    /// Tokens already emitted at frame `t`.
+    pub emitted_at_t: usize,
+}
+
 #[derive(Debug, Clone)]
 pub struct TimestampedResult {
     pub text: String,
     pub timestamps: Vec<f32>,
     pub tokens: Vec<String>,
+    /// Softmax probability of each token, aligned with `tokens`.
+    pub confidences: Vec<f32>,
+    /// Runner-up hypotheses, best first. Only filled by beam search.
+    pub alternatives: Vec<TranscriptionAlternative>,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -25,6 +59,8 @@ pub enum ParakeetError {
     OutputNotFound(String),
     #[error("Failed to get tensor shape for input: {0}")]
     TensorShape(String),
+    #[error("Transcription cancelled")]
+    Cancelled,
 }
 
 pub struct ParakeetModel {
@@ -34,6 +70,9 @@ pub struct ParakeetModel {
     pub vocab: Vec<String>,
     pub blank_idx: i32,
     pub vocab_size: usize,
+    /// Number of decoder_joint runs since the model was loaded, for benchmarks.
+    #[cfg(test)]
+    pub decoder_joint_calls: usize,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -43,6 +82,7 @@ pub struct Token {
     pub t_start: f32,
     pub t_end: f32,
     pub is_blank: bool,
+    pub confidence: f32,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -51,6 +91,8 @@ pub struct Word {
     pub t_start: f32,
     pub t_end: f32,
     pub tokens: Vec<Token>,
+    /// Confidence of the least certain token of the word.
+    pub confidence: f32,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -59,6 +101,8 @@ pub struct Segment {
     pub t_start: f32,
     pub t_end: f32,
     pub words: Vec<Word>,
+    /// Average confidence of the words of the segment.
+    pub confidence: f32,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -74,47 +118,210 @@ pub enum TimestampGranularity {
     Segment,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum QuantizationType {
+    #[default]
     FP32,
     Int8,
 }
-/// Parameters for configuring Parakeet model loading.
-#[derive(Debug, Clone)]
-pub struct ParakeetModelParams {
-    pub quantization: QuantizationType,
+
+/// ONNX Runtime graph optimizations applied when a session is created.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OptimizationLevel {
+    Disable,
+    Basic,
+    Extended,
+    All,
 }
 
-impl Default for ParakeetModelParams {
+/// ONNX Runtime options shared by every session of a model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionOptions {
+    /// Threads used inside an operator, `None` lets ONNX Runtime pick one per core.
+    pub intra_threads: Option<usize>,
+    /// Threads running independent operators in parallel, sequential when `None` or 1.
+    pub inter_threads: Option<usize>,
+    pub optimization_level: OptimizationLevel,
+}
+
+impl Default for SessionOptions {
     fn default() -> Self {
         Self {
-            quantization: QuantizationType::FP32,
+            intra_threads: None,
+            inter_threads: None,
+            optimization_level: OptimizationLevel::All,
         }
     }
 }
 
-impl ParakeetModelParams {
+/// Parameters for configuring Parakeet model loading.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModelParams {
+    pub quantization: QuantizationType,
+    pub session: SessionOptions,
+}
+
+impl ModelParams {
     pub fn int8() -> Self {
         Self {
             quantization: QuantizationType::Int8,
+            ..Default::default()
+        }
+    }
+}
+
+/// Parameters for splitting long audio into overlapping windows.
+///
+/// Each window goes through the preprocessor and encoder on its own, so memory
+/// usage stays bounded by the window length instead of the recording length.
+#[derive(Debug, Clone)]
+pub struct ChunkingParams {
+    /// Length of a single window, in seconds.
+    pub window_secs: f32,
+    /// Audio shared by two consecutive windows, in seconds.
+    pub overlap_secs: f32,
+}
+
+impl Default for ChunkingParams {
+    fn default() -> Self {
+        Self {
+            window_secs: 60.0,
+            overlap_secs: 4.0,
+        }
+    }
+}
+
+/// Tokens decoded from one window of a chunked transcription.
+/// Frame indices are absolute, i.e. relative to the start of the whole recording.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChunkDecoding {
+    pub start_frame: usize,
+    pub end_frame: usize,
+    pub decoded: DecodedTokens,
+}
+
+/// Strategy used to turn decoder outputs into tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodingStrategy {
+    /// Takes the most likely token at every step.
+    Greedy,
+    /// Keeps the `beam_size` most likely hypotheses, each with its own decoder state.
+    /// `n_best` is the number of ranked hypotheses returned, the best one included.
+    BeamSearch { beam_size: usize, n_best: usize },
+}
+
+/// Token sequences of the hotwords to boost, stored as a prefix tree.
+///
+/// Used for shallow fusion: while a hypothesis follows a path of the tree, each
+/// token earns `weight` on top of its log-probability. If the path is left before
+/// a hotword is complete, the bonus earned so far is taken back.
+#[derive(Debug, Clone)]
+pub struct HotwordTrie {
+    pub(super) nodes: Vec<HotwordNode>,
+    pub(super) weight: f32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(super) struct HotwordNode {
+    pub(super) children: HashMap<i32, usize>,
+    pub(super) is_end: bool,
+}
+
+/// Position of a hypothesis in the hotword tree.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HotwordState {
+    pub(super) node: usize,
+    /// Tokens matched since the last completed hotword.
+    pub(super) matched: usize,
+}
+
+/// Words to favor while decoding, e.g. names from the custom dictionary.
+#[derive(Debug, Clone)]
+pub struct ContextBiasing {
+    pub words: Vec<String>,
+    /// Bonus added to the log-probability of each token that matches a word.
+    pub weight: f32,
+}
+
+/// Options that control the decoding loop.
+#[derive(Debug, Clone)]
+pub struct DecodingOptions {
+    pub strategy: DecodingStrategy,
+    /// Jump ahead by the duration predicted by TDT models instead of
+    /// advancing one encoder frame at a time. Ignored for plain RNN-T models.
+    pub use_tdt_durations: bool,
+    pub biasing: Option<ContextBiasing>,
+    /// Checked between encoder windows and decoding steps, decoding stops with
+    /// `ParakeetError::Cancelled` once it is set.
+    pub cancelled: Option<Arc<AtomicBool>>,
+}
+
+impl Default for DecodingOptions {
+    fn default() -> Self {
+        Self {
+            strategy: DecodingStrategy::Greedy,
+            use_tdt_durations: true,
+            biasing: None,
+            cancelled: None,
+        }
+    }
+}
+
+impl DecodingOptions {
+    pub fn check_cancelled(&self) -> Result<(), ParakeetError> {
+        match &self.cancelled {
+            Some(cancelled) if cancelled.load(Ordering::Relaxed) => Err(ParakeetError::Cancelled),
+            _ => Ok(()),
         }
     }
 }
 
 /// Parameters for configuring Parakeet inference behavior.
 #[derive(Debug, Clone)]
-pub struct ParakeetInferenceParams {
+pub struct InferenceParams {
     pub timestamp_granularity: TimestampGranularity,
+    /// Audio longer than one window is transcribed in overlapping chunks.
+    /// `None` always runs the whole waveform in a single pass.
+    pub chunking: Option<ChunkingParams>,
+    pub decoding: DecodingOptions,
 }
 
-impl Default for ParakeetInferenceParams {
+impl Default for InferenceParams {
     fn default() -> Self {
         Self {
             timestamp_granularity: TimestampGranularity::Token,
+            chunking: Some(ChunkingParams::default()),
+            decoding: DecodingOptions::default(),
         }
     }
 }
 
+/// A transcription engine available in the registry.
+#[derive(Debug, Clone, Serialize)]
+pub struct EngineInfo {
+    pub id: String,
+    pub label: String,
+}
+
+/// Registry entry; `create` builds the engine without a loaded model.
+pub struct RegisteredEngine {
+    pub id: &'static str,
+    pub label: &'static str,
+    pub create: fn() -> Box<dyn TranscriptionEngine>,
+}
+
+/// Speed of one model configuration measured by `benchmark::run_benchmark`.
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchmarkResult {
+    pub params: ModelParams,
+    pub load_seconds: f32,
+    /// Processing time divided by audio duration, below 1 is faster than real time.
+    pub real_time_factor: Option<f32>,
+    pub error: Option<String>,
+}
+
 /// Parakeet speech recognition engine wrapper.
 pub struct ParakeetEngine {
     pub model: Option<ParakeetModel>,
@@ -129,3 +336,27 @@ impl ParakeetEngine {
         }
     }
 }
+
+/// NeMo CTC model exported to ONNX: `model.onnx` (or `model.int8.onnx`),
+/// the `nemo128.onnx` preprocessor and `vocab.txt`.
+pub struct CtcModel {
+    pub model: Session,
+    pub preprocessor: Session,
+    pub vocab: Vec<String>,
+    pub blank_idx: i32,
+}
+
+/// CTC speech recognition engine wrapper.
+pub struct CtcEngine {
+    pub model: Option<CtcModel>,
+    pub loaded_model_path: Option<std::path::PathBuf>,
+}
+
+impl CtcEngine {
+    pub fn new() -> Self {
+        Self {
+            model: None,
+            loaded_model_path: None,
+        }
+    }
+}
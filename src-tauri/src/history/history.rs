@@ -48,6 +48,9 @@ pub fn purge_history_file(app: &AppHandle) -> Result<()> {
     let path = get_history_file_path(app)?;
     match path.exists() {
         true => {
+            if let Ok(data) = read_history(app) {
+                remove_archived_audio(app, &data);
+            }
             let _ = fs::remove_file(path);
         }
         false => {
@@ -57,24 +60,24 @@ pub fn purge_history_file(app: &AppHandle) -> Result<()> {
     Ok(())
 }
 
-pub fn add_transcription(app: &AppHandle, text: String) -> Result<()> {
-    let mut data = if is_persist_enabled(app) {
-        read_history(app)?
-    } else {
-        match memory_data().lock() {
-            Ok(d) => d.clone(),
-            Err(_) => HistoryData::default(),
-        }
-    };
+/// Adds a transcription at the top of the history and returns its id.
+pub fn add_transcription(app: &AppHandle, text: String, audio_file: Option<String>) -> Result<u64> {
+    let mut data = load_history(app)?;
 
     let timestamp = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)?
         .as_secs() as i64;
 
+    let id = data.next_id;
+    // The archived audio outlives the entry, only the retention policy removes it
+    if let Some(file_name) = &audio_file {
+        data.archived_files.push(file_name.clone());
+    }
     let entry = HistoryEntry {
-        id: data.next_id,
+        id,
         timestamp,
         text,
+        audio_file,
     };
 
     data.entries.insert(0, entry);
@@ -84,16 +87,46 @@ pub fn add_transcription(app: &AppHandle, text: String) -> Result<()> {
         data.entries.truncate(MAX_HISTORY_ENTRIES);
     }
 
-    if is_persist_enabled(app) {
-        write_history(app, &data)?;
-    } else if let Ok(mut guard) = memory_data().lock() {
-        *guard = data.clone();
-    }
+    store_history(app, &data)?;
 
     let _ = app.emit("history-updated", ());
 
     crate::onboarding::onboarding::mark_onboarding_on_history_write(app);
 
+    Ok(id)
+}
+
+/// Replaces the text of entry `id`, keeping its place and audio.
+/// Returns false when the entry is no longer in the history.
+pub fn replace_transcription(app: &AppHandle, id: u64, text: String) -> Result<bool> {
+    let mut data = load_history(app)?;
+    let Some(entry) = data.entries.iter_mut().find(|entry| entry.id == id) else {
+        return Ok(false);
+    };
+    entry.text = text;
+
+    store_history(app, &data)?;
+    let _ = app.emit("history-updated", ());
+    Ok(true)
+}
+
+fn load_history(app: &AppHandle) -> Result<HistoryData> {
+    if is_persist_enabled(app) {
+        read_history(app)
+    } else {
+        match memory_data().lock() {
+            Ok(d) => Ok(d.clone()),
+            Err(_) => Ok(HistoryData::default()),
+        }
+    }
+}
+
+fn store_history(app: &AppHandle, data: &HistoryData) -> Result<()> {
+    if is_persist_enabled(app) {
+        write_history(app, data)?;
+    } else if let Ok(mut guard) = memory_data().lock() {
+        *guard = data.clone();
+    }
     Ok(())
 }
 
@@ -121,15 +154,46 @@ pub fn get_last_transcription(app: &AppHandle) -> Result<String> {
     Ok(data.entries.first().unwrap().text.clone())
 }
 
-/// Clears all transcription history entries and emits an event to notify the frontend.
+/// Clears all transcription history entries and their archived audio, and emits an
+/// event to notify the frontend.
 pub fn clear_history(app: &AppHandle) -> Result<()> {
-    if is_persist_enabled(app) {
-        let mut data = read_history(app)?;
-        data.entries.clear();
-        write_history(app, &data)?;
-    } else if let Ok(mut guard) = memory_data().lock() {
-        guard.entries.clear();
+    let mut data = load_history(app)?;
+    let cleared = HistoryData {
+        entries: std::mem::take(&mut data.entries),
+        archived_files: std::mem::take(&mut data.archived_files),
+        ..HistoryData::default()
+    };
+    store_history(app, &data)?;
+    remove_archived_audio(app, &cleared);
+    let _ = app.emit("history-updated", ());
+    Ok(())
+}
+
+/// Archived files linked to the history, the retention policy deletes all others.
+pub fn archived_files(app: &AppHandle) -> Result<Vec<String>> {
+    Ok(load_history(app)?.archived_files)
+}
+
+/// Unlinks archived files deleted by the retention policy.
+pub fn forget_archived_files(app: &AppHandle, file_names: &[String]) -> Result<()> {
+    if file_names.is_empty() {
+        return Ok(());
     }
+
+    let mut data = load_history(app)?;
+    data.archived_files
+        .retain(|file_name| !file_names.contains(file_name));
+    store_history(app, &data)?;
     let _ = app.emit("history-updated", ());
     Ok(())
 }
+
+fn remove_archived_audio(app: &AppHandle, data: &HistoryData) {
+    let entry_files = data
+        .entries
+        .iter()
+        .filter_map(|entry| entry.audio_file.as_ref());
+    for file_name in data.archived_files.iter().chain(entry_files) {
+        crate::audio::archive::remove_archived_file(app, file_name);
+    }
+}
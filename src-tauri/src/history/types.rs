@@ -5,12 +5,19 @@ pub struct HistoryEntry {
     pub id: u64,
     pub timestamp: i64,
     pub text: String,
+    /// File name in the audio archive, when archiving was enabled for this entry
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audio_file: Option<String>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct HistoryData {
     pub entries: Vec<HistoryEntry>,
     pub next_id: u64,
+    /// Every archived file linked to the history, including those of entries that
+    /// have since dropped out of it
+    #[serde(default)]
+    pub archived_files: Vec<String>,
 }
 
 impl Default for HistoryData {
@@ -18,6 +25,7 @@ impl Default for HistoryData {
         Self {
             entries: Vec::new(),
             next_id: 1,
+            archived_files: Vec::new(),
         }
     }
 }
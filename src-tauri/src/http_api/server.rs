@@ -1,8 +1,9 @@
 use crate::audio;
+use crate::audio::types::DecodeError;
 use crate::dictionary::{fix_transcription_with_dictionary, get_cc_rules_path, Dictionary};
 use anyhow::Result;
 use axum::{
-    extract::{DefaultBodyLimit, Multipart},
+    extract::{DefaultBodyLimit, Multipart, Query},
     http::StatusCode,
     response::IntoResponse,
     routing::post,
@@ -14,9 +15,38 @@ use std::net::SocketAddr;
 use std::sync::Arc;
 use tauri::Manager;
 
+/// Hypotheses above this count cost more decoding time than they are worth.
+const MAX_N_BEST: usize = 10;
+
+#[derive(Deserialize)]
+pub struct TranscribeQuery {
+    /// Number of ranked hypotheses to compute, the returned `text` included.
+    pub n_best: Option<usize>,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct TranscriptionResponse {
     pub text: String,
+    pub words: Vec<WordResponse>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub alternatives: Vec<AlternativeResponse>,
+}
+
+/// A runner-up hypothesis of the raw transcription, best first.
+#[derive(Serialize, Deserialize)]
+pub struct AlternativeResponse {
+    pub text: String,
+    /// Log-probability of the hypothesis, higher is better.
+    pub score: f32,
+}
+
+/// A word of the raw transcription, before dictionary corrections.
+#[derive(Serialize, Deserialize)]
+pub struct WordResponse {
+    pub text: String,
+    pub start: f32,
+    pub end: f32,
+    pub confidence: f32,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -60,12 +90,32 @@ pub async fn start_http_api(
 
 async fn transcribe_handler(
     axum::extract::State(app): axum::extract::State<Arc<tauri::AppHandle>>,
+    Query(query): Query<TranscribeQuery>,
     mut multipart: Multipart,
 ) -> impl IntoResponse {
+    let n_best = query.n_best.unwrap_or(1);
+    if !(1..=MAX_N_BEST).contains(&n_best) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: format!("n_best must be between 1 and {}", MAX_N_BEST),
+            }),
+        )
+            .into_response();
+    }
+
     loop {
         match multipart.next_field().await {
             Ok(Some(field)) => {
                 if field.name() == Some("audio") {
+                    // The extension helps the decoder pick the right container
+                    let extension = field
+                        .file_name()
+                        .and_then(|name| std::path::Path::new(name).extension())
+                        .and_then(|ext| ext.to_str())
+                        .filter(|ext| ext.chars().all(|c| c.is_ascii_alphanumeric()))
+                        .unwrap_or("wav")
+                        .to_string();
                     let bytes = match field.bytes().await {
                         Ok(b) => b,
                         Err(e) => {
@@ -79,8 +129,11 @@ async fn transcribe_handler(
                         }
                     };
 
-                    let temp_path =
-                        std::env::temp_dir().join(format!("murmure-{}.wav", uuid::Uuid::new_v4()));
+                    let temp_path = std::env::temp_dir().join(format!(
+                        "murmure-{}.{}",
+                        uuid::Uuid::new_v4(),
+                        extension
+                    ));
 
                     if let Err(e) = std::fs::write(&temp_path, bytes) {
                         return (
@@ -92,39 +145,81 @@ async fn transcribe_handler(
                             .into_response();
                     }
 
-                    let result = match audio::preload_engine(&app) {
-                        Ok(_) => match audio::transcribe_audio(&app, &temp_path) {
-                            Ok(raw_text) => {
-                                let text = match get_cc_rules_path(&app) {
-                                    Ok(cc_rules_path) => {
-                                        let dictionary = app.state::<Dictionary>().get();
-                                        fix_transcription_with_dictionary(
-                                            raw_text,
-                                            dictionary,
-                                            cc_rules_path,
-                                        )
-                                    }
-                                    Err(_) => raw_text,
-                                };
-
-                                Ok(text)
-                            }
-                            Err(e) => Err(format!("Transcription failed: {}", e)),
-                        },
-                        Err(e) => Err(format!("Model not available: {}", e)),
+                    // Waiting behind dictation in the worker queue must not hold a runtime thread
+                    let transcription = {
+                        let app = app.clone();
+                        let temp_path = temp_path.clone();
+                        tokio::task::spawn_blocking(move || {
+                            audio::transcribe_audio(
+                                &app,
+                                &temp_path,
+                                audio::types::JobPriority::Batch,
+                                n_best,
+                            )
+                        })
+                        .await
+                        .unwrap_or_else(|e| {
+                            Err(anyhow::anyhow!("Transcription task failed: {}", e))
+                        })
+                    };
+                    let result = match transcription {
+                        Ok(transcription) => {
+                            let raw_text = transcription.text;
+                            let words = transcription
+                                .segments
+                                .into_iter()
+                                .map(|segment| WordResponse {
+                                    text: segment.text,
+                                    start: segment.start,
+                                    end: segment.end,
+                                    confidence: segment.confidence,
+                                })
+                                .collect();
+                            let alternatives = transcription
+                                .alternatives
+                                .into_iter()
+                                .map(|alternative| AlternativeResponse {
+                                    text: alternative.text,
+                                    score: alternative.score,
+                                })
+                                .collect();
+
+                            let text = match get_cc_rules_path(&app) {
+                                Ok(cc_rules_path) => {
+                                    let dictionary = app.state::<Dictionary>().get();
+                                    fix_transcription_with_dictionary(
+                                        raw_text,
+                                        dictionary,
+                                        cc_rules_path,
+                                    )
+                                }
+                                Err(_) => raw_text,
+                            };
+
+                            Ok(TranscriptionResponse {
+                                text,
+                                words,
+                                alternatives,
+                            })
+                        }
+                        Err(e) => {
+                            let status = match e.downcast_ref::<DecodeError>() {
+                                Some(decode_error) if decode_error.is_invalid_input() => {
+                                    StatusCode::UNSUPPORTED_MEDIA_TYPE
+                                }
+                                _ => StatusCode::INTERNAL_SERVER_ERROR,
+                            };
+                            Err((status, format!("{:#}", e)))
+                        }
                     };
 
                     let _ = std::fs::remove_file(&temp_path);
 
                     return match result {
-                        Ok(text) => {
-                            (StatusCode::OK, Json(TranscriptionResponse { text })).into_response()
+                        Ok(response) => (StatusCode::OK, Json(response)).into_response(),
+                        Err((status, e)) => {
+                            (status, Json(ErrorResponse { error: e })).into_response()
                         }
-                        Err(e) => (
-                            StatusCode::INTERNAL_SERVER_ERROR,
-                            Json(ErrorResponse { error: e }),
-                        )
-                            .into_response(),
                     };
                 }
             }
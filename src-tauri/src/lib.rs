@@ -19,7 +19,7 @@ mod utils;
 
 use crate::shortcuts::init_shortcuts;
 use audio::preload_engine;
-use audio::types::AudioState;
+use audio::types::{AudioState, TranscriptionWorker};
 use commands::*;
 use dictionary::Dictionary;
 use http_api::HttpApiState;
@@ -87,6 +87,8 @@ pub fn run() {
                 Arc::new(Model::new(app.handle().clone()).expect("Failed to initialize model"));
             app.manage(model);
             app.manage(AudioState::new());
+            app.manage(TranscriptionWorker::new());
+            audio::start_transcription_worker(app.handle());
 
             let mut s = settings::load_settings(app.handle());
 
@@ -121,6 +123,16 @@ pub fn run() {
             audio::sound::init_sound_system(app.handle());
 
             audio::microphone::init_mic_cache_if_needed(app.handle(), s.mic_id.clone());
+            audio::microphone::start_device_watcher(app.handle());
+            audio::preroll::start_preroll_monitor(app.handle());
+
+            // Archived audio may have aged past the retention limits while the app was closed
+            let app_handle = app.handle().clone();
+            std::thread::spawn(move || {
+                if let Err(e) = audio::archive::enforce_retention(&app_handle) {
+                    warn!("Failed to apply archive retention: {:#}", e);
+                }
+            });
 
             if s.api_enabled {
                 let app_handle = app.handle().clone();
@@ -134,19 +146,41 @@ pub fn run() {
                 crate::shortcuts::force_stop_recording(&app_handle);
             });
 
+            let app_handle = app.handle().clone();
+            app.handle().listen("recording-rollover", move |_| {
+                crate::audio::rollover_recording(&app_handle);
+            });
+
+            let app_handle = app.handle().clone();
+            app.handle().listen("recording-silence-detected", move |_| {
+                info!("Silence detected in hands-free mode, stopping...");
+                crate::shortcuts::force_stop_recording(&app_handle);
+            });
+
             Ok(())
         })
-        .on_window_event(|window, event| {
-            if let tauri::WindowEvent::CloseRequested { api, .. } = event {
+        .on_window_event(|window, event| match event {
+            tauri::WindowEvent::CloseRequested { api, .. } => {
                 api.prevent_close();
                 let _ = window.hide();
             }
+            tauri::WindowEvent::Focused(_) if window.label() == "main" => {
+                audio::preroll::refresh_preroll(window.app_handle());
+            }
+            _ => {}
         })
         .invoke_handler(tauri::generate_handler![
             is_model_available,
             get_model_path,
             get_recent_transcriptions,
             clear_history,
+            get_history_audio_path,
+            get_archive_enabled,
+            set_archive_enabled,
+            get_archive_max_age_days,
+            set_archive_max_age_days,
+            get_archive_max_size_mb,
+            set_archive_max_size_mb,
             get_record_shortcut,
             set_record_shortcut,
             set_dictionary,
@@ -163,6 +197,15 @@ pub fn run() {
             set_secondary_record_shortcut,
             get_cancel_recording_shortcut,
             set_cancel_recording_shortcut,
+            get_pause_recording_shortcut,
+            set_pause_recording_shortcut,
+            pause_recording,
+            resume_recording,
+            reprocess_recording,
+            get_reprocess_shortcut,
+            set_reprocess_shortcut,
+            get_retained_recordings,
+            set_retained_recordings,
             get_llm_mode_1_shortcut,
             set_llm_mode_1_shortcut,
             get_llm_mode_2_shortcut,
@@ -216,6 +259,69 @@ pub fn run() {
             validate_regex,
             get_log_level,
             set_log_level,
+            get_beam_size,
+            set_beam_size,
+            get_context_biasing_enabled,
+            set_context_biasing_enabled,
+            get_context_biasing_weight,
+            set_context_biasing_weight,
+            get_available_engines,
+            get_engine,
+            set_engine,
+            get_engine_model_path,
+            set_engine_model_path,
+            get_installed_models,
+            get_active_model,
+            import_model,
+            set_active_model,
+            get_engine_status,
+            unload_engine,
+            get_transcription_queue_depth,
+            cancel_transcription,
+            get_engine_idle_unload_minutes,
+            set_engine_idle_unload_minutes,
+            get_model_precision,
+            set_model_precision,
+            get_onnx_threads,
+            set_onnx_threads,
+            get_onnx_optimization_level,
+            set_onnx_optimization_level,
+            benchmark_engine,
+            get_vad_trim_silence,
+            set_vad_trim_silence,
+            get_hands_free_silence_ms,
+            set_hands_free_silence_ms,
+            get_live_preview_enabled,
+            set_live_preview_enabled,
+            get_in_memory_capture,
+            set_in_memory_capture,
+            get_preroll_ms,
+            set_preroll_ms,
+            get_preroll_policy,
+            set_preroll_policy,
+            get_dsp_high_pass_hz,
+            set_dsp_high_pass_hz,
+            get_dsp_noise_gate_db,
+            set_dsp_noise_gate_db,
+            get_dsp_normalization,
+            set_dsp_normalization,
+            get_resample_quality,
+            set_resample_quality,
+            get_recording_limit_secs,
+            set_recording_limit_secs,
+            get_recording_limit_warning_secs,
+            set_recording_limit_warning_secs,
+            get_recording_limit_action,
+            set_recording_limit_action,
+            get_min_recording_ms,
+            set_min_recording_ms,
+            get_min_recording_level_db,
+            set_min_recording_level_db,
+            get_mic_channel_count,
+            get_mic_channel_strategy,
+            set_mic_channel_strategy,
+            get_file_channel_strategy,
+            set_file_channel_strategy,
             open_accessibility_settings,
             check_accessibility_permission
         ])
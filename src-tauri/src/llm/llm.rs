@@ -8,9 +8,12 @@ use log::warn;
 use std::time::Duration;
 use tauri::{AppHandle, Emitter, Manager};
 
+/// Runs the transcription through the LLM mode at `mode_index`, or the active one.
 pub async fn post_process_with_llm(
     app: &AppHandle,
     transcription: String,
+    uncertain_words: &[String],
+    mode_index: Option<usize>,
     force_bypass: bool,
 ) -> Result<String, String> {
     // If force_bypass is true, skip LLM processing entirely
@@ -22,7 +25,7 @@ pub async fn post_process_with_llm(
 
     let active_mode = settings
         .modes
-        .get(settings.active_mode_index)
+        .get(mode_index.unwrap_or(settings.active_mode_index))
         .ok_or("No active mode selected")?;
 
     if active_mode.model.is_empty() {
@@ -37,13 +40,16 @@ pub async fn post_process_with_llm(
         .into_keys()
         .collect::<Vec<String>>()
         .join(", ");
+    let uncertain_words = uncertain_words.join(", ");
 
     let prompt = active_mode
         .prompt
         .replace("{{TRANSCRIPT}}", &transcription)
         .replace("{transcript}", &transcription) // Support new variable syntax
         .replace("{{DICTIONARY}}", &dictionary_words)
-        .replace("{dictionary}", &dictionary_words); // Support new variable syntax
+        .replace("{dictionary}", &dictionary_words) // Support new variable syntax
+        .replace("{{UNCERTAIN_WORDS}}", &uncertain_words)
+        .replace("{uncertain_words}", &uncertain_words);
 
     let client = reqwest::Client::new();
     let url = format!("{}/generate", settings.url.trim_end_matches('/'));
@@ -80,11 +86,16 @@ pub async fn post_process_with_llm(
     Ok(ollama_response.response.trim().to_string())
 }
 
-pub async fn process_command_with_llm(app: &AppHandle, prompt: String) -> Result<String, String> {
+/// Sends a command prompt to the model of the LLM mode at `mode_index`, or the active one.
+pub async fn process_command_with_llm(
+    app: &AppHandle,
+    prompt: String,
+    mode_index: Option<usize>,
+) -> Result<String, String> {
     let settings = load_llm_connect_settings(app);
     let active_mode = settings
         .modes
-        .get(settings.active_mode_index)
+        .get(mode_index.unwrap_or(settings.active_mode_index))
         .ok_or("No active mode selected")?;
 
     if active_mode.model.is_empty() {
@@ -0,0 +1,389 @@
+use crate::model::types::{ModelError, OnnxSignature};
+use flate2::read::GzDecoder;
+use ort::session::builder::GraphOptimizationLevel;
+use ort::session::Session;
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Manifest in `sha256sum` format listing the files of a model and their hashes.
+pub const CHECKSUMS_FILENAME: &str = "checksums.sha256";
+
+const PARAKEET_FILES: [&str; 4] = [
+    "encoder-model*.onnx",
+    "decoder_joint-model*.onnx",
+    "nemo128.onnx",
+    "vocab.txt",
+];
+const CTC_FILES: [&str; 3] = ["model*.onnx", "nemo128.onnx", "vocab.txt"];
+/// Full precision files loaded for FP32, Int8 loading falls back to them.
+const PARAKEET_FP32_FILES: [&str; 2] = ["encoder-model.onnx", "decoder_joint-model.onnx"];
+const CTC_FP32_FILES: [&str; 1] = ["model.onnx"];
+
+const PREPROCESSOR_SIGNATURE: OnnxSignature = OnnxSignature {
+    pattern: "nemo128.onnx",
+    inputs: &["waveforms", "waveforms_lens"],
+    outputs: &["features", "features_lens"],
+};
+const PARAKEET_SIGNATURES: [OnnxSignature; 3] = [
+    PREPROCESSOR_SIGNATURE,
+    OnnxSignature {
+        pattern: "encoder-model*.onnx",
+        inputs: &["audio_signal", "length"],
+        outputs: &["outputs", "encoded_lengths"],
+    },
+    OnnxSignature {
+        pattern: "decoder_joint-model*.onnx",
+        inputs: &[
+            "encoder_outputs",
+            "targets",
+            "target_length",
+            "input_states_1",
+            "input_states_2",
+        ],
+        outputs: &["outputs"],
+    },
+];
+const CTC_SIGNATURES: [OnnxSignature; 2] = [
+    PREPROCESSOR_SIGNATURE,
+    OnnxSignature {
+        pattern: "model*.onnx",
+        inputs: &["audio_signal", "length"],
+        outputs: &["logprobs"],
+    },
+];
+
+/// Checks that `dir` holds a complete model and returns the engine able to run it.
+/// With `verify_checksums`, every file listed in the checksum manifest is hashed too.
+pub fn validate_model_dir(dir: &Path, verify_checksums: bool) -> Result<&'static str, ModelError> {
+    reject_symlinks(dir)?;
+
+    let (engine, required) = match find_file(dir, "model*.onnx") {
+        Some(_) if find_file(dir, "encoder-model*.onnx").is_none() => ("ctc", &CTC_FILES[..]),
+        _ => ("parakeet", &PARAKEET_FILES[..]),
+    };
+
+    if let Some(missing) = required
+        .iter()
+        .find(|pattern| find_file(dir, pattern).is_none())
+    {
+        return Err(ModelError::MissingFile(missing.to_string()));
+    }
+
+    if verify_checksums && dir.join(CHECKSUMS_FILENAME).exists() {
+        verify_checksum_manifest(dir)?;
+    }
+
+    Ok(engine)
+}
+
+/// Whether `dir` holds the full precision files `engine` loads for FP32.
+pub fn has_fp32_files(dir: &Path, engine: &str) -> bool {
+    let files = match engine {
+        "ctc" => &CTC_FP32_FILES[..],
+        _ => &PARAKEET_FP32_FILES[..],
+    };
+    files.iter().all(|file| is_regular_file(&dir.join(file)))
+}
+
+/// Opens every ONNX file of the `engine` model in `dir` and checks it declares the
+/// inputs and outputs the engine feeds and reads. Used on import, where the archive
+/// content is untrusted and a checksum manifest alone proves nothing.
+pub fn check_onnx_signatures(dir: &Path, engine: &str) -> Result<(), ModelError> {
+    let signatures = match engine {
+        "ctc" => &CTC_SIGNATURES[..],
+        _ => &PARAKEET_SIGNATURES[..],
+    };
+
+    for signature in signatures {
+        let path = find_file(dir, signature.pattern)
+            .ok_or_else(|| ModelError::MissingFile(signature.pattern.to_string()))?;
+        let filename = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let incompatible = |reason: String| ModelError::IncompatibleOnnx(filename.clone(), reason);
+
+        let session = Session::builder()
+            .and_then(|builder| builder.with_optimization_level(GraphOptimizationLevel::Disable))
+            .and_then(|builder| builder.commit_from_file(&path))
+            .map_err(|e| incompatible(e.to_string()))?;
+
+        let inputs: Vec<&str> = session.inputs.iter().map(|i| i.name.as_str()).collect();
+        let outputs: Vec<&str> = session.outputs.iter().map(|o| o.name.as_str()).collect();
+        if let Some(name) = signature.inputs.iter().find(|name| !inputs.contains(name)) {
+            return Err(incompatible(format!("missing input '{}'", name)));
+        }
+        if let Some(name) = signature
+            .outputs
+            .iter()
+            .find(|name| !outputs.contains(name))
+        {
+            return Err(incompatible(format!("missing output '{}'", name)));
+        }
+    }
+
+    Ok(())
+}
+
+/// Hashes every file listed in the checksum manifest of `dir`.
+pub fn verify_checksum_manifest(dir: &Path) -> Result<(), ModelError> {
+    let manifest = BufReader::new(File::open(dir.join(CHECKSUMS_FILENAME))?);
+
+    for line in manifest.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let (expected, filename) = line
+            .split_once(char::is_whitespace)
+            .map(|(hash, name)| (hash, name.trim_start().trim_start_matches('*')))
+            .filter(|(_, name)| !name.is_empty() && !name.contains(['/', '\\']))
+            .ok_or_else(|| ModelError::InvalidManifest(line.clone()))?;
+
+        let path = dir.join(filename);
+        if !is_regular_file(&path) {
+            return Err(ModelError::MissingFile(filename.to_string()));
+        }
+        if !sha256_file(&path)?.eq_ignore_ascii_case(expected) {
+            return Err(ModelError::ChecksumMismatch(filename.to_string()));
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes a checksum manifest for every file of `dir`, so later corruption can be detected.
+pub fn write_checksum_manifest(dir: &Path) -> Result<(), ModelError> {
+    let mut filenames: Vec<String> = fs::read_dir(dir)?
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|file_type| file_type.is_file()))
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|name| name != CHECKSUMS_FILENAME)
+        .collect();
+    filenames.sort();
+
+    let mut manifest = String::new();
+    for filename in filenames {
+        let hash = sha256_file(&dir.join(&filename))?;
+        manifest.push_str(&format!("{}  {}\n", hash, filename));
+    }
+
+    File::create(dir.join(CHECKSUMS_FILENAME))?.write_all(manifest.as_bytes())?;
+    Ok(())
+}
+
+pub fn sha256_file(path: &Path) -> Result<String, ModelError> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 1024 * 1024];
+
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Extracts a `.tar.gz` archive. Entries escaping `dest` are skipped by `tar`.
+pub fn extract_archive(archive: &Path, dest: &Path) -> Result<(), ModelError> {
+    let decoder = GzDecoder::new(BufReader::new(File::open(archive)?));
+    tar::Archive::new(decoder).unpack(dest)?;
+    Ok(())
+}
+
+/// Returns the directory holding the model files: `dir` itself or one of its subdirectories.
+/// Symbolic links are never followed, so an archive cannot point outside itself.
+pub fn find_model_root(dir: &Path) -> Option<PathBuf> {
+    if is_regular_file(&dir.join("vocab.txt")) {
+        return Some(dir.to_path_buf());
+    }
+
+    fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|file_type| file_type.is_dir()))
+        .find_map(|entry| find_model_root(&entry.path()))
+}
+
+/// Fails on the first symbolic link found directly in `dir`.
+pub fn reject_symlinks(dir: &Path) -> Result<(), ModelError> {
+    if fs::symlink_metadata(dir)?.file_type().is_symlink() {
+        return Err(ModelError::Symlink(dir.display().to_string()));
+    }
+
+    for entry in fs::read_dir(dir)?.flatten() {
+        if entry.file_type()?.is_symlink() {
+            return Err(ModelError::Symlink(
+                entry.file_name().to_string_lossy().to_string(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn is_regular_file(path: &Path) -> bool {
+    fs::symlink_metadata(path).is_ok_and(|metadata| metadata.is_file())
+}
+
+/// Model name derived from an archive file name, e.g. `my-model.tar.gz` gives `my-model`.
+pub fn model_name_from_archive(archive: &Path) -> String {
+    let filename = archive
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    [".tar.gz", ".tgz"]
+        .iter()
+        .find_map(|extension| filename.strip_suffix(extension))
+        .unwrap_or(&filename)
+        .to_string()
+}
+
+pub fn dir_size(dir: &Path) -> u64 {
+    fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|entry| entry.metadata().ok())
+                .filter(|metadata| metadata.is_file())
+                .map(|metadata| metadata.len())
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+fn find_file(dir: &Path, pattern: &str) -> Option<PathBuf> {
+    let (prefix, suffix) = pattern.split_once('*').unwrap_or((pattern, ""));
+
+    fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .find(|path| {
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy())
+                .unwrap_or_default();
+            is_regular_file(path)
+                && name.len() >= prefix.len() + suffix.len()
+                && name.starts_with(prefix)
+                && name.ends_with(suffix)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_model_dir(files: &[&str]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("murmure-model-test-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        for file in files {
+            fs::write(dir.join(file), file.as_bytes()).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn test_validate_model_dir_detects_engine() {
+        let parakeet = temp_model_dir(&[
+            "encoder-model.int8.onnx",
+            "decoder_joint-model.int8.onnx",
+            "nemo128.onnx",
+            "vocab.txt",
+        ]);
+        let ctc = temp_model_dir(&["model.onnx", "nemo128.onnx", "vocab.txt"]);
+
+        assert_eq!(validate_model_dir(&parakeet, true).unwrap(), "parakeet");
+        assert_eq!(validate_model_dir(&ctc, true).unwrap(), "ctc");
+
+        fs::remove_dir_all(parakeet).unwrap();
+        fs::remove_dir_all(ctc).unwrap();
+    }
+
+    #[test]
+    fn test_validate_model_dir_reports_missing_file() {
+        let dir = temp_model_dir(&["encoder-model.onnx", "nemo128.onnx", "vocab.txt"]);
+
+        let error = validate_model_dir(&dir, false).unwrap_err();
+        assert!(
+            matches!(error, ModelError::MissingFile(file) if file == "decoder_joint-model*.onnx")
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_checksum_manifest_detects_corruption() {
+        let dir = temp_model_dir(&["model.onnx", "nemo128.onnx", "vocab.txt"]);
+        write_checksum_manifest(&dir).unwrap();
+        assert!(validate_model_dir(&dir, true).is_ok());
+
+        fs::write(dir.join("vocab.txt"), b"corrupted").unwrap();
+        let error = validate_model_dir(&dir, true).unwrap_err();
+        assert!(matches!(error, ModelError::ChecksumMismatch(file) if file == "vocab.txt"));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_has_fp32_files() {
+        let int8 = temp_model_dir(&["encoder-model.int8.onnx", "decoder_joint-model.int8.onnx"]);
+        let both = temp_model_dir(&[
+            "encoder-model.int8.onnx",
+            "encoder-model.onnx",
+            "decoder_joint-model.onnx",
+        ]);
+
+        assert!(!has_fp32_files(&int8, "parakeet"));
+        assert!(has_fp32_files(&both, "parakeet"));
+        assert!(!has_fp32_files(&both, "ctc"));
+
+        fs::remove_dir_all(int8).unwrap();
+        fs::remove_dir_all(both).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlinks_are_rejected() {
+        let outside = temp_model_dir(&["model.onnx", "nemo128.onnx", "vocab.txt"]);
+        let dir = temp_model_dir(&["model.onnx", "nemo128.onnx"]);
+        std::os::unix::fs::symlink(outside.join("vocab.txt"), dir.join("vocab.txt")).unwrap();
+        let archive = temp_model_dir(&[]);
+        std::os::unix::fs::symlink(&outside, archive.join("linked")).unwrap();
+
+        let error = validate_model_dir(&dir, false).unwrap_err();
+        assert!(matches!(error, ModelError::Symlink(file) if file == "vocab.txt"));
+        assert!(find_model_root(&archive).is_none());
+
+        for dir in [outside, dir, archive] {
+            fs::remove_dir_all(dir).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_check_onnx_signatures_rejects_invalid_model() {
+        let dir = temp_model_dir(&["model.onnx", "nemo128.onnx", "vocab.txt"]);
+
+        let error = check_onnx_signatures(&dir, "ctc").unwrap_err();
+        assert!(matches!(error, ModelError::IncompatibleOnnx(file, _) if file == "nemo128.onnx"));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_model_name_from_archive() {
+        assert_eq!(
+            model_name_from_archive(Path::new("/tmp/parakeet-fr.tar.gz")),
+            "parakeet-fr"
+        );
+        assert_eq!(model_name_from_archive(Path::new("ctc.tgz")), "ctc");
+    }
+}
@@ -0,0 +1,181 @@
+use crate::model::helpers::{
+    check_onnx_signatures, dir_size, extract_archive, find_model_root, model_name_from_archive,
+    validate_model_dir, write_checksum_manifest, CHECKSUMS_FILENAME,
+};
+use crate::model::types::{ModelError, ModelInfo};
+use crate::model::{Model, MODEL_FILENAME};
+use crate::settings::AppSettings;
+use anyhow::Result;
+use log::{error, info, warn};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Directory holding the models imported by the user.
+pub fn models_dir(app: &AppHandle) -> Result<PathBuf> {
+    let dir = app.path().app_data_dir()?.join("models");
+    if !dir.exists() {
+        fs::create_dir_all(&dir)?;
+    }
+    Ok(dir)
+}
+
+/// Lists the bundled model followed by the imported ones.
+/// Files are only checked for presence here, checksums are verified on activation.
+pub fn list_models(app: &AppHandle) -> Result<Vec<ModelInfo>> {
+    let mut models = Vec::new();
+
+    if let Ok(path) = app.state::<Arc<Model>>().get_model_path() {
+        models.push(model_info(MODEL_FILENAME.to_string(), path, true, false));
+    }
+
+    let mut installed: Vec<ModelInfo> = fs::read_dir(models_dir(app)?)?
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        // Hidden directories are imports in progress
+        .filter(|id| !id.starts_with('.'))
+        .filter_map(|id| {
+            let path = installed_model_path(app, &id).ok()?;
+            Some(model_info(id, path, false, false))
+        })
+        .collect();
+    installed.sort_by(|a, b| a.id.cmp(&b.id));
+    models.extend(installed);
+
+    Ok(models)
+}
+
+/// Imports a model from a `.tar.gz` archive into the models directory.
+pub fn import_model(app: &AppHandle, archive: &Path) -> Result<ModelInfo> {
+    let models_dir = models_dir(app)?;
+    let staging_dir = models_dir.join(format!(".import-{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(&staging_dir)?;
+
+    let result = import_into(&models_dir, &staging_dir, archive);
+    if let Err(e) = fs::remove_dir_all(&staging_dir) {
+        warn!("Failed to remove model import directory: {}", e);
+    }
+
+    let info = result?;
+    info!("Model '{}' imported to {}", info.id, info.path.display());
+    Ok(info)
+}
+
+fn import_into(models_dir: &Path, staging_dir: &Path, archive: &Path) -> Result<ModelInfo> {
+    extract_archive(archive, staging_dir)?;
+    let root = find_model_root(staging_dir).ok_or(ModelError::NotFoundInArchive)?;
+
+    let id = match root.as_path() == staging_dir {
+        true => model_name_from_archive(archive),
+        false => root
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| model_name_from_archive(archive)),
+    };
+    let target = models_dir.join(&id);
+    if id.is_empty() || id.starts_with('.') || target.exists() || id == MODEL_FILENAME {
+        return Err(ModelError::AlreadyInstalled(id).into());
+    }
+
+    // The archive is untrusted: its ONNX files must match what the engine runs.
+    // The manifest, shipped or written now, only detects later corruption.
+    let engine = validate_model_dir(&root, true)?;
+    check_onnx_signatures(&root, engine)?;
+    if !root.join(CHECKSUMS_FILENAME).exists() {
+        write_checksum_manifest(&root)?;
+    }
+
+    fs::rename(&root, &target)?;
+    Ok(model_info(id, target, false, false))
+}
+
+/// Returns the directory of the active model, `None` for the bundled one.
+/// An active model whose directory was removed is replaced by the bundled one,
+/// in `settings` and on disk.
+pub fn active_model_path(app: &AppHandle, settings: &mut AppSettings) -> Result<Option<PathBuf>> {
+    let Some(id) = settings.active_model.clone() else {
+        return Ok(None);
+    };
+
+    match installed_model_path(app, &id) {
+        Ok(path) => Ok(Some(path)),
+        Err(e) => {
+            warn!("{}, falling back to the bundled model", e);
+            let path = app.state::<Arc<Model>>().get_model_path()?;
+            let info = model_info(MODEL_FILENAME.to_string(), path, true, false);
+
+            settings.active_model = None;
+            if let Some(engine) = &info.engine {
+                settings.engine = engine.clone();
+            }
+            crate::settings::save_settings(app, settings).map_err(|e| anyhow::anyhow!(e))?;
+
+            let _ = app.emit("model-changed", &info);
+            Ok(None)
+        }
+    }
+}
+
+/// Makes `id` the active model after verifying its files, then swaps the loaded engine.
+/// The bundled model is selected with its own id.
+pub fn set_active_model(app: &AppHandle, id: &str) -> Result<ModelInfo> {
+    let info = match id == MODEL_FILENAME {
+        true => {
+            let path = app.state::<Arc<Model>>().get_model_path()?;
+            model_info(id.to_string(), path, true, true)
+        }
+        false => {
+            let path = installed_model_path(app, id)?;
+            model_info(id.to_string(), path, false, true)
+        }
+    };
+
+    if let Some(reason) = &info.error {
+        anyhow::bail!("Model '{}' cannot be used: {}", id, reason);
+    }
+
+    let mut settings = crate::settings::load_settings(app);
+    settings.active_model = (!info.bundled).then(|| info.id.clone());
+    settings.engine_model_path = None;
+    if let Some(engine) = &info.engine {
+        settings.engine = engine.clone();
+    }
+    crate::settings::save_settings(app, &settings).map_err(|e| anyhow::anyhow!(e))?;
+
+    info!("Active model set to '{}'", info.id);
+    let _ = app.emit("model-changed", &info);
+
+    // Hot-swap the engine in the background, the next dictation waits for it
+    let app_handle = app.clone();
+    std::thread::spawn(move || {
+        if let Err(e) = crate::audio::preload_engine(&app_handle) {
+            error!("Failed to load the new model: {}", e);
+        }
+    });
+
+    Ok(info)
+}
+
+fn installed_model_path(app: &AppHandle, id: &str) -> Result<PathBuf> {
+    // Ids are plain directory names, never paths
+    let is_plain_name = !id.is_empty() && !id.starts_with('.') && !id.contains(['/', '\\']);
+    let path = models_dir(app)?.join(id);
+    if !is_plain_name || !path.is_dir() {
+        return Err(ModelError::NotInstalled(id.to_string()).into());
+    }
+    Ok(path)
+}
+
+fn model_info(id: String, path: PathBuf, bundled: bool, verify_checksums: bool) -> ModelInfo {
+    let validation = validate_model_dir(&path, verify_checksums);
+    ModelInfo {
+        size_bytes: dir_size(&path),
+        engine: validation.as_ref().ok().map(|engine| engine.to_string()),
+        error: validation.err().map(|e| e.to_string()),
+        id,
+        path,
+        bundled,
+    }
+}
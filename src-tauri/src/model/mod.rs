@@ -1,2 +1,7 @@
+pub mod helpers;
+pub mod manager;
 pub mod model;
+pub mod types;
+
 pub use model::*;
+pub use types::*;
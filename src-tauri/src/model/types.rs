@@ -0,0 +1,46 @@
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// A model directory known to the model manager.
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelInfo {
+    /// Directory name, used as the model identifier.
+    pub id: String,
+    pub path: PathBuf,
+    /// Engine able to run the model, see `engine::registry`.
+    pub engine: Option<String>,
+    /// Shipped with the application, cannot be removed.
+    pub bundled: bool,
+    pub size_bytes: u64,
+    /// Why the model cannot be used, `None` when it is valid.
+    pub error: Option<String>,
+}
+
+/// Names an ONNX file of a model must declare for its engine to run it.
+pub struct OnnxSignature {
+    pub pattern: &'static str,
+    pub inputs: &'static [&'static str],
+    pub outputs: &'static [&'static str],
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ModelError {
+    #[error("Missing model file: {0}")]
+    MissingFile(String),
+    #[error("Checksum mismatch for {0}, the file may be corrupted")]
+    ChecksumMismatch(String),
+    #[error("Invalid checksum manifest line: {0}")]
+    InvalidManifest(String),
+    #[error("{0} is a symbolic link, model files must be regular files")]
+    Symlink(String),
+    #[error("{0} is not a compatible ONNX model: {1}")]
+    IncompatibleOnnx(String, String),
+    #[error("No model found in the archive")]
+    NotFoundInArchive,
+    #[error("A model named '{0}' is already installed")]
+    AlreadyInstalled(String),
+    #[error("Model '{0}' is not installed")]
+    NotInstalled(String),
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
@@ -126,6 +126,7 @@ pub fn show_recording_overlay(app_handle: &AppHandle) {
         let _ = window.show();
         let _ = window.set_ignore_cursor_events(true);
         let _ = window.emit("show-overlay", "recording");
+        crate::audio::preroll::refresh_preroll(app_handle);
     } else {
         warn!("recording_overlay window not found on show_recording_overlay");
     }
@@ -149,6 +150,7 @@ pub fn hide_recording_overlay(app_handle: &AppHandle) {
     if let Some(window) = app_handle.get_webview_window("recording_overlay") {
         let _ = window.emit("hide-overlay", ());
         let _ = window.hide();
+        crate::audio::preroll::refresh_preroll(app_handle);
     } else {
         warn!("recording_overlay window not found on hide_recording_overlay");
     }
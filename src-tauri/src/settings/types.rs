@@ -1,4 +1,9 @@
+use crate::audio::types::{
+    ChannelStrategy, Normalization, PrerollPolicy, RecordingLimitAction, ResampleQuality,
+};
+use crate::engine::{OptimizationLevel, QuantizationType};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 pub enum PasteMethod {
@@ -28,6 +33,8 @@ pub struct AppSettings {
     pub llm_record_shortcut: String,
     pub command_shortcut: String,
     pub cancel_recording_shortcut: String,
+    pub pause_recording_shortcut: String, // Empty = no shortcut
+    pub reprocess_shortcut: String,       // Empty = no shortcut
     pub secondary_record_shortcut: String,
     pub llm_mode_1_shortcut: String,
     pub llm_mode_2_shortcut: String,
@@ -35,12 +42,12 @@ pub struct AppSettings {
     pub llm_mode_4_shortcut: String,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub dictionary: Vec<String>,
-    pub record_mode: String,      // "push_to_talk" | "toggle_to_talk"
-    pub overlay_mode: String,     // "hidden" | "recording" | "always"
+    pub record_mode: String, // "push_to_talk" | "toggle_to_talk" | "hands_free"
+    pub overlay_mode: String, // "hidden" | "recording" | "always"
     pub overlay_position: String, // "top" | "bottom"
-    pub api_enabled: bool,        // Enable local HTTP API
-    pub api_port: u16,            // Port for local HTTP API
-    pub copy_to_clipboard: bool,  // Keep transcription in clipboard after recording finishes
+    pub api_enabled: bool,   // Enable local HTTP API
+    pub api_port: u16,       // Port for local HTTP API
+    pub copy_to_clipboard: bool, // Keep transcription in clipboard after recording finishes
     #[serde(default)]
     pub paste_method: PasteMethod, // Paste method: CtrlV or CtrlShiftV (for terminals)
     #[serde(default)]
@@ -55,6 +62,38 @@ pub struct AppSettings {
     pub log_level: String,      // "info" | "debug" | "trace" | "warn" | "error"
     #[serde(default)]
     pub auto_send_enter: bool, // Automatically send Enter after pasting transcription
+    pub beam_size: usize,       // Decoder beam width, 1 = greedy decoding
+    pub context_biasing_enabled: bool, // Boost dictionary words while decoding
+    pub context_biasing_weight: f32, // Bonus per matching token, in log-probability
+    pub engine: String,         // Transcription engine id, see engine::registry
+    pub engine_model_path: Option<String>, // Model directory, None = bundled Parakeet model
+    pub active_model: Option<String>, // Imported model id, None = bundled model
+    pub engine_idle_unload_minutes: u32, // Free the model after this idle time, 0 = never
+    pub model_precision: QuantizationType, // Int8 is faster, FP32 slightly more accurate
+    pub onnx_intra_threads: usize, // Threads per ONNX operator, 0 = one per core
+    pub onnx_inter_threads: usize, // Operators run in parallel above 1, 0 = sequential
+    pub onnx_optimization_level: OptimizationLevel,
+    pub vad_trim_silence: bool, // Drop leading and trailing silence before transcribing
+    pub hands_free_silence_ms: u32, // Hands-free recordings stop after this much silence
+    pub live_preview_enabled: bool, // Show partial transcripts in the overlay while recording
+    pub in_memory_capture: bool, // Keep recordings in memory instead of a temporary WAV file
+    pub preroll_ms: u32, // Audio kept from before the shortcut, 0 = microphone closed between recordings
+    pub preroll_policy: PrerollPolicy, // When the microphone may stay open for the pre-roll
+    pub dsp_high_pass_hz: u32, // High-pass cutoff before transcription, 0 = off
+    pub dsp_noise_gate_db: Option<f32>, // Noise gate threshold in dBFS, None = off
+    pub dsp_normalization: Normalization, // Level adjustment before transcription
+    pub resample_quality: ResampleQuality, // Filter length when converting to 16 kHz
+    pub channel_strategies: HashMap<String, ChannelStrategy>, // Per microphone id, "default" = system default
+    pub file_channel_strategy: ChannelStrategy, // Downmix of files sent to the HTTP API or benchmark
+    pub recording_limit_secs: u32,              // Longest recording, or chunk length with rollover
+    pub recording_limit_warning_secs: u32,      // Countdown shown before the limit, 0 = no warning
+    pub recording_limit_action: RecordingLimitAction, // Stop, or transcribe and continue in a new chunk
+    pub min_recording_ms: u32, // Shorter recordings are discarded as accidental taps
+    pub min_recording_level_db: Option<f32>, // Recordings never this loud are discarded, None = off
+    pub retained_recordings: usize, // Recordings kept in memory for reprocessing, 0 = none
+    pub archive_enabled: bool, // Keep a FLAC copy of each transcribed recording
+    pub archive_max_age_days: Option<u32>, // Archived audio older than this is deleted, None = keep
+    pub archive_max_size_mb: Option<u64>, // Oldest archived audio is deleted past this size, None = no cap
 }
 
 impl Default for AppSettings {
@@ -65,6 +104,8 @@ impl Default for AppSettings {
             llm_record_shortcut: "ctrl+alt+space".to_string(),
             command_shortcut: "ctrl+shift+x".to_string(),
             cancel_recording_shortcut: "escape".to_string(),
+            pause_recording_shortcut: String::new(),
+            reprocess_shortcut: String::new(),
             secondary_record_shortcut: "mouse4".to_string(),
             llm_mode_1_shortcut: "ctrl+shift+1".to_string(),
             llm_mode_2_shortcut: "ctrl+shift+2".to_string(),
@@ -85,6 +126,38 @@ impl Default for AppSettings {
             mic_id: None,
             log_level: "info".to_string(),
             auto_send_enter: false,
+            beam_size: 1,
+            context_biasing_enabled: false,
+            context_biasing_weight: 1.5,
+            engine: crate::engine::registry::DEFAULT_ENGINE_ID.to_string(),
+            engine_model_path: None,
+            active_model: None,
+            engine_idle_unload_minutes: 0,
+            model_precision: QuantizationType::Int8,
+            onnx_intra_threads: 0,
+            onnx_inter_threads: 0,
+            onnx_optimization_level: OptimizationLevel::All,
+            vad_trim_silence: false,
+            hands_free_silence_ms: 1500,
+            live_preview_enabled: false,
+            in_memory_capture: false,
+            preroll_ms: 0,
+            preroll_policy: PrerollPolicy::default(),
+            dsp_high_pass_hz: 0,
+            dsp_noise_gate_db: None,
+            dsp_normalization: Normalization::default(),
+            resample_quality: ResampleQuality::default(),
+            channel_strategies: HashMap::new(),
+            file_channel_strategy: ChannelStrategy::default(),
+            recording_limit_secs: 300,
+            recording_limit_warning_secs: 15,
+            recording_limit_action: RecordingLimitAction::default(),
+            min_recording_ms: 300,
+            min_recording_level_db: Some(-50.0),
+            retained_recordings: 0,
+            archive_enabled: false,
+            archive_max_age_days: Some(30),
+            archive_max_size_mb: Some(1024),
         }
     }
 }
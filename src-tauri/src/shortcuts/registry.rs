@@ -5,11 +5,7 @@ use std::sync::atomic::Ordering;
 
 impl ShortcutRegistry {
     pub fn from_settings(settings: &crate::settings::types::AppSettings) -> Self {
-        let activation_mode = if settings.record_mode == "toggle_to_talk" {
-            ActivationMode::ToggleToTalk
-        } else {
-            ActivationMode::PushToTalk
-        };
+        let activation_mode = ActivationMode::from_record_mode(&settings.record_mode);
 
         let mut bindings = vec![
             ShortcutBinding {
@@ -42,6 +38,16 @@ impl ShortcutRegistry {
                 action: ShortcutAction::CancelRecording,
                 activation_mode: ActivationMode::PushToTalk,
             },
+            ShortcutBinding {
+                keys: parse_binding_keys(&settings.pause_recording_shortcut),
+                action: ShortcutAction::PauseRecording,
+                activation_mode: ActivationMode::PushToTalk,
+            },
+            ShortcutBinding {
+                keys: parse_binding_keys(&settings.reprocess_shortcut),
+                action: ShortcutAction::ReprocessLastRecording,
+                activation_mode: ActivationMode::PushToTalk,
+            },
         ];
 
         let mode_shortcuts = [
@@ -74,6 +74,21 @@ pub fn handle_shortcut_event(
                 }
             }
         }
+        ShortcutAction::PauseRecording => {
+            if event_type == KeyEventType::Pressed
+                && *recording_state().source.lock() != RecordingSource::None
+            {
+                crate::audio::toggle_pause_recording(app);
+            }
+        }
+        ShortcutAction::ReprocessLastRecording => {
+            // In LLM mode, the mode shortcuts choose which LLM mode is used
+            if event_type == KeyEventType::Pressed
+                && *recording_state().source.lock() == RecordingSource::None
+            {
+                crate::audio::reprocess_last_recording(app, RecordingMode::Llm);
+            }
+        }
         ShortcutAction::SwitchLLMMode(index) => {
             if event_type == KeyEventType::Pressed {
                 let mut last_switch = recording_state().last_mode_switch.lock();
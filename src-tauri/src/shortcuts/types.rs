@@ -16,6 +16,8 @@ pub enum ShortcutAction {
     StartRecordingSecondary,
     PasteLastTranscript,
     CancelRecording,
+    PauseRecording,
+    ReprocessLastRecording,
     SwitchLLMMode(usize),
 }
 
@@ -25,6 +27,17 @@ pub enum ActivationMode {
     ToggleToTalk,
 }
 
+impl ActivationMode {
+    /// Hands-free recordings start and stop like toggle to talk,
+    /// and also stop by themselves after a silence.
+    pub fn from_record_mode(record_mode: &str) -> Self {
+        match record_mode {
+            "toggle_to_talk" | "hands_free" => ActivationMode::ToggleToTalk,
+            _ => ActivationMode::PushToTalk,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ShortcutBinding {
     pub keys: Vec<i32>,
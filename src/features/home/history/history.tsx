@@ -14,7 +14,8 @@ import {
 import { toast } from 'react-toastify';
 import { formatTime } from './history.helpers';
 import { useHistoryState } from './hooks/use-history-state';
-import { InfoIcon, Trash2 } from 'lucide-react';
+import { FileAudio, InfoIcon, Trash2 } from 'lucide-react';
+import { revealItemInDir } from '@tauri-apps/plugin-opener';
 import { Tooltip, TooltipContent, TooltipTrigger } from '@/components/tooltip';
 import { useTranslation } from '@/i18n';
 
@@ -32,6 +33,23 @@ export const History = () => {
         }
     };
 
+    const handleShowAudio = async (id: number) => {
+        try {
+            const path = await invoke<string | null>(
+                'get_history_audio_path',
+                { id }
+            );
+            if (path == null) {
+                toast.info(t('This audio is no longer in the archive'));
+                return;
+            }
+            await revealItemInDir(path);
+        } catch (error) {
+            toast.error(t('Failed to open archived audio'));
+            console.error('Show archived audio error:', error);
+        }
+    };
+
     return (
         <div className="space-y-2 w-full mb-8">
             <div className="flex items-center justify-between">
@@ -118,9 +136,21 @@ export const History = () => {
                                         entry.text
                                     )}
                                 </Typography.Paragraph>
-                                <Typography.Paragraph className="text-xs block w-20 text-right">
-                                    {formatTime(entry.timestamp)}
-                                </Typography.Paragraph>
+                                <div className="flex flex-col items-end gap-1">
+                                    <Typography.Paragraph className="text-xs block w-20 text-right">
+                                        {formatTime(entry.timestamp)}
+                                    </Typography.Paragraph>
+                                    {entry.audio_file && (
+                                        <FileAudio
+                                            className="size-4 cursor-pointer hover:text-zinc-100 text-zinc-400 transition-colors"
+                                            aria-label={t('Show archived audio')}
+                                            onClick={(event) => {
+                                                event.stopPropagation();
+                                                handleShowAudio(entry.id);
+                                            }}
+                                        />
+                                    )}
+                                </div>
                             </div>
                         </button>
                     ))}
@@ -6,6 +6,7 @@ interface HistoryEntry {
     id: number;
     timestamp: number;
     text: string;
+    audio_file?: string;
 }
 
 export const useHistoryState = () => {
@@ -25,14 +25,16 @@ export const Home = () => {
     const pasteTimerRef = useRef<number | null>(null);
 
     useEffect(() => {
-        const unlistenCancelPromise = listen('recording-cancelled', () => {
+        const showCancelled = () => {
             if (cancelTimerRef.current) clearTimeout(cancelTimerRef.current);
             setIsCancelled(true);
             cancelTimerRef.current = setTimeout(() => {
                 setIsCancelled(false);
                 cancelTimerRef.current = null;
             }, 700);
-        });
+        };
+        const unlistenCancelPromise = listen('recording-cancelled', showCancelled);
+        const unlistenDiscardPromise = listen('recording-discarded', showCancelled);
         const unlistenPasteModePromise = listen<string>('overlay-paste-mode', (event) => {
             const mode = event.payload as PasteMode;
             if (pasteTimerRef.current) clearTimeout(pasteTimerRef.current);
@@ -44,6 +46,7 @@ export const Home = () => {
         });
         return () => {
             unlistenCancelPromise.then((unlisten) => unlisten());
+            unlistenDiscardPromise.then((unlisten) => unlisten());
             unlistenPasteModePromise.then((unlisten) => unlisten());
         };
     }, []);
@@ -11,13 +11,13 @@ interface HighlightedPromptEditorProps {
 /**
  * Applies syntax highlighting to the prompt text.
  * - XML tags: highlighted in cyan
- * - Variables {{TRANSCRIPT}} and {{DICTIONARY}}: highlighted in amber
+ * - Variables {{TRANSCRIPT}}, {{DICTIONARY}} and {{UNCERTAIN_WORDS}}: highlighted in amber
  * This is purely visual and does not modify the underlying text.
  */
 const highlightSyntax = (text: string): React.ReactNode[] => {
     // Regex to match XML tags or variables
     const regex =
-        /(<\/?[a-zA-Z][a-zA-Z0-9_-]*(?:\s[^>]*)?>)|({{(?:TRANSCRIPT|DICTIONARY)}})/g;
+        /(<\/?[a-zA-Z][a-zA-Z0-9_-]*(?:\s[^>]*)?>)|({{(?:TRANSCRIPT|DICTIONARY|UNCERTAIN_WORDS)}})/g;
     const parts: React.ReactNode[] = [];
     let lastIndex = 0;
     let match: RegExpExecArray | null;
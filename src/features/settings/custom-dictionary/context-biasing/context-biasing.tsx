@@ -0,0 +1,68 @@
+import { NumberInput } from '@/components/number-input';
+import { SettingsUI } from '@/components/settings-ui';
+import { Switch } from '@/components/switch';
+import { Typography } from '@/components/typography';
+import { Crosshair } from 'lucide-react';
+import { useTranslation } from '@/i18n';
+import { useContextBiasingState } from './hooks/use-context-biasing-state';
+
+export const ContextBiasing = () => {
+    const {
+        biasingEnabled,
+        setBiasingEnabled,
+        biasingWeight,
+        setBiasingWeight,
+    } = useContextBiasingState();
+    const { t } = useTranslation();
+
+    return (
+        <SettingsUI.Container>
+            <SettingsUI.Item>
+                <SettingsUI.Description>
+                    <Typography.Title className="flex items-center gap-2">
+                        <Crosshair className="w-4 h-4 text-zinc-400" />
+                        {t('Favor dictionary words while transcribing')}
+                    </Typography.Title>
+                    <Typography.Paragraph>
+                        {t(
+                            'The model leans towards your custom words as it decodes, instead of only correcting the text afterwards.'
+                        )}
+                    </Typography.Paragraph>
+                </SettingsUI.Description>
+                <Switch
+                    checked={biasingEnabled}
+                    onCheckedChange={setBiasingEnabled}
+                    data-testid="context-biasing-switch"
+                />
+            </SettingsUI.Item>
+            {biasingEnabled && (
+                <>
+                    <SettingsUI.Separator />
+                    <SettingsUI.Item>
+                        <SettingsUI.Description>
+                            <Typography.Title>
+                                {t('Boost strength')}
+                            </Typography.Title>
+                            <Typography.Paragraph>
+                                {t(
+                                    'From 0 to 10. Too high a value makes similar-sounding words turn into dictionary words.'
+                                )}
+                            </Typography.Paragraph>
+                        </SettingsUI.Description>
+                        <NumberInput
+                            min={0}
+                            max={10}
+                            stepper={0.5}
+                            decimalScale={1}
+                            value={biasingWeight}
+                            onValueChange={(value) =>
+                                setBiasingWeight(value ?? 1.5)
+                            }
+                            data-testid="context-biasing-weight-input"
+                        />
+                    </SettingsUI.Item>
+                </>
+            )}
+        </SettingsUI.Container>
+    );
+};
@@ -0,0 +1,52 @@
+import { invoke } from '@tauri-apps/api/core';
+import { useEffect, useState } from 'react';
+
+export const useContextBiasingState = () => {
+    const [biasingEnabled, setBiasingEnabled] = useState<boolean>(false);
+    const [biasingWeight, setBiasingWeight] = useState<number>(1.5);
+
+    useEffect(() => {
+        const loadContextBiasing = async () => {
+            try {
+                setBiasingEnabled(
+                    await invoke<boolean>('get_context_biasing_enabled')
+                );
+                setBiasingWeight(
+                    await invoke<number>('get_context_biasing_weight')
+                );
+            } catch (error) {
+                console.error('Failed to load context biasing:', error);
+            }
+        };
+        loadContextBiasing();
+    }, []);
+
+    const handleSetBiasingEnabled = async (enabled: boolean) => {
+        try {
+            setBiasingEnabled(enabled);
+            await invoke('set_context_biasing_enabled', { enabled });
+        } catch (error) {
+            console.error('Failed to set context biasing:', error);
+            setBiasingEnabled(!enabled);
+        }
+    };
+
+    const handleSetBiasingWeight = async (weight: number) => {
+        if (weight < 0 || weight > 10) {
+            return;
+        }
+        try {
+            setBiasingWeight(weight);
+            await invoke('set_context_biasing_weight', { weight });
+        } catch (error) {
+            console.error('Failed to set context biasing weight:', error);
+        }
+    };
+
+    return {
+        biasingEnabled,
+        setBiasingEnabled: handleSetBiasingEnabled,
+        biasingWeight,
+        setBiasingWeight: handleSetBiasingWeight,
+    };
+};
@@ -25,6 +25,7 @@ import {
     DialogTitle,
 } from '@/components/dialog';
 import { Button } from '@/components/button';
+import { ContextBiasing } from './context-biasing/context-biasing';
 
 export const CustomDictionary = () => {
     const [customWords, setCustomWords] = useState<string[]>([]);
@@ -284,6 +285,8 @@ export const CustomDictionary = () => {
                     </div>
                 )}
             </div>
+
+            <ContextBiasing />
         </main>
     );
 };
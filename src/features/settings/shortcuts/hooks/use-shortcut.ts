@@ -26,12 +26,13 @@ export const useShortcut = ({
     }, [getCommand]);
 
     const saveShortcut = async (value: string) => {
-        if (!value?.trim()) return;
+        // Only shortcuts that are off by default can be cleared
+        if (value.trim() === '' && defaultShortcut !== '') return;
         try {
             const normalized = await invoke<string>(setCommand, {
                 binding: value,
             });
-            if (normalized) setShortcut(normalized);
+            setShortcut(normalized);
         } catch {
             toast.error(t('Failed to save shortcut'));
         }
@@ -70,6 +71,16 @@ export const SHORTCUT_CONFIGS = {
         getCommand: 'get_cancel_recording_shortcut',
         setCommand: 'set_cancel_recording_shortcut',
     },
+    pauseRecording: {
+        defaultShortcut: '',
+        getCommand: 'get_pause_recording_shortcut',
+        setCommand: 'set_pause_recording_shortcut',
+    },
+    reprocess: {
+        defaultShortcut: '',
+        getCommand: 'get_reprocess_shortcut',
+        setCommand: 'set_reprocess_shortcut',
+    },
     record: {
         defaultShortcut: 'ctrl+space',
         getCommand: 'get_record_shortcut',
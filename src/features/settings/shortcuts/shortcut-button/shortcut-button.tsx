@@ -35,8 +35,10 @@ export const ShortcutButton = ({
         label = <RenderKeys keyString={binding} className="flex-wrap" />;
     } else if (isRecording) {
         label = <span className="text-zinc-500">{t('Press keys...')}</span>;
-    } else {
+    } else if (shortcut !== '') {
         label = <RenderKeys keyString={shortcut} className="flex-wrap" />;
+    } else {
+        label = <span className="text-zinc-500">{t('Not set')}</span>;
     }
 
     return (
@@ -35,6 +35,18 @@ export const Shortcuts = () => {
         resetShortcut: resetCancelRecordingShortcut,
     } = useShortcut(SHORTCUT_CONFIGS.cancelRecording);
 
+    const {
+        shortcut: pauseRecordingShortcut,
+        setShortcut: setPauseRecordingShortcut,
+        resetShortcut: resetPauseRecordingShortcut,
+    } = useShortcut(SHORTCUT_CONFIGS.pauseRecording);
+
+    const {
+        shortcut: reprocessShortcut,
+        setShortcut: setReprocessShortcut,
+        resetShortcut: resetReprocessShortcut,
+    } = useShortcut(SHORTCUT_CONFIGS.reprocess);
+
     const {
         shortcut: commandShortcut,
         setShortcut: setCommandShortcut,
@@ -192,6 +204,72 @@ export const Shortcuts = () => {
                                 dataTestId="cancel-recording-button"
                             />
                         </SettingsUI.Item>
+                        <SettingsUI.Separator />
+                        <SettingsUI.Item>
+                            <SettingsUI.Description>
+                                <Typography.Title>
+                                    {t('Pause Recording')}
+                                </Typography.Title>
+                                <Typography.Paragraph>
+                                    {pauseRecordingShortcut !== '' ? (
+                                        <>
+                                            {t('Press ')}
+                                            <RenderKeys
+                                                keyString={
+                                                    pauseRecordingShortcut
+                                                }
+                                            />
+                                            {t(
+                                                ' to pause the recording, and again to resume it in the same transcription.'
+                                            )}
+                                        </>
+                                    ) : (
+                                        t(
+                                            'Set a shortcut to pause the recording, and again to resume it in the same transcription.'
+                                        )
+                                    )}
+                                </Typography.Paragraph>
+                            </SettingsUI.Description>
+                            <ShortcutButton
+                                keyName={t('Pause Recording')}
+                                shortcut={pauseRecordingShortcut}
+                                saveShortcut={setPauseRecordingShortcut}
+                                resetShortcut={resetPauseRecordingShortcut}
+                                dataTestId="pause-recording-button"
+                            />
+                        </SettingsUI.Item>
+                        <SettingsUI.Separator />
+                        <SettingsUI.Item>
+                            <SettingsUI.Description>
+                                <Typography.Title>
+                                    {t('Reprocess Last Recording')}
+                                </Typography.Title>
+                                <Typography.Paragraph>
+                                    {reprocessShortcut !== '' ? (
+                                        <>
+                                            {t('Press ')}
+                                            <RenderKeys
+                                                keyString={reprocessShortcut}
+                                            />
+                                            {t(
+                                                ' to transcribe your last recording again with the active LLM mode, without speaking again.'
+                                            )}
+                                        </>
+                                    ) : (
+                                        t(
+                                            'Set a shortcut to transcribe your last recording again with the active LLM mode. Recordings are only kept when enabled in System settings.'
+                                        )
+                                    )}
+                                </Typography.Paragraph>
+                            </SettingsUI.Description>
+                            <ShortcutButton
+                                keyName={t('Reprocess Last Recording')}
+                                shortcut={reprocessShortcut}
+                                saveShortcut={setReprocessShortcut}
+                                resetShortcut={resetReprocessShortcut}
+                                dataTestId="reprocess-recording-button"
+                            />
+                        </SettingsUI.Item>
                     </SettingsUI.Container>
                 </section>
 
@@ -0,0 +1,143 @@
+import { SettingsUI } from '@/components/settings-ui';
+import { Typography } from '@/components/typography';
+import { Switch } from '@/components/switch';
+import { Button } from '@/components/button';
+import { Archive, FolderOpen } from 'lucide-react';
+import {
+    Select,
+    SelectContent,
+    SelectItem,
+    SelectTrigger,
+    SelectValue,
+} from '@/components/select';
+import { useTranslation } from '@/i18n';
+import { appDataDir, join } from '@tauri-apps/api/path';
+import { revealItemInDir } from '@tauri-apps/plugin-opener';
+import { toast } from 'react-toastify';
+import { useAudioArchiveState } from './hooks/use-audio-archive-state';
+
+const KEEP_FOREVER = 'forever';
+
+const MAX_AGE_OPTIONS = [
+    { value: '7', label: '7 days' },
+    { value: '30', label: '30 days' },
+    { value: '90', label: '90 days' },
+    { value: '365', label: '1 year' },
+    { value: KEEP_FOREVER, label: 'Forever' },
+];
+
+const MAX_SIZE_OPTIONS = [
+    { value: '256', label: '256 MB' },
+    { value: '1024', label: '1 GB' },
+    { value: '5120', label: '5 GB' },
+    { value: KEEP_FOREVER, label: 'No limit' },
+];
+
+const toOption = (value: number | null) =>
+    value == null ? KEEP_FOREVER : String(value);
+const fromOption = (value: string) =>
+    value === KEEP_FOREVER ? null : Number(value);
+
+export const AudioArchiveSettings = () => {
+    const { t } = useTranslation();
+    const {
+        archiveEnabled,
+        setArchiveEnabled,
+        maxAgeDays,
+        setMaxAgeDays,
+        maxSizeMb,
+        setMaxSizeMb,
+    } = useAudioArchiveState();
+
+    const handleOpenArchiveFolder = async () => {
+        try {
+            await revealItemInDir(await join(await appDataDir(), 'archive'));
+        } catch (error) {
+            console.error('Failed to open archive folder:', error);
+            toast.error(t('Failed to open archive folder'));
+        }
+    };
+
+    return (
+        <SettingsUI.Item>
+            <SettingsUI.Description>
+                <Typography.Title className="flex items-center gap-2">
+                    <Archive className="w-4 h-4 text-zinc-400" />
+                    {t('Audio archive')}
+                </Typography.Title>
+                <Typography.Paragraph>
+                    {t(
+                        'Keep a compressed copy of each recording, linked to its history entry. Older files are deleted automatically.'
+                    )}
+                </Typography.Paragraph>
+            </SettingsUI.Description>
+            <div className="flex items-center gap-2">
+                {archiveEnabled && (
+                    <>
+                        <Select
+                            value={toOption(maxAgeDays)}
+                            onValueChange={(value) =>
+                                setMaxAgeDays(fromOption(value))
+                            }
+                        >
+                            <SelectTrigger
+                                className="w-[110px]"
+                                title={t('Keep for')}
+                                data-testid="archive-max-age-select"
+                            >
+                                <SelectValue />
+                            </SelectTrigger>
+                            <SelectContent>
+                                {MAX_AGE_OPTIONS.map((option) => (
+                                    <SelectItem
+                                        key={option.value}
+                                        value={option.value}
+                                    >
+                                        {t(option.label)}
+                                    </SelectItem>
+                                ))}
+                            </SelectContent>
+                        </Select>
+                        <Select
+                            value={toOption(maxSizeMb)}
+                            onValueChange={(value) =>
+                                setMaxSizeMb(fromOption(value))
+                            }
+                        >
+                            <SelectTrigger
+                                className="w-[110px]"
+                                title={t('Size limit')}
+                                data-testid="archive-max-size-select"
+                            >
+                                <SelectValue />
+                            </SelectTrigger>
+                            <SelectContent>
+                                {MAX_SIZE_OPTIONS.map((option) => (
+                                    <SelectItem
+                                        key={option.value}
+                                        value={option.value}
+                                    >
+                                        {t(option.label)}
+                                    </SelectItem>
+                                ))}
+                            </SelectContent>
+                        </Select>
+                        <Button
+                            variant="outline"
+                            size="icon"
+                            onClick={handleOpenArchiveFolder}
+                            title={t('View archive')}
+                            data-testid="open-archive-folder-button"
+                        >
+                            <FolderOpen className="w-4 h-4" />
+                        </Button>
+                    </>
+                )}
+                <Switch
+                    checked={archiveEnabled}
+                    onCheckedChange={setArchiveEnabled}
+                />
+            </div>
+        </SettingsUI.Item>
+    );
+};
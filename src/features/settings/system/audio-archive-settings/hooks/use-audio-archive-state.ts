@@ -0,0 +1,55 @@
+import { invoke } from '@tauri-apps/api/core';
+import { useEffect, useState } from 'react';
+import { toast } from 'react-toastify';
+import { useTranslation } from '@/i18n';
+
+export const useAudioArchiveState = () => {
+    const [archiveEnabled, setArchiveEnabled] = useState<boolean>(false);
+    const [maxAgeDays, setMaxAgeDays] = useState<number | null>(30);
+    const [maxSizeMb, setMaxSizeMb] = useState<number | null>(1024);
+    const { t } = useTranslation();
+
+    useEffect(() => {
+        invoke<boolean>('get_archive_enabled').then(setArchiveEnabled);
+        invoke<number | null>('get_archive_max_age_days').then(setMaxAgeDays);
+        invoke<number | null>('get_archive_max_size_mb').then(setMaxSizeMb);
+    }, []);
+
+    const handleSetArchiveEnabled = async (enabled: boolean) => {
+        try {
+            setArchiveEnabled(enabled);
+            await invoke('set_archive_enabled', { enabled });
+        } catch {
+            setArchiveEnabled(!enabled);
+        }
+    };
+
+    const handleSetMaxAgeDays = async (days: number | null) => {
+        try {
+            await invoke('set_archive_max_age_days', { days });
+            setMaxAgeDays(days);
+        } catch (error) {
+            console.error('Failed to save archive retention:', error);
+            toast.error(t('Failed to save archive retention'));
+        }
+    };
+
+    const handleSetMaxSizeMb = async (sizeMb: number | null) => {
+        try {
+            await invoke('set_archive_max_size_mb', { sizeMb });
+            setMaxSizeMb(sizeMb);
+        } catch (error) {
+            console.error('Failed to save archive size limit:', error);
+            toast.error(t('Failed to save archive retention'));
+        }
+    };
+
+    return {
+        archiveEnabled,
+        setArchiveEnabled: handleSetArchiveEnabled,
+        maxAgeDays,
+        setMaxAgeDays: handleSetMaxAgeDays,
+        maxSizeMb,
+        setMaxSizeMb: handleSetMaxSizeMb,
+    };
+};
@@ -0,0 +1,34 @@
+import { NumberInput } from '@/components/number-input';
+import { SettingsUI } from '@/components/settings-ui';
+import { Typography } from '@/components/typography';
+import { ListTree } from 'lucide-react';
+import { useTranslation } from '@/i18n';
+import { useDecodingState } from './hooks/use-decoding-state';
+
+export const DecodingSettings = () => {
+    const { beamSize, setBeamSize } = useDecodingState();
+    const { t } = useTranslation();
+
+    return (
+        <SettingsUI.Item>
+            <SettingsUI.Description>
+                <Typography.Title className="flex items-center gap-2">
+                    <ListTree className="w-4 h-4 text-zinc-400" />
+                    {t('Beam width')}
+                </Typography.Title>
+                <Typography.Paragraph>
+                    {t(
+                        'Number of hypotheses compared while decoding (1-16). 1 is fastest; higher values help with jargon and near-homophones but slow down transcription.'
+                    )}
+                </Typography.Paragraph>
+            </SettingsUI.Description>
+            <NumberInput
+                min={1}
+                max={16}
+                value={beamSize}
+                onValueChange={(value) => setBeamSize(value ?? 1)}
+                data-testid="beam-size-input"
+            />
+        </SettingsUI.Item>
+    );
+};
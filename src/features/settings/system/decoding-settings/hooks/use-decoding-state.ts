@@ -0,0 +1,31 @@
+import { invoke } from '@tauri-apps/api/core';
+import { useEffect, useState } from 'react';
+
+export const useDecodingState = () => {
+    const [beamSize, setBeamSize] = useState<number>(1);
+
+    useEffect(() => {
+        invoke<number>('get_beam_size')
+            .then(setBeamSize)
+            .catch((error) =>
+                console.error('Failed to load beam size:', error)
+            );
+    }, []);
+
+    const handleSetBeamSize = async (size: number) => {
+        if (size < 1 || size > 16) {
+            return;
+        }
+        try {
+            setBeamSize(size);
+            await invoke('set_beam_size', { beamSize: size });
+        } catch (error) {
+            console.error('Failed to set beam size:', error);
+        }
+    };
+
+    return {
+        beamSize,
+        setBeamSize: handleSetBeamSize,
+    };
+};
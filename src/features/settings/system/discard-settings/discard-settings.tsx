@@ -0,0 +1,93 @@
+import { SettingsUI } from '@/components/settings-ui';
+import { Typography } from '@/components/typography';
+import { MicOff } from 'lucide-react';
+import {
+    Select,
+    SelectContent,
+    SelectItem,
+    SelectTrigger,
+    SelectValue,
+} from '@/components/select';
+import { useTranslation } from '@/i18n';
+import { useDiscardState } from './hooks/use-discard-state';
+
+const OFF = 'off';
+
+const MIN_DURATION_OPTIONS = [
+    { value: '0', label: 'Any length' },
+    { value: '200', label: '0.2 s' },
+    { value: '300', label: '0.3 s' },
+    { value: '500', label: '0.5 s' },
+    { value: '1000', label: '1 s' },
+];
+
+const MIN_LEVEL_OPTIONS = [
+    { value: OFF, label: 'Any level' },
+    { value: '-60', label: '-60 dB' },
+    { value: '-50', label: '-50 dB' },
+    { value: '-40', label: '-40 dB' },
+];
+
+export const DiscardSettings = () => {
+    const { t } = useTranslation();
+    const { minDurationMs, setMinDurationMs, minLevelDb, setMinLevelDb } =
+        useDiscardState();
+
+    return (
+        <SettingsUI.Item>
+            <SettingsUI.Description>
+                <Typography.Title className="flex items-center gap-2">
+                    <MicOff className="w-4 h-4 text-zinc-400" />
+                    {t('Ignore accidental taps')}
+                </Typography.Title>
+                <Typography.Paragraph>
+                    {t(
+                        'Recordings shorter or quieter than this are dropped instead of transcribed. Audio kept from before the shortcut does not count.'
+                    )}
+                </Typography.Paragraph>
+            </SettingsUI.Description>
+            <div className="flex items-center gap-2">
+                <Select
+                    value={String(minDurationMs)}
+                    onValueChange={(value) => setMinDurationMs(Number(value))}
+                >
+                    <SelectTrigger
+                        className="w-[120px]"
+                        title={t('Minimum duration')}
+                        data-testid="min-recording-duration-select"
+                    >
+                        <SelectValue />
+                    </SelectTrigger>
+                    <SelectContent>
+                        {MIN_DURATION_OPTIONS.map((option) => (
+                            <SelectItem key={option.value} value={option.value}>
+                                {t(option.label)}
+                            </SelectItem>
+                        ))}
+                    </SelectContent>
+                </Select>
+                <Select
+                    value={minLevelDb == null ? OFF : String(minLevelDb)}
+                    onValueChange={(value) =>
+                        setMinLevelDb(value === OFF ? null : Number(value))
+                    }
+                >
+                    <SelectTrigger
+                        className="w-[120px]"
+                        title={t('Minimum level')}
+                        data-testid="min-recording-level-select"
+                    >
+                        <SelectValue />
+                    </SelectTrigger>
+                    <SelectContent>
+                        {MIN_LEVEL_OPTIONS.map((option) => (
+                            <SelectItem key={option.value} value={option.value}>
+                                {t(option.label)}
+                            </SelectItem>
+                        ))}
+                    </SelectContent>
+                </Select>
+            </div>
+        </SettingsUI.Item>
+    );
+};
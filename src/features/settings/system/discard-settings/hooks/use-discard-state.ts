@@ -0,0 +1,44 @@
+import { invoke } from '@tauri-apps/api/core';
+import { useEffect, useState } from 'react';
+import { toast } from 'react-toastify';
+import { useTranslation } from '@/i18n';
+
+export const useDiscardState = () => {
+    const [minDurationMs, setMinDurationMs] = useState<number>(300);
+    const [minLevelDb, setMinLevelDb] = useState<number | null>(-50);
+    const { t } = useTranslation();
+
+    useEffect(() => {
+        invoke<number>('get_min_recording_ms').then(setMinDurationMs);
+        invoke<number | null>('get_min_recording_level_db').then(
+            setMinLevelDb
+        );
+    }, []);
+
+    const handleSetMinDurationMs = async (minMs: number) => {
+        try {
+            await invoke('set_min_recording_ms', { minMs });
+            setMinDurationMs(minMs);
+        } catch (error) {
+            console.error('Failed to save minimum duration:', error);
+            toast.error(t('Failed to save accidental tap settings'));
+        }
+    };
+
+    const handleSetMinLevelDb = async (levelDb: number | null) => {
+        try {
+            await invoke('set_min_recording_level_db', { levelDb });
+            setMinLevelDb(levelDb);
+        } catch (error) {
+            console.error('Failed to save minimum level:', error);
+            toast.error(t('Failed to save accidental tap settings'));
+        }
+    };
+
+    return {
+        minDurationMs,
+        setMinDurationMs: handleSetMinDurationMs,
+        minLevelDb,
+        setMinLevelDb: handleSetMinLevelDb,
+    };
+};
@@ -0,0 +1,160 @@
+import { SettingsUI } from '@/components/settings-ui';
+import { Typography } from '@/components/typography';
+import { Button } from '@/components/button';
+import { Cpu, FolderOpen, MemoryStick, X } from 'lucide-react';
+import {
+    Select,
+    SelectContent,
+    SelectItem,
+    SelectTrigger,
+    SelectValue,
+} from '@/components/select';
+import { useTranslation } from '@/i18n';
+import { open } from '@tauri-apps/plugin-dialog';
+import { useEngineState } from './hooks/use-engine-state';
+import {
+    EngineStatus,
+    useEngineStatusState,
+} from './hooks/use-engine-status-state';
+
+const STATUS_LABELS: Record<EngineStatus, string> = {
+    loading: 'Loading...',
+    ready: 'Loaded in memory',
+    unloaded: 'Not loaded',
+};
+
+const IDLE_UNLOAD_OPTIONS = [
+    { value: '0', label: 'Never' },
+    { value: '5', label: '5 minutes' },
+    { value: '15', label: '15 minutes' },
+    { value: '30', label: '30 minutes' },
+    { value: '60', label: '1 hour' },
+];
+
+export const EngineSettings = () => {
+    const { t } = useTranslation();
+    const { engines, engine, setEngine, modelPath, setModelPath } =
+        useEngineState();
+    const { status, idleUnloadMinutes, setIdleUnloadMinutes, unloadEngine } =
+        useEngineStatusState();
+
+    const handleChooseModelPath = async () => {
+        const path = await open({ directory: true, multiple: false });
+        if (typeof path === 'string') {
+            setModelPath(path);
+        }
+    };
+
+    return (
+        <>
+            <SettingsUI.Item>
+                <SettingsUI.Description>
+                    <Typography.Title className="flex items-center gap-2">
+                        <Cpu className="w-4 h-4 text-zinc-400" />
+                        {t('Transcription engine')}
+                    </Typography.Title>
+                    <Typography.Paragraph>
+                        {t(
+                            'Choose the speech recognition model architecture. The new engine is loaded on the next transcription.'
+                        )}
+                    </Typography.Paragraph>
+                </SettingsUI.Description>
+                <Select value={engine} onValueChange={setEngine}>
+                    <SelectTrigger
+                        className="w-[220px]"
+                        data-testid="engine-select"
+                    >
+                        <SelectValue />
+                    </SelectTrigger>
+                    <SelectContent>
+                        {engines.map((item) => (
+                            <SelectItem key={item.id} value={item.id}>
+                                {item.label}
+                            </SelectItem>
+                        ))}
+                    </SelectContent>
+                </Select>
+            </SettingsUI.Item>
+            <SettingsUI.Separator />
+            <SettingsUI.Item>
+                <SettingsUI.Description>
+                    <Typography.Title className="flex items-center gap-2">
+                        <FolderOpen className="w-4 h-4 text-zinc-400" />
+                        {t('Model directory')}
+                    </Typography.Title>
+                    <Typography.Paragraph>
+                        {modelPath ?? t('Uses the model bundled with Murmure.')}
+                    </Typography.Paragraph>
+                </SettingsUI.Description>
+                <div className="flex items-center gap-2">
+                    {modelPath != null && (
+                        <Button
+                            variant="ghost"
+                            size="icon"
+                            onClick={() => setModelPath(null)}
+                            aria-label={t('Use bundled model')}
+                        >
+                            <X className="w-4 h-4" />
+                        </Button>
+                    )}
+                    <Button
+                        variant="outline"
+                        onClick={handleChooseModelPath}
+                        data-testid="engine-model-path-button"
+                    >
+                        {t('Choose folder')}
+                    </Button>
+                </div>
+            </SettingsUI.Item>
+            <SettingsUI.Separator />
+            <SettingsUI.Item>
+                <SettingsUI.Description>
+                    <Typography.Title className="flex items-center gap-2">
+                        <MemoryStick className="w-4 h-4 text-zinc-400" />
+                        {t('Unload model when idle')}
+                    </Typography.Title>
+                    <Typography.Paragraph>
+                        {t(
+                            'Free the memory used by the model after a period without dictation. It is loaded again on the next recording.'
+                        )}
+                    </Typography.Paragraph>
+                    <Typography.Paragraph data-testid="engine-status">
+                        {t(STATUS_LABELS[status])}
+                    </Typography.Paragraph>
+                </SettingsUI.Description>
+                <div className="flex items-center gap-2">
+                    <Button
+                        variant="outline"
+                        onClick={unloadEngine}
+                        disabled={status !== 'ready'}
+                    >
+                        {t('Unload now')}
+                    </Button>
+                    <Select
+                        value={String(idleUnloadMinutes)}
+                        onValueChange={(value) =>
+                            setIdleUnloadMinutes(Number(value))
+                        }
+                    >
+                        <SelectTrigger
+                            className="w-[140px]"
+                            data-testid="engine-idle-unload-select"
+                        >
+                            <SelectValue />
+                        </SelectTrigger>
+                        <SelectContent>
+                            {IDLE_UNLOAD_OPTIONS.map((option) => (
+                                <SelectItem
+                                    key={option.value}
+                                    value={option.value}
+                                >
+                                    {t(option.label)}
+                                </SelectItem>
+                            ))}
+                        </SelectContent>
+                    </Select>
+                </div>
+            </SettingsUI.Item>
+        </>
+    );
+};
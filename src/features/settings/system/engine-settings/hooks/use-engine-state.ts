@@ -0,0 +1,42 @@
+import { invoke } from '@tauri-apps/api/core';
+import { useEffect, useState } from 'react';
+import { toast } from 'react-toastify';
+
+export interface EngineInfo {
+    id: string;
+    label: string;
+}
+
+export const useEngineState = () => {
+    const [engines, setEngines] = useState<EngineInfo[]>([]);
+    const [engine, setEngine] = useState('parakeet');
+    const [modelPath, setModelPath] = useState<string | null>(null);
+
+    useEffect(() => {
+        invoke<EngineInfo[]>('get_available_engines').then(setEngines);
+        invoke<string>('get_engine').then(setEngine);
+        invoke<string | null>('get_engine_model_path').then(setModelPath);
+    }, []);
+
+    return {
+        engines,
+        engine,
+        setEngine: async (id: string) => {
+            try {
+                await invoke('set_engine', { engine: id });
+                setEngine(id);
+            } catch (error) {
+                toast.error(String(error));
+            }
+        },
+        modelPath,
+        setModelPath: async (path: string | null) => {
+            try {
+                await invoke('set_engine_model_path', { path });
+                setModelPath(path);
+            } catch (error) {
+                toast.error(String(error));
+            }
+        },
+    };
+};
@@ -0,0 +1,33 @@
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { useEffect, useState } from 'react';
+
+export type EngineStatus = 'loading' | 'ready' | 'unloaded';
+
+export const useEngineStatusState = () => {
+    const [status, setStatus] = useState<EngineStatus>('unloaded');
+    const [idleUnloadMinutes, setIdleUnloadMinutes] = useState(0);
+
+    useEffect(() => {
+        invoke<EngineStatus>('get_engine_status').then(setStatus);
+        invoke<number>('get_engine_idle_unload_minutes').then(
+            setIdleUnloadMinutes
+        );
+        const unlistenPromise = listen<EngineStatus>('engine-status', (event) =>
+            setStatus(event.payload)
+        );
+        return () => {
+            unlistenPromise.then((unlisten) => unlisten());
+        };
+    }, []);
+
+    return {
+        status,
+        idleUnloadMinutes,
+        setIdleUnloadMinutes: (minutes: number) => {
+            setIdleUnloadMinutes(minutes);
+            invoke('set_engine_idle_unload_minutes', { minutes });
+        },
+        unloadEngine: () => invoke('unload_engine'),
+    };
+};
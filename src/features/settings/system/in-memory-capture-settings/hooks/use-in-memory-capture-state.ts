@@ -0,0 +1,29 @@
+import { invoke } from '@tauri-apps/api/core';
+import { useState, useEffect } from 'react';
+
+export const useInMemoryCaptureState = () => {
+    const [inMemoryCapture, setInMemoryCapture] = useState<boolean>(false);
+
+    useEffect(() => {
+        invoke<boolean>('get_in_memory_capture')
+            .then(setInMemoryCapture)
+            .catch((error) =>
+                console.error('Failed to load in-memory capture state:', error)
+            );
+    }, []);
+
+    const handleSetInMemoryCapture = async (enabled: boolean) => {
+        try {
+            setInMemoryCapture(enabled);
+            await invoke('set_in_memory_capture', { enabled });
+        } catch (error) {
+            console.error('Failed to set in-memory capture:', error);
+            setInMemoryCapture(!enabled);
+        }
+    };
+
+    return {
+        inMemoryCapture,
+        setInMemoryCapture: handleSetInMemoryCapture,
+    };
+};
@@ -0,0 +1,32 @@
+import { SettingsUI } from '@/components/settings-ui';
+import { Typography } from '@/components/typography';
+import { Switch } from '@/components/switch';
+import { HardDrive } from 'lucide-react';
+import { useInMemoryCaptureState } from './hooks/use-in-memory-capture-state';
+import { useTranslation } from '@/i18n';
+
+export const InMemoryCaptureSettings = () => {
+    const { inMemoryCapture, setInMemoryCapture } = useInMemoryCaptureState();
+    const { t } = useTranslation();
+
+    return (
+        <SettingsUI.Item>
+            <SettingsUI.Description>
+                <Typography.Title className="flex items-center gap-2">
+                    <HardDrive className="w-4 h-4 text-zinc-400" />
+                    {t('Keep recordings in memory')}
+                </Typography.Title>
+                <Typography.Paragraph>
+                    {t(
+                        'Never write recordings to a temporary file on disk. Long recordings use more memory.'
+                    )}
+                </Typography.Paragraph>
+            </SettingsUI.Description>
+            <Switch
+                checked={inMemoryCapture}
+                onCheckedChange={setInMemoryCapture}
+                data-testid="in-memory-capture-switch"
+            />
+        </SettingsUI.Item>
+    );
+};
@@ -0,0 +1,29 @@
+import { invoke } from '@tauri-apps/api/core';
+import { useState, useEffect } from 'react';
+
+export const useLivePreviewState = () => {
+    const [livePreview, setLivePreview] = useState<boolean>(false);
+
+    useEffect(() => {
+        invoke<boolean>('get_live_preview_enabled')
+            .then(setLivePreview)
+            .catch((error) =>
+                console.error('Failed to load live preview state:', error)
+            );
+    }, []);
+
+    const handleSetLivePreview = async (enabled: boolean) => {
+        try {
+            setLivePreview(enabled);
+            await invoke('set_live_preview_enabled', { enabled });
+        } catch (error) {
+            console.error('Failed to set live preview:', error);
+            setLivePreview(!enabled);
+        }
+    };
+
+    return {
+        livePreview,
+        setLivePreview: handleSetLivePreview,
+    };
+};
@@ -0,0 +1,32 @@
+import { SettingsUI } from '@/components/settings-ui';
+import { Typography } from '@/components/typography';
+import { Switch } from '@/components/switch';
+import { TextCursorInput } from 'lucide-react';
+import { useLivePreviewState } from './hooks/use-live-preview-state';
+import { useTranslation } from '@/i18n';
+
+export const LivePreviewSettings = () => {
+    const { livePreview, setLivePreview } = useLivePreviewState();
+    const { t } = useTranslation();
+
+    return (
+        <SettingsUI.Item>
+            <SettingsUI.Description>
+                <Typography.Title className="flex items-center gap-2">
+                    <TextCursorInput className="w-4 h-4 text-zinc-400" />
+                    {t('Live preview')}
+                </Typography.Title>
+                <Typography.Paragraph>
+                    {t(
+                        'Show a partial transcription in the overlay while you speak. Uses more CPU during recording.'
+                    )}
+                </Typography.Paragraph>
+            </SettingsUI.Description>
+            <Switch
+                checked={livePreview}
+                onCheckedChange={setLivePreview}
+                data-testid="live-preview-switch"
+            />
+        </SettingsUI.Item>
+    );
+};
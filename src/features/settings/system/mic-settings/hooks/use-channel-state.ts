@@ -0,0 +1,59 @@
+import { invoke } from '@tauri-apps/api/core';
+import { useEffect, useState } from 'react';
+
+type ChannelStrategy =
+    | 'average'
+    | 'left'
+    | 'right'
+    | 'loudest'
+    | { channel: number };
+
+function toValue(strategy: ChannelStrategy): string {
+    return typeof strategy === 'string'
+        ? strategy
+        : `channel:${strategy.channel}`;
+}
+
+function fromValue(value: string): ChannelStrategy {
+    if (value.startsWith('channel:')) {
+        return { channel: Number(value.slice('channel:'.length)) };
+    }
+    return value as ChannelStrategy;
+}
+
+// `micId` is null for the system default microphone.
+export const useChannelState = (micId: string | null) => {
+    const [channelCount, setChannelCount] = useState<number>(1);
+    const [strategy, setStrategy] = useState<string>('average');
+
+    useEffect(() => {
+        invoke<number>('get_mic_channel_count', { micId })
+            .then(setChannelCount)
+            .catch(() => setChannelCount(1));
+        invoke<ChannelStrategy>('get_mic_channel_strategy', { micId })
+            .then((value) => setStrategy(toValue(value)))
+            .catch((error) =>
+                console.error('Failed to load channel strategy:', error)
+            );
+    }, [micId]);
+
+    const handleSetStrategy = async (value: string) => {
+        const previous = strategy;
+        try {
+            setStrategy(value);
+            await invoke('set_mic_channel_strategy', {
+                micId,
+                strategy: fromValue(value),
+            });
+        } catch (error) {
+            console.error('Failed to set channel strategy:', error);
+            setStrategy(previous);
+        }
+    };
+
+    return {
+        channelCount,
+        strategy,
+        setStrategy: handleSetStrategy,
+    };
+};
@@ -1,5 +1,6 @@
 import { useCallback, useEffect, useRef, useState } from 'react';
 import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
 import { useTranslation } from '@/i18n';
 import { toast } from 'react-toastify';
 
@@ -316,6 +317,20 @@ export function useMicState() {
         };
     }, [refreshMicList]);
 
+    useEffect(() => {
+        const unlistenPromises = [
+            'mic-added',
+            'mic-removed',
+            'mic-fallback',
+        ].map((event) => listen(event, () => void refreshMicList()));
+
+        return () => {
+            for (const unlistenPromise of unlistenPromises) {
+                unlistenPromise.then((unlisten) => unlisten());
+            }
+        };
+    }, [refreshMicList]);
+
     async function setMic(id: string) {
         setCurrentMic(id);
 
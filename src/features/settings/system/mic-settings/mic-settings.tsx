@@ -10,6 +10,7 @@ import {
 } from '@/components/select';
 import { useTranslation } from '@/i18n';
 import { useMicState } from './hooks/use-mic-state';
+import { useChannelState } from './hooks/use-channel-state';
 
 export const MicSettings = () => {
     const { t } = useTranslation();
@@ -23,6 +24,9 @@ export const MicSettings = () => {
         preferredMicLabel,
         activeMicFallbackLabel,
     } = useMicState();
+    const { channelCount, strategy, setStrategy } = useChannelState(
+        currentMic === 'automatic' ? null : currentMic
+    );
 
     return (
         <SettingsUI.Item>
@@ -86,6 +90,44 @@ export const MicSettings = () => {
                         </span>
                     </Typography.Paragraph>
                 )}
+                {channelCount > 1 && (
+                    <Select value={strategy} onValueChange={setStrategy}>
+                        <SelectTrigger
+                            className="w-[240px] mt-2"
+                            data-testid="mic-channel-select"
+                        >
+                            <SelectValue />
+                        </SelectTrigger>
+                        <SelectContent>
+                            <SelectItem value="average">
+                                {t('Average of all channels')}
+                            </SelectItem>
+                            <SelectItem value="left">
+                                {t('Left channel')}
+                            </SelectItem>
+                            <SelectItem value="right">
+                                {t('Right channel')}
+                            </SelectItem>
+                            <SelectItem value="loudest">
+                                {t('Loudest channel')}
+                            </SelectItem>
+                            {channelCount > 2 &&
+                                Array.from(
+                                    { length: channelCount },
+                                    (_, index) => (
+                                        <SelectItem
+                                            key={index}
+                                            value={`channel:${index}`}
+                                        >
+                                            {t('Channel {{number}}', {
+                                                number: index + 1,
+                                            })}
+                                        </SelectItem>
+                                    )
+                                )}
+                        </SelectContent>
+                    </Select>
+                )}
             </div>
         </SettingsUI.Item>
     );
@@ -0,0 +1,54 @@
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { useCallback, useEffect, useState } from 'react';
+import { toast } from 'react-toastify';
+
+export interface ModelInfo {
+    id: string;
+    path: string;
+    engine: string | null;
+    bundled: boolean;
+    size_bytes: number;
+    error: string | null;
+}
+
+export const useModelState = () => {
+    const [models, setModels] = useState<ModelInfo[]>([]);
+    const [activeModel, setActiveModel] = useState<string | null>(null);
+    const [isBusy, setIsBusy] = useState(false);
+
+    const refresh = useCallback(async () => {
+        setModels(await invoke<ModelInfo[]>('get_installed_models'));
+        setActiveModel(await invoke<string>('get_active_model'));
+    }, []);
+
+    useEffect(() => {
+        refresh();
+        const unlistenPromise = listen('model-changed', refresh);
+        return () => {
+            unlistenPromise.then((unlisten) => unlisten());
+        };
+    }, [refresh]);
+
+    const run = async (action: () => Promise<unknown>) => {
+        setIsBusy(true);
+        try {
+            await action();
+            await refresh();
+        } catch (error) {
+            toast.error(String(error));
+        } finally {
+            setIsBusy(false);
+        }
+    };
+
+    return {
+        models,
+        activeModel,
+        isBusy,
+        selectModel: (id: string) =>
+            run(() => invoke('set_active_model', { id })),
+        importModel: (archivePath: string) =>
+            run(() => invoke('import_model', { archivePath })),
+    };
+};
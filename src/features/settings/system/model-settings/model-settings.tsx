@@ -0,0 +1,82 @@
+import { SettingsUI } from '@/components/settings-ui';
+import { Typography } from '@/components/typography';
+import { Button } from '@/components/button';
+import { Package } from 'lucide-react';
+import {
+    Select,
+    SelectContent,
+    SelectItem,
+    SelectTrigger,
+    SelectValue,
+} from '@/components/select';
+import { useTranslation } from '@/i18n';
+import { open } from '@tauri-apps/plugin-dialog';
+import { useModelState } from './hooks/use-model-state';
+
+export const ModelSettings = () => {
+    const { t } = useTranslation();
+    const { models, activeModel, isBusy, selectModel, importModel } =
+        useModelState();
+
+    const handleImportModel = async () => {
+        const archivePath = await open({
+            directory: false,
+            multiple: false,
+            title: t('Select model archive'),
+            filters: [{ name: 'Model archives', extensions: ['gz', 'tgz'] }],
+        });
+        if (typeof archivePath === 'string') {
+            importModel(archivePath);
+        }
+    };
+
+    return (
+        <SettingsUI.Item>
+            <SettingsUI.Description>
+                <Typography.Title className="flex items-center gap-2">
+                    <Package className="w-4 h-4 text-zinc-400" />
+                    {t('Model')}
+                </Typography.Title>
+                <Typography.Paragraph>
+                    {t(
+                        'Import a model from a .tar.gz archive and choose the one used for transcription.'
+                    )}
+                </Typography.Paragraph>
+            </SettingsUI.Description>
+            <div className="flex items-center gap-2">
+                <Select
+                    value={activeModel ?? undefined}
+                    onValueChange={selectModel}
+                    disabled={isBusy}
+                >
+                    <SelectTrigger
+                        className="w-[220px]"
+                        data-testid="model-select"
+                    >
+                        <SelectValue />
+                    </SelectTrigger>
+                    <SelectContent>
+                        {models.map((model) => (
+                            <SelectItem
+                                key={model.id}
+                                value={model.id}
+                                disabled={model.error != null}
+                                title={model.error ?? undefined}
+                            >
+                                {model.id}
+                            </SelectItem>
+                        ))}
+                    </SelectContent>
+                </Select>
+                <Button
+                    variant="outline"
+                    onClick={handleImportModel}
+                    disabled={isBusy}
+                    data-testid="model-import-button"
+                >
+                    {isBusy ? t('Please wait...') : t('Import')}
+                </Button>
+            </div>
+        </SettingsUI.Item>
+    );
+};
@@ -0,0 +1,28 @@
+import { invoke } from '@tauri-apps/api/core';
+import { useEffect, useState } from 'react';
+import { toast } from 'react-toastify';
+import { useTranslation } from '@/i18n';
+
+export const useRecentRecordingsState = () => {
+    const [retainedRecordings, setRetainedRecordings] = useState<number>(0);
+    const { t } = useTranslation();
+
+    useEffect(() => {
+        invoke<number>('get_retained_recordings').then(setRetainedRecordings);
+    }, []);
+
+    const handleSetRetainedRecordings = async (count: number) => {
+        try {
+            await invoke('set_retained_recordings', { count });
+            setRetainedRecordings(count);
+        } catch (error) {
+            console.error('Failed to save retained recordings:', error);
+            toast.error(t('Failed to save recent recordings setting'));
+        }
+    };
+
+    return {
+        retainedRecordings,
+        setRetainedRecordings: handleSetRetainedRecordings,
+    };
+};
@@ -0,0 +1,59 @@
+import { SettingsUI } from '@/components/settings-ui';
+import { Typography } from '@/components/typography';
+import { RotateCcw } from 'lucide-react';
+import {
+    Select,
+    SelectContent,
+    SelectItem,
+    SelectTrigger,
+    SelectValue,
+} from '@/components/select';
+import { useTranslation } from '@/i18n';
+import { useRecentRecordingsState } from './hooks/use-recent-recordings-state';
+
+const RETAINED_RECORDINGS_OPTIONS = [
+    { value: '0', label: 'Off' },
+    { value: '1', label: 'Last recording' },
+    { value: '3', label: '3 recordings' },
+    { value: '5', label: '5 recordings' },
+];
+
+export const RecentRecordingsSettings = () => {
+    const { t } = useTranslation();
+    const { retainedRecordings, setRetainedRecordings } =
+        useRecentRecordingsState();
+
+    return (
+        <SettingsUI.Item>
+            <SettingsUI.Description>
+                <Typography.Title className="flex items-center gap-2">
+                    <RotateCcw className="w-4 h-4 text-zinc-400" />
+                    {t('Reprocess recent recordings')}
+                </Typography.Title>
+                <Typography.Paragraph>
+                    {t(
+                        'Keep the last recordings in memory to transcribe them again in another mode. Nothing is written to disk and they are lost when Murmure closes.'
+                    )}
+                </Typography.Paragraph>
+            </SettingsUI.Description>
+            <Select
+                value={String(retainedRecordings)}
+                onValueChange={(value) => setRetainedRecordings(Number(value))}
+            >
+                <SelectTrigger
+                    className="w-[150px]"
+                    data-testid="retained-recordings-select"
+                >
+                    <SelectValue />
+                </SelectTrigger>
+                <SelectContent>
+                    {RETAINED_RECORDINGS_OPTIONS.map((option) => (
+                        <SelectItem key={option.value} value={option.value}>
+                            {t(option.label)}
+                        </SelectItem>
+                    ))}
+                </SelectContent>
+            </Select>
+        </SettingsUI.Item>
+    );
+};
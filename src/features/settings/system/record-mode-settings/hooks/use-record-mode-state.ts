@@ -1,14 +1,14 @@
 import { invoke } from '@tauri-apps/api/core';
 import { useEffect, useState } from 'react';
 
-export type RecordMode = 'push_to_talk' | 'toggle_to_talk';
+export type RecordMode = 'push_to_talk' | 'toggle_to_talk' | 'hands_free';
 
 export const useRecordModeState = () => {
     const [recordMode, setRecordMode] = useState<RecordMode>('push_to_talk');
 
     useEffect(() => {
         invoke<RecordMode>('get_record_mode').then((mode) => {
-            if (['push_to_talk', 'toggle_to_talk'].includes(mode)) {
+            if (['push_to_talk', 'toggle_to_talk', 'hands_free'].includes(mode)) {
                 setRecordMode(mode);
             }
         });
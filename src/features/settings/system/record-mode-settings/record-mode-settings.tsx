@@ -17,6 +17,7 @@ import {
 const SUPPORTED_RECORD_MODE: { key: RecordMode; label: string }[] = [
     { key: 'push_to_talk', label: 'Push to talk' },
     { key: 'toggle_to_talk', label: 'Toggle to talk' },
+    { key: 'hands_free', label: 'Hands-free' },
 ];
 
 export const RecordModeSettings = () => {
@@ -0,0 +1,64 @@
+import { invoke } from '@tauri-apps/api/core';
+import { useEffect, useState } from 'react';
+import { toast } from 'react-toastify';
+import { useTranslation } from '@/i18n';
+
+export type RecordingLimitAction = 'stop' | 'rollover';
+
+export const useRecordingLimitState = () => {
+    const [limitSecs, setLimitSecs] = useState<number>(300);
+    const [warningSecs, setWarningSecs] = useState<number>(15);
+    const [action, setAction] = useState<RecordingLimitAction>('stop');
+    const { t } = useTranslation();
+
+    useEffect(() => {
+        invoke<number>('get_recording_limit_secs').then(setLimitSecs);
+        invoke<number>('get_recording_limit_warning_secs').then(
+            setWarningSecs
+        );
+        invoke<RecordingLimitAction>('get_recording_limit_action').then(
+            setAction
+        );
+    }, []);
+
+    const handleSetLimitSecs = async (secs: number) => {
+        try {
+            await invoke('set_recording_limit_secs', { limitSecs: secs });
+            setLimitSecs(secs);
+        } catch (error) {
+            console.error('Failed to save recording limit:', error);
+            toast.error(t('Failed to save recording limit'));
+        }
+    };
+
+    const handleSetWarningSecs = async (secs: number) => {
+        try {
+            await invoke('set_recording_limit_warning_secs', {
+                warningSecs: secs,
+            });
+            setWarningSecs(secs);
+        } catch (error) {
+            console.error('Failed to save recording limit warning:', error);
+            toast.error(t('Failed to save recording limit'));
+        }
+    };
+
+    const handleSetAction = async (value: RecordingLimitAction) => {
+        try {
+            await invoke('set_recording_limit_action', { action: value });
+            setAction(value);
+        } catch (error) {
+            console.error('Failed to save recording limit action:', error);
+            toast.error(t('Failed to save recording limit'));
+        }
+    };
+
+    return {
+        limitSecs,
+        setLimitSecs: handleSetLimitSecs,
+        warningSecs,
+        setWarningSecs: handleSetWarningSecs,
+        action,
+        setAction: handleSetAction,
+    };
+};
@@ -0,0 +1,118 @@
+import { SettingsUI } from '@/components/settings-ui';
+import { Typography } from '@/components/typography';
+import { Timer } from 'lucide-react';
+import {
+    Select,
+    SelectContent,
+    SelectItem,
+    SelectTrigger,
+    SelectValue,
+} from '@/components/select';
+import { useTranslation } from '@/i18n';
+import {
+    RecordingLimitAction,
+    useRecordingLimitState,
+} from './hooks/use-recording-limit-state';
+
+const LIMIT_OPTIONS = [
+    { value: '60', label: '1 minute' },
+    { value: '120', label: '2 minutes' },
+    { value: '300', label: '5 minutes' },
+    { value: '600', label: '10 minutes' },
+    { value: '1800', label: '30 minutes' },
+    { value: '3600', label: '1 hour' },
+];
+
+const WARNING_OPTIONS = [
+    { value: '0', label: 'No countdown' },
+    { value: '5', label: '5 seconds' },
+    { value: '15', label: '15 seconds' },
+    { value: '30', label: '30 seconds' },
+];
+
+export const RecordingLimitSettings = () => {
+    const { t } = useTranslation();
+    const {
+        limitSecs,
+        setLimitSecs,
+        warningSecs,
+        setWarningSecs,
+        action,
+        setAction,
+    } = useRecordingLimitState();
+
+    return (
+        <SettingsUI.Item>
+            <SettingsUI.Description>
+                <Typography.Title className="flex items-center gap-2">
+                    <Timer className="w-4 h-4 text-zinc-400" />
+                    {t('Recording limit')}
+                </Typography.Title>
+                <Typography.Paragraph>
+                    {t(
+                        'Longest recording, with a countdown in the overlay. When it is reached, stop, or paste what was said so far and keep recording.'
+                    )}
+                </Typography.Paragraph>
+            </SettingsUI.Description>
+            <div className="flex items-center gap-2">
+                <Select
+                    value={String(limitSecs)}
+                    onValueChange={(value) => setLimitSecs(Number(value))}
+                >
+                    <SelectTrigger
+                        className="w-[110px]"
+                        title={t('Recording limit')}
+                        data-testid="recording-limit-select"
+                    >
+                        <SelectValue />
+                    </SelectTrigger>
+                    <SelectContent>
+                        {LIMIT_OPTIONS.map((option) => (
+                            <SelectItem key={option.value} value={option.value}>
+                                {t(option.label)}
+                            </SelectItem>
+                        ))}
+                    </SelectContent>
+                </Select>
+                <Select
+                    value={String(warningSecs)}
+                    onValueChange={(value) => setWarningSecs(Number(value))}
+                >
+                    <SelectTrigger
+                        className="w-[130px]"
+                        title={t('Countdown')}
+                        data-testid="recording-limit-warning-select"
+                    >
+                        <SelectValue />
+                    </SelectTrigger>
+                    <SelectContent>
+                        {WARNING_OPTIONS.map((option) => (
+                            <SelectItem key={option.value} value={option.value}>
+                                {t(option.label)}
+                            </SelectItem>
+                        ))}
+                    </SelectContent>
+                </Select>
+                <Select
+                    value={action}
+                    onValueChange={(value) =>
+                        setAction(value as RecordingLimitAction)
+                    }
+                >
+                    <SelectTrigger
+                        className="w-[160px]"
+                        data-testid="recording-limit-action-select"
+                    >
+                        <SelectValue />
+                    </SelectTrigger>
+                    <SelectContent>
+                        <SelectItem value="stop">{t('Stop')}</SelectItem>
+                        <SelectItem value="rollover">
+                            {t('Paste and continue')}
+                        </SelectItem>
+                    </SelectContent>
+                </Select>
+            </div>
+        </SettingsUI.Item>
+    );
+};
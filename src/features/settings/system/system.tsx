@@ -8,11 +8,20 @@ import { CopyToClipboardSettings } from './copy-to-clipboard-settings/copy-to-cl
 import { PasteMethodSettings } from './paste-method-settings/paste-method-settings';
 import { AutoSendEnterSettings } from './auto-send-enter-settings/auto-send-enter-settings';
 import { HistorySettings } from './history-settings/history-settings';
+import { AudioArchiveSettings } from './audio-archive-settings/audio-archive-settings';
+import { DecodingSettings } from './decoding-settings/decoding-settings';
+import { EngineSettings } from './engine-settings/engine-settings';
+import { ModelSettings } from './model-settings/model-settings';
+import { LivePreviewSettings } from './live-preview-settings/live-preview-settings';
+import { InMemoryCaptureSettings } from './in-memory-capture-settings/in-memory-capture-settings';
+import { RecentRecordingsSettings } from './recent-recordings-settings/recent-recordings-settings';
 import { LanguageSettings } from './language-settings/language-settings';
 import { SoundSettings } from './sound-settings/sound-settings';
 import { MicSettings } from './mic-settings/mic-settings';
 import { useTranslation } from '@/i18n';
 import { RecordModeSettings } from '@/features/settings/system/record-mode-settings/record-mode-settings.tsx';
+import { RecordingLimitSettings } from './recording-limit-settings/recording-limit-settings';
+import { DiscardSettings } from './discard-settings/discard-settings';
 
 import { LogLevelSettings } from './log-level-settings/log-level-settings';
 
@@ -40,6 +49,16 @@ export const System = () => {
                         <SettingsUI.Separator />
                         <RecordModeSettings />
                         <SettingsUI.Separator />
+                        <RecordingLimitSettings />
+                        <SettingsUI.Separator />
+                        <DiscardSettings />
+                        <SettingsUI.Separator />
+                        <DecodingSettings />
+                        <SettingsUI.Separator />
+                        <EngineSettings />
+                        <SettingsUI.Separator />
+                        <ModelSettings />
+                        <SettingsUI.Separator />
                         <PasteMethodSettings />
                         <SettingsUI.Separator />
                         <AutoSendEnterSettings />
@@ -48,10 +67,18 @@ export const System = () => {
                         <SettingsUI.Separator />
                         <HistorySettings />
                         <SettingsUI.Separator />
+                        <InMemoryCaptureSettings />
+                        <SettingsUI.Separator />
+                        <RecentRecordingsSettings />
+                        <SettingsUI.Separator />
+                        <AudioArchiveSettings />
+                        <SettingsUI.Separator />
                         <SoundSettings />
                         <SettingsUI.Separator />
                         <OverlaySettings />
                         <SettingsUI.Separator />
+                        <LivePreviewSettings />
+                        <SettingsUI.Separator />
                         <APISettings />
                         <SettingsUI.Separator />
                         <LogLevelSettings />
@@ -14,7 +14,10 @@ export const Overlay = () => {
     const [feedback, setFeedback] = useState<string | null>(null);
     const [isError, setIsError] = useState(false);
     const [isCancelled, setIsCancelled] = useState(false);
+    const [isPaused, setIsPaused] = useState(false);
+    const [limitCountdown, setLimitCountdown] = useState<number | null>(null);
     const [pasteMode, setPasteMode] = useState<PasteMode>(null);
+    const [partialText, setPartialText] = useState('');
     const [recordingMode, setRecordingMode] =
         useState<RecordingMode>('standard');
     const { level } = useLevelState();
@@ -63,22 +66,45 @@ export const Overlay = () => {
             if (mode === 'llm' || mode === 'command' || mode === 'standard') {
                 setRecordingMode(mode);
             }
+            setPartialText('');
+            setIsPaused(false);
+            setLimitCountdown(null);
+        });
+        const unlistenPartialPromise = listen<string>('transcription-partial', (event) => {
+            setPartialText(event.payload);
+        });
+        const unlistenPausePromise = listen<boolean>('recording-paused', (event) => {
+            setIsPaused(event.payload);
+        });
+        const unlistenLimitWarningPromise = listen<number>('recording-limit-warning', (event) => {
+            setLimitCountdown(event.payload);
+        });
+        const unlistenRolloverPromise = listen('recording-rollover', () => {
+            setLimitCountdown(null);
         });
         const unlistenShowPromise = listen('show-overlay', () => {
             setHasAudio(false);
+            setPartialText('');
+            setIsPaused(false);
+            setLimitCountdown(null);
             if (audioTimerRef.current) {
                 clearTimeout(audioTimerRef.current);
                 audioTimerRef.current = null;
             }
         });
-        const unlistenCancelPromise = listen('recording-cancelled', () => {
+        // Discarded taps get the same animation as a cancelled recording
+        const showCancelled = () => {
             if (cancelTimerRef.current) clearTimeout(cancelTimerRef.current);
             setIsCancelled(true);
+            setIsPaused(false);
+            setLimitCountdown(null);
             cancelTimerRef.current = setTimeout(() => {
                 setIsCancelled(false);
                 cancelTimerRef.current = null;
             }, 700);
-        });
+        };
+        const unlistenCancelPromise = listen('recording-cancelled', showCancelled);
+        const unlistenDiscardPromise = listen('recording-discarded', showCancelled);
         const unlistenPasteModePromise = listen<string>('overlay-paste-mode', (event) => {
             const mode = event.payload as PasteMode;
             if (pasteTimerRef.current) clearTimeout(pasteTimerRef.current);
@@ -94,8 +120,13 @@ export const Overlay = () => {
             unlistenSettingsPromise.then((unlisten) => unlisten());
             unlistenErrorPromise.then((unlisten) => unlisten());
             unlistenModePromise.then((unlisten) => unlisten());
+            unlistenPartialPromise.then((unlisten) => unlisten());
+            unlistenPausePromise.then((unlisten) => unlisten());
+            unlistenLimitWarningPromise.then((unlisten) => unlisten());
+            unlistenRolloverPromise.then((unlisten) => unlisten());
             unlistenShowPromise.then((unlisten) => unlisten());
             unlistenCancelPromise.then((unlisten) => unlisten());
+            unlistenDiscardPromise.then((unlisten) => unlisten());
             unlistenPasteModePromise.then((unlisten) => unlisten());
         };
     }, []);
@@ -158,6 +189,21 @@ export const Overlay = () => {
                 </span>
             );
         }
+        if (isPaused) {
+            return (
+                <span className="text-white text-[8px] flex items-center justify-center h-full animate-pulse">
+                    Paused
+                </span>
+            );
+        }
+        if (partialText !== '') {
+            // Keep the end of the text visible, it is where the speaker is
+            return (
+                <div className="flex items-center justify-end h-full px-1.5 overflow-hidden whitespace-nowrap">
+                    <span className="text-white text-[8px]">{partialText}</span>
+                </div>
+            );
+        }
         return (
             <div className={clsx('origin-center', 'h-[20px]', 'mt-1', 'p-1.5', 'overflow-hidden')}>
                 {hasAudio ? (
@@ -186,6 +232,11 @@ export const Overlay = () => {
             )}
         >
             {renderContent()}
+            {limitCountdown !== null && !isCancelled && (
+                <span className="absolute top-0 right-0.5 text-[6px] text-amber-400 tabular-nums">
+                    {limitCountdown}s
+                </span>
+            )}
         </div>
     );
 };